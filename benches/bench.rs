@@ -1,5 +1,8 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use huginn::{validation::sanitize_input, SecurityConfig};
+use huginn::{
+    validation::{sanitize_input, sanitize_into},
+    SecurityConfig,
+};
 use std::time::Duration;
 
 fn bench_sanitization(c: &mut Criterion) {
@@ -16,8 +19,33 @@ fn bench_sanitization(c: &mut Criterion) {
         b.iter(|| sanitize_input(black_box(binding.as_str()), &config))
     });
 
+    let mut buffer = String::with_capacity(binding.len());
+    group.bench_function("sanitize_into 1KB input", |b| {
+        b.iter(|| {
+            buffer.clear();
+            sanitize_into(black_box(binding.as_str()), &config, &mut buffer)
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_patterns(c: &mut Criterion) {
+    let config = SecurityConfig::default();
+    let binding = "AAAA".repeat(1000);
+
+    let mut group = c.benchmark_group("Patterns");
+    group.sample_size(500);
+    group.warm_up_time(Duration::from_secs(5));
+    group.measurement_time(Duration::from_secs(10));
+    group.noise_threshold(0.05);
+
+    group.bench_function("match patterns 1KB input", |b| {
+        b.iter(|| config.find_blocked_patterns(black_box(binding.as_str())))
+    });
+
     group.finish();
 }
 
-criterion_group!(benches, bench_sanitization);
+criterion_group!(benches, bench_sanitization, bench_patterns);
 criterion_main!(benches);
@@ -1,8 +1,9 @@
 use huginn::{
+    pattern::Regex,
     validation::{sanitize_and_validate, Validator},
+    validators::LengthValidator,
     SecurityConfig, ValidationError,
 };
-use regex::Regex;
 
 // 1. Email Validator ---------------------------------------------------------
 #[derive(Clone, Copy)]
@@ -16,9 +17,7 @@ impl Validator<String> for EmailValidator {
         if re.is_match(input) {
             Ok(input.to_string())
         } else {
-            Err(ValidationError::InvalidFormat {
-                target_type: self.target_type(),
-            })
+            Err(ValidationError::invalid_format(self.target_type()))
         }
     }
 
@@ -33,9 +32,9 @@ struct NumberValidator;
 
 impl Validator<i32> for NumberValidator {
     fn validate(&self, input: &str) -> Result<i32, ValidationError> {
-        input.parse().map_err(|_| ValidationError::InvalidFormat {
-            target_type: self.target_type(),
-        })
+        input
+            .parse()
+            .map_err(|_| ValidationError::invalid_format(self.target_type()))
     }
 
     fn target_type(&self) -> &'static str {
@@ -73,29 +72,6 @@ impl Validator<String> for PasswordValidator {
     }
 }
 
-// 4. Length Validator -------------------------------------------------------
-#[derive(Clone, Copy)]
-struct LengthValidator {
-    max: usize,
-}
-
-impl Validator<String> for LengthValidator {
-    fn validate(&self, input: &str) -> Result<String, ValidationError> {
-        if input.len() > self.max {
-            Err(ValidationError::custom(format!(
-                "Input exceeds maximum length of {} characters",
-                self.max
-            )))
-        } else {
-            Ok(input.to_string())
-        }
-    }
-
-    fn target_type(&self) -> &'static str {
-        "length"
-    }
-}
-
 fn main() {
     let base_config = SecurityConfig::default();
 
@@ -143,7 +119,7 @@ fn main() {
 
     // Length Validation
     println!("\n=== Testing Length Validation ===");
-    let length_validator = LengthValidator { max: 10 };
+    let length_validator = LengthValidator::new().with_max(10);
     let length_cases = ["short", "AAAAAAAAAAAAAAAAAAAA"];
 
     for input in length_cases {
@@ -154,7 +130,7 @@ fn main() {
 
 fn print_result<T: std::fmt::Display>(
     input: &str,
-    result: Result<huginn::validation::SanitizedInput<T>, ValidationError>,
+    result: Result<huginn::validation::SanitizedInput<'_, T>, ValidationError>,
 ) {
     match result {
         Ok(res) => println!("[OK] '{}' => {}", input, res.cleaned),
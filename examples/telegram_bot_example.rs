@@ -1,8 +1,8 @@
 use huginn::{
-    validation::{sanitize_and_validate_async, Validator},
-    SecurityConfig, ValidationError,
+    pattern::Regex, ratelimit::RateLimiter, validation::Validator, Pipeline, SecurityConfig,
+    ValidationContext, ValidationError,
 };
-use regex::Regex;
+use std::time::Duration;
 use teloxide::{prelude::*, types::Message};
 
 // 1. Username Validator for Telegram with injection protection
@@ -16,9 +16,7 @@ impl Validator<String> for UsernameValidator {
         if re.is_match(input) {
             Ok(input.to_string())
         } else {
-            Err(ValidationError::InvalidFormat {
-                target_type: self.target_type(),
-            })
+            Err(ValidationError::invalid_format(self.target_type()))
         }
     }
 
@@ -38,9 +36,7 @@ impl Validator<String> for PhoneValidator {
         if re.is_match(input) {
             Ok(input.to_string())
         } else {
-            Err(ValidationError::InvalidFormat {
-                target_type: self.target_type(),
-            })
+            Err(ValidationError::invalid_format(self.target_type()))
         }
     }
 
@@ -60,9 +56,7 @@ impl Validator<String> for CommandValidator {
         if re.is_match(input) {
             Ok(input.to_string())
         } else {
-            Err(ValidationError::InvalidFormat {
-                target_type: self.target_type(),
-            })
+            Err(ValidationError::invalid_format(self.target_type()))
         }
     }
 
@@ -81,9 +75,7 @@ impl Validator<String> for TextMessageValidator {
         // Дополнительная проверка на длину и отсутствие подозрительных последовательностей
         if input.len() > 4096 {
             // Telegram max message length
-            return Err(ValidationError::Custom {
-                message: "Message too long".to_string(),
-            });
+            return Err(ValidationError::custom("Message too long"));
         }
         Ok(input.to_string())
     }
@@ -109,13 +101,18 @@ async fn main() {
         .expect("Invalid regex pattern")
         .build();
 
+    // At most 20 messages per chat and minute, to resist flooding
+    let pipeline = Pipeline::builder(config)
+        .with_rate_limit(RateLimiter::new(20, Duration::from_secs(60)), "chat")
+        .build();
+
     let username_validator = UsernameValidator;
     let phone_validator = PhoneValidator;
     let command_validator = CommandValidator;
     let text_validator = TextMessageValidator;
 
     teloxide::repl(bot, move |bot: Bot, msg: Message| {
-        let config = config.clone();
+        let pipeline = pipeline.clone();
         let username_validator = username_validator.clone();
         let phone_validator = phone_validator.clone();
         let command_validator = command_validator.clone();
@@ -123,9 +120,13 @@ async fn main() {
 
         async move {
             if let Some(text) = msg.text() {
+                let context = ValidationContext::new().with("chat", msg.chat.id.to_string());
                 // Обработка команд
                 if text.starts_with('/') {
-                    match sanitize_and_validate_async(text, &command_validator, &config).await {
+                    match pipeline
+                        .process_async_with_context(text, &command_validator, &context)
+                        .await
+                    {
                         Ok(sanitized) => {
                             bot.send_message(
                                 msg.chat.id,
@@ -140,8 +141,9 @@ async fn main() {
                     }
                 }
                 // Обработка username
-                else if text.starts_with('@') {
-                    match sanitize_and_validate_async(&text[1..], &username_validator, &config)
+                else if let Some(username) = text.strip_prefix('@') {
+                    match pipeline
+                        .process_async_with_context(username, &username_validator, &context)
                         .await
                     {
                         Ok(sanitized) => {
@@ -157,7 +159,10 @@ async fn main() {
                         }
                     }
                 } else if text.starts_with('+') {
-                    match sanitize_and_validate_async(text, &phone_validator, &config).await {
+                    match pipeline
+                        .process_async_with_context(text, &phone_validator, &context)
+                        .await
+                    {
                         Ok(sanitized) => {
                             bot.send_message(
                                 msg.chat.id,
@@ -171,7 +176,10 @@ async fn main() {
                         }
                     }
                 } else {
-                    match sanitize_and_validate_async(text, &text_validator, &config).await {
+                    match pipeline
+                        .process_async_with_context(text, &text_validator, &context)
+                        .await
+                    {
                         Ok(sanitized) => {
                             bot.send_message(
                                 msg.chat.id,
@@ -0,0 +1,408 @@
+#![warn(missing_docs)]
+
+//! # huginn-cli
+//!
+//! Command line tool that sanitizes and validates input with huginn and
+//! prints one JSON report per input, for testing rule files and for shell
+//! pipelines.
+//!
+//! ```sh
+//! huginn check --config rules.toml --validator email < input.txt
+//! huginn check --csv --column email --validator email users.csv
+//! ```
+//!
+//! Each line of the input is checked separately unless `--whole` or `--csv`
+//! is given. The exit status is 0 if every input was accepted, 1 if any was
+//! rejected and 2 if the command could not run.
+
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use huginn::{
+    validators::{
+        CookieNameValidator, CookieValueValidator, EmailHeaderValidator, EmailValidator,
+        FilenameValidator, HeaderValueValidator, HostnameValidator, ShellArgValidator,
+        UrlValidator,
+    },
+    PatternCategory, SecurityConfig, ValidationError, Validator,
+};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::{
+    error::Error,
+    fs,
+    io::{self, BufWriter, Read, Stdout, Write},
+    process::ExitCode,
+};
+
+/// Names accepted by `--preset`
+const PRESETS: [&str; 4] = ["default", "headers", "sql", "shell"];
+
+/// Names accepted by `--validator`
+const VALIDATORS: [&str; 9] = [
+    "cookie_name",
+    "cookie_value",
+    "email",
+    "email_header",
+    "filename",
+    "header",
+    "hostname",
+    "shell_arg",
+    "url",
+];
+
+type CliResult<T> = Result<T, Box<dyn Error>>;
+
+/// Sanitizes and validates one input, returning the validated string
+type Check = Box<dyn Fn(&str) -> Result<String, ValidationError>>;
+
+/// Rules file read with `--config`
+///
+/// ```toml
+/// # Start from the default forbidden characters and blocked patterns (true by default)
+/// defaults = true
+/// # Forbid ASCII control characters except tab
+/// control_chars = true
+/// forbidden_chars = "#$"
+/// categories = ["ldap_injection", "log_injection"]
+///
+/// [[patterns]]
+/// name = "tenant_secret"
+/// pattern = "(?i)acme-internal"
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Rules {
+    defaults: bool,
+    control_chars: bool,
+    forbidden_chars: String,
+    categories: Vec<String>,
+    patterns: Vec<PatternEntry>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            defaults: true,
+            control_chars: false,
+            forbidden_chars: String::new(),
+            categories: Vec::new(),
+            patterns: Vec::new(),
+        }
+    }
+}
+
+/// Blocked pattern of a rules file, reported under its source unless named
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PatternEntry {
+    name: Option<String>,
+    pattern: String,
+}
+
+impl Rules {
+    fn into_config(self) -> CliResult<SecurityConfig> {
+        let mut builder = SecurityConfig::builder();
+        if self.defaults {
+            builder = builder
+                .with_default_forbidden_chars()
+                .with_default_blocked_patterns();
+        }
+        if self.control_chars {
+            builder = builder.with_control_chars();
+        }
+        for c in self.forbidden_chars.chars() {
+            builder = builder.add_forbidden_char(c);
+        }
+        for name in &self.categories {
+            let category = PatternCategory::from_name(name)
+                .ok_or_else(|| format!("unknown category '{}'", name))?;
+            builder = builder.with_category(category);
+        }
+        for entry in &self.patterns {
+            let name = entry.name.as_deref().unwrap_or(&entry.pattern);
+            builder = builder.add_named_blocked_pattern(name, &entry.pattern)?;
+        }
+        Ok(builder.build())
+    }
+}
+
+/// Validator passing sanitized input through unchanged
+struct Passthrough;
+
+impl Validator<String> for Passthrough {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        Ok(input.to_string())
+    }
+
+    fn target_type(&self) -> &'static str {
+        "text"
+    }
+}
+
+/// Writes one JSON report per input to stdout, counting rejections
+struct Reporter {
+    out: BufWriter<Stdout>,
+    check: Check,
+    rejected_only: bool,
+    rejected: usize,
+}
+
+impl Reporter {
+    /// Checks an input and reports it under the given location fields
+    fn check(&mut self, mut report: Map<String, Value>, input: &str) -> io::Result<()> {
+        let result = (self.check)(input);
+        if result.is_err() {
+            self.rejected += 1;
+        } else if self.rejected_only {
+            return Ok(());
+        }
+        report.insert("valid".to_string(), result.is_ok().into());
+        match result {
+            Ok(cleaned) => report.insert("cleaned".to_string(), cleaned.into()),
+            Err(error) => report.insert("error".to_string(), serde_json::to_value(&error)?),
+        };
+        serde_json::to_writer(&mut self.out, &report)?;
+        writeln!(self.out)
+    }
+}
+
+fn main() -> ExitCode {
+    let matches = cli().get_matches();
+    let result = match matches.subcommand() {
+        Some(("check", args)) => check(args),
+        Some(("presets", _)) => print_names(&PRESETS),
+        Some(("validators", _)) => print_names(&VALIDATORS),
+        _ => unreachable!("a subcommand is required"),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(err) => {
+            eprintln!("huginn: {}", err);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn cli() -> Command {
+    Command::new("huginn")
+        .about("Sanitize and validate input with huginn rules, printing JSON reports")
+        .version(env!("CARGO_PKG_VERSION"))
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("check")
+                .about("Check each line of the files, or of stdin, and print one JSON report per input")
+                .arg(
+                    Arg::new("files")
+                        .value_name("FILE")
+                        .num_args(0..)
+                        .help("Files to read; stdin if none or '-'"),
+                )
+                .arg(
+                    Arg::new("config")
+                        .short('c')
+                        .long("config")
+                        .value_name("RULES")
+                        .conflicts_with("preset")
+                        .help("TOML rules file with forbidden characters, categories and patterns"),
+                )
+                .arg(
+                    Arg::new("preset")
+                        .short('p')
+                        .long("preset")
+                        .value_name("NAME")
+                        .help("Built-in configuration: default, headers, sql or shell"),
+                )
+                .arg(
+                    Arg::new("validator")
+                        .short('v')
+                        .long("validator")
+                        .value_name("NAME")
+                        .help("Built-in validator to run after sanitizing; see `huginn validators`"),
+                )
+                .arg(
+                    Arg::new("whole")
+                        .long("whole")
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with("csv")
+                        .help("Check each file as a single input instead of line by line"),
+                )
+                .arg(
+                    Arg::new("csv")
+                        .long("csv")
+                        .action(ArgAction::SetTrue)
+                        .help("Read CSV with a header row and check its cells"),
+                )
+                .arg(
+                    Arg::new("column")
+                        .long("column")
+                        .value_name("NAME")
+                        .action(ArgAction::Append)
+                        .requires("csv")
+                        .help("Only check the CSV column with this header; repeatable"),
+                )
+                .arg(
+                    Arg::new("rejected_only")
+                        .long("rejected-only")
+                        .action(ArgAction::SetTrue)
+                        .help("Only print reports of rejected inputs"),
+                ),
+        )
+        .subcommand(Command::new("presets").about("List the built-in configurations"))
+        .subcommand(Command::new("validators").about("List the built-in validators"))
+}
+
+/// Runs `huginn check`, returning whether every input was accepted
+fn check(args: &ArgMatches) -> CliResult<bool> {
+    let config = match (
+        args.get_one::<String>("config"),
+        args.get_one::<String>("preset"),
+    ) {
+        (Some(path), _) => load_rules(path)?,
+        (None, Some(name)) => preset(name)?,
+        (None, None) => SecurityConfig::default(),
+    };
+    let mut reporter = Reporter {
+        out: BufWriter::new(io::stdout()),
+        check: checker(args.get_one::<String>("validator"), config)?,
+        rejected_only: args.get_flag("rejected_only"),
+        rejected: 0,
+    };
+
+    let files: Vec<&str> = args
+        .get_many::<String>("files")
+        .map_or_else(|| vec!["-"], |files| files.map(String::as_str).collect());
+    for source in files {
+        let text = read_source(source)?;
+        if args.get_flag("csv") {
+            let columns: Vec<&String> = args.get_many("column").into_iter().flatten().collect();
+            check_csv(&mut reporter, source, &text, &columns)?;
+        } else if args.get_flag("whole") {
+            reporter.check(location(source), &text)?;
+        } else {
+            for (index, line) in text.lines().enumerate() {
+                let mut report = location(source);
+                report.insert("line".to_string(), (index + 1).into());
+                reporter.check(report, line)?;
+            }
+        }
+    }
+    reporter.out.flush()?;
+    Ok(reporter.rejected == 0)
+}
+
+/// Checks the cells of the selected columns, or of every column if none are selected
+fn check_csv(
+    reporter: &mut Reporter,
+    source: &str,
+    text: &str,
+    columns: &[&String],
+) -> CliResult<()> {
+    let mut reader = csv::Reader::from_reader(text.as_bytes());
+    let headers = reader.headers()?.clone();
+    let selected: Vec<usize> = if columns.is_empty() {
+        (0..headers.len()).collect()
+    } else {
+        columns
+            .iter()
+            .map(|name| {
+                headers
+                    .iter()
+                    .position(|header| header == name.as_str())
+                    .ok_or_else(|| format!("{}: no CSV column '{}'", source, name))
+            })
+            .collect::<Result<_, _>>()?
+    };
+
+    for record in reader.records() {
+        let record = record?;
+        let line = record.position().map_or(0, |position| position.line());
+        for &index in &selected {
+            let Some(cell) = record.get(index) else {
+                continue;
+            };
+            let mut report = location(source);
+            report.insert("line".to_string(), line.into());
+            report.insert("column".to_string(), headers[index].into());
+            reporter.check(report, cell)?;
+        }
+    }
+    Ok(())
+}
+
+fn location(source: &str) -> Map<String, Value> {
+    let mut report = Map::new();
+    report.insert("source".to_string(), source.into());
+    report
+}
+
+fn read_source(source: &str) -> CliResult<String> {
+    if source == "-" {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+        Ok(text)
+    } else {
+        fs::read_to_string(source).map_err(|err| format!("{}: {}", source, err).into())
+    }
+}
+
+fn load_rules(path: &str) -> CliResult<SecurityConfig> {
+    let text = fs::read_to_string(path).map_err(|err| format!("{}: {}", path, err))?;
+    let rules: Rules = toml::from_str(&text).map_err(|err| format!("{}: {}", path, err))?;
+    rules
+        .into_config()
+        .map_err(|err| format!("{}: {}", path, err).into())
+}
+
+fn preset(name: &str) -> CliResult<SecurityConfig> {
+    match name {
+        "default" => Ok(SecurityConfig::default()),
+        "headers" => Ok(SecurityConfig::for_headers()),
+        "sql" => Ok(SecurityConfig::for_sql_params()),
+        "shell" => Ok(SecurityConfig::for_shell_args()),
+        other => Err(format!(
+            "unknown preset '{}'; use one of: {}",
+            other,
+            PRESETS.join(", ")
+        )
+        .into()),
+    }
+}
+
+/// Builds the check for the named validator, or a sanitize-only check if none is named
+fn checker(name: Option<&String>, config: SecurityConfig) -> CliResult<Check> {
+    fn boxed(validator: impl Validator<String> + 'static, config: SecurityConfig) -> Check {
+        Box::new(move |input| {
+            huginn::sanitize_and_validate(input, &validator, &config)
+                .map(|sanitized| sanitized.cleaned)
+        })
+    }
+
+    Ok(match name.map(String::as_str) {
+        None => boxed(Passthrough, config),
+        Some("cookie_name") => boxed(CookieNameValidator, config),
+        Some("cookie_value") => boxed(CookieValueValidator::new(), config),
+        Some("email") => boxed(EmailValidator::new(), config),
+        Some("email_header") => boxed(EmailHeaderValidator::new(), config),
+        Some("filename") => boxed(FilenameValidator::new(), config),
+        Some("header") => boxed(HeaderValueValidator::new(), config),
+        Some("hostname") => boxed(HostnameValidator::new(), config),
+        Some("shell_arg") => boxed(ShellArgValidator::new(), config),
+        Some("url") => boxed(UrlValidator::new(), config),
+        Some(other) => {
+            return Err(format!(
+                "unknown validator '{}'; use one of: {}",
+                other,
+                VALIDATORS.join(", ")
+            )
+            .into())
+        }
+    })
+}
+
+fn print_names(names: &[&str]) -> CliResult<bool> {
+    let mut out = io::stdout().lock();
+    for name in names {
+        writeln!(out, "{}", name)?;
+    }
+    Ok(true)
+}
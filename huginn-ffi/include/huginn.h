@@ -0,0 +1,96 @@
+/*
+ * C interface to the huginn input validation library.
+ *
+ * Configurations are opaque handles created from a preset or a builder and
+ * freed with huginn_config_free. Functions return a HuginnStatus; after a
+ * failure, huginn_last_error and huginn_last_error_code describe it until
+ * the next call on the same thread. Strings returned through out-parameters
+ * are owned by the caller and freed with huginn_string_free.
+ *
+ *     HuginnConfig *config = huginn_config_preset("default");
+ *     char *email = NULL;
+ *     if (huginn_validate(config, "email", input, &email) == HUGINN_OK) {
+ *         use(email);
+ *         huginn_string_free(email);
+ *     } else {
+ *         fprintf(stderr, "%s: %s\n", huginn_last_error_code(), huginn_last_error());
+ *     }
+ *     huginn_config_free(config);
+ */
+
+#ifndef HUGINN_H
+#define HUGINN_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum HuginnStatus {
+    /* The call succeeded */
+    HUGINN_OK = 0,
+    /* The input was rejected; the last error holds the reason and its HUG- code */
+    HUGINN_REJECTED = 1,
+    /* A pointer was null, a string was not UTF-8, or a name was unknown */
+    HUGINN_INVALID_ARGUMENT = 2,
+    /* A blocked pattern failed to compile */
+    HUGINN_INVALID_PATTERN = 3,
+    /* The library panicked; this is a bug */
+    HUGINN_PANIC = 4,
+} HuginnStatus;
+
+typedef struct HuginnConfig HuginnConfig;
+typedef struct HuginnConfigBuilder HuginnConfigBuilder;
+
+/* Creates a configuration from a preset: "default", "headers", "sql" or "shell".
+ * Returns NULL for an unknown preset. */
+HuginnConfig *huginn_config_preset(const char *name);
+
+/* Frees a configuration; NULL is ignored. */
+void huginn_config_free(HuginnConfig *config);
+
+/* Creates a builder, starting from the default forbidden characters and
+ * blocked patterns if with_defaults is set, or empty otherwise. */
+HuginnConfigBuilder *huginn_config_builder_new(bool with_defaults);
+
+/* Forbids a character, given as a Unicode code point. */
+HuginnStatus huginn_config_builder_add_forbidden_char(HuginnConfigBuilder *builder,
+                                                      uint32_t code_point);
+
+/* Blocks inputs matching a regular expression. */
+HuginnStatus huginn_config_builder_add_blocked_pattern(HuginnConfigBuilder *builder,
+                                                       const char *pattern);
+
+/* Builds the configuration and frees the builder. */
+HuginnConfig *huginn_config_builder_build(HuginnConfigBuilder *builder);
+
+/* Frees a builder without building it; NULL is ignored. */
+void huginn_config_builder_free(HuginnConfigBuilder *builder);
+
+/* Decodes, strips and screens input, writing the sanitized string to out. */
+HuginnStatus huginn_sanitize(const HuginnConfig *config, const char *input, char **out);
+
+/* Sanitizes input and validates it with a built-in validator: "cookie_name",
+ * "cookie_value", "email", "email_header", "filename", "header", "hostname",
+ * "shell_arg" or "url". out may be NULL when only the outcome is needed. */
+HuginnStatus huginn_validate(const HuginnConfig *config,
+                             const char *validator,
+                             const char *input,
+                             char **out);
+
+/* Frees a string returned by the library; NULL is ignored. */
+void huginn_string_free(char *value);
+
+/* Message of the last failure on this thread, or NULL. */
+const char *huginn_last_error(void);
+
+/* HUG- code of the last rejection on this thread, or NULL. */
+const char *huginn_last_error_code(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* HUGINN_H */
@@ -0,0 +1,449 @@
+#![warn(missing_docs)]
+#![deny(unsafe_op_in_unsafe_fn)]
+
+//! # huginn-ffi
+//!
+//! C interface to huginn, so C, C++ and any language with a C FFI can run
+//! the same sanitization rules and validators as Rust services. The
+//! declarations are in `include/huginn.h`.
+//!
+//! Configurations are opaque handles created from a preset or a builder
+//! and freed with [`huginn_config_free`]. Functions return a
+//! [`HuginnStatus`]; after a failure, [`huginn_last_error`] and
+//! [`huginn_last_error_code`] describe it until the next call on the same
+//! thread. Strings returned through out-parameters are owned by the caller
+//! and freed with [`huginn_string_free`]. All unsafe code of the bindings
+//! lives in this crate; the huginn crate itself forbids it.
+
+use huginn::{
+    config::SecurityConfigBuilder,
+    validators::{
+        CookieNameValidator, CookieValueValidator, EmailHeaderValidator, EmailValidator,
+        FilenameValidator, HeaderValueValidator, HostnameValidator, ShellArgValidator,
+        UrlValidator,
+    },
+    SecurityConfig, ValidationError, Validator,
+};
+use std::{
+    cell::RefCell,
+    ffi::{c_char, CStr, CString},
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr,
+};
+
+/// Result of a call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HuginnStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// The input was rejected; the last error holds the reason and its `HUG-` code
+    Rejected = 1,
+    /// A pointer was null, a string was not UTF-8, or a name was unknown
+    InvalidArgument = 2,
+    /// A blocked pattern failed to compile
+    InvalidPattern = 3,
+    /// The library panicked; this is a bug
+    Panic = 4,
+}
+
+/// Security configuration handle
+#[derive(Debug)]
+pub struct HuginnConfig(SecurityConfig);
+
+/// Builder for a custom security configuration
+#[derive(Debug)]
+pub struct HuginnConfigBuilder(Option<SecurityConfigBuilder>);
+
+/// Validator passing sanitized input through unchanged
+struct Passthrough;
+
+impl Validator<String> for Passthrough {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        Ok(input.to_string())
+    }
+
+    fn target_type(&self) -> &'static str {
+        "text"
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<(CString, CString)>> = const { RefCell::new(None) };
+}
+
+/// Failure of a call, recorded as the thread's last error
+struct Failure {
+    status: HuginnStatus,
+    code: &'static str,
+    message: String,
+}
+
+impl Failure {
+    fn invalid_argument(message: impl Into<String>) -> Self {
+        Self {
+            status: HuginnStatus::InvalidArgument,
+            code: "",
+            message: message.into(),
+        }
+    }
+}
+
+impl From<ValidationError> for Failure {
+    fn from(error: ValidationError) -> Self {
+        Self {
+            status: HuginnStatus::Rejected,
+            code: error.code(),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Runs `f`, recording its failure or panic as the thread's last error
+fn call(f: impl FnOnce() -> Result<(), Failure>) -> HuginnStatus {
+    let (status, error) = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => (HuginnStatus::Ok, None),
+        Ok(Err(failure)) => (failure.status, Some((failure.code, failure.message))),
+        Err(_) => (
+            HuginnStatus::Panic,
+            Some(("", "huginn panicked; please report a bug".to_string())),
+        ),
+    };
+    LAST_ERROR.with(|last| {
+        *last.borrow_mut() = error.map(|(code, message)| {
+            (
+                CString::new(code).unwrap_or_default(),
+                CString::new(message.replace('\0', "")).unwrap_or_default(),
+            )
+        })
+    });
+    status
+}
+
+/// Reads a NUL-terminated UTF-8 string
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL-terminated string that outlives `'a`.
+unsafe fn read_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, Failure> {
+    if ptr.is_null() {
+        return Err(Failure::invalid_argument(format!("{} is null", name)));
+    }
+    // SAFETY: the caller guarantees a valid NUL-terminated string
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|_| Failure::invalid_argument(format!("{} is not valid UTF-8", name)))
+}
+
+/// Hands a string to the caller through an out-parameter
+///
+/// # Safety
+///
+/// `out` must be null or valid for writing a pointer.
+unsafe fn write_string(out: *mut *mut c_char, value: String) -> Result<(), Failure> {
+    if out.is_null() {
+        return Err(Failure::invalid_argument("out is null"));
+    }
+    let value = CString::new(value)
+        .map_err(|_| Failure::invalid_argument("result contains a NUL character"))?;
+    // SAFETY: the caller guarantees `out` is valid for writes
+    unsafe { *out = value.into_raw() };
+    Ok(())
+}
+
+/// Returns the configuration behind a handle
+///
+/// # Safety
+///
+/// `config` must be null or a live handle from this library.
+unsafe fn config_ref<'a>(config: *const HuginnConfig) -> Result<&'a SecurityConfig, Failure> {
+    // SAFETY: the caller guarantees the handle is live
+    unsafe { config.as_ref() }
+        .map(|config| &config.0)
+        .ok_or_else(|| Failure::invalid_argument("config is null"))
+}
+
+/// Creates a configuration from a preset: `default`, `headers`, `sql` or `shell`
+///
+/// Returns null for an unknown preset.
+///
+/// # Safety
+///
+/// `name` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn huginn_config_preset(name: *const c_char) -> *mut HuginnConfig {
+    let mut config = ptr::null_mut();
+    call(|| {
+        // SAFETY: forwarded from the caller
+        let preset = match unsafe { read_str(name, "name") }? {
+            "default" => SecurityConfig::default(),
+            "headers" => SecurityConfig::for_headers(),
+            "sql" => SecurityConfig::for_sql_params(),
+            "shell" => SecurityConfig::for_shell_args(),
+            other => {
+                return Err(Failure::invalid_argument(format!(
+                    "unknown preset '{}'; use default, headers, sql or shell",
+                    other
+                )))
+            }
+        };
+        config = Box::into_raw(Box::new(HuginnConfig(preset)));
+        Ok(())
+    });
+    config
+}
+
+/// Frees a configuration; null is ignored
+///
+/// # Safety
+///
+/// `config` must be null or a handle from this library that is not used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn huginn_config_free(config: *mut HuginnConfig) {
+    if !config.is_null() {
+        // SAFETY: the caller hands back ownership of a handle created by `Box::into_raw`
+        drop(unsafe { Box::from_raw(config) });
+    }
+}
+
+/// Creates a builder for a custom configuration
+///
+/// With `with_defaults` set, the builder starts from the default forbidden
+/// characters and blocked patterns; otherwise it starts empty.
+#[no_mangle]
+pub extern "C" fn huginn_config_builder_new(with_defaults: bool) -> *mut HuginnConfigBuilder {
+    let mut builder = SecurityConfig::builder();
+    if with_defaults {
+        builder = builder
+            .with_default_forbidden_chars()
+            .with_default_blocked_patterns();
+    }
+    Box::into_raw(Box::new(HuginnConfigBuilder(Some(builder))))
+}
+
+/// Applies `f` to the builder behind a handle
+///
+/// # Safety
+///
+/// `builder` must be null or a live builder handle from this library.
+unsafe fn update_builder(
+    builder: *mut HuginnConfigBuilder,
+    f: impl FnOnce(SecurityConfigBuilder) -> Result<SecurityConfigBuilder, Failure>,
+) -> Result<(), Failure> {
+    // SAFETY: the caller guarantees the handle is live and not shared
+    let slot =
+        unsafe { builder.as_mut() }.ok_or_else(|| Failure::invalid_argument("builder is null"))?;
+    let current = slot
+        .0
+        .take()
+        .ok_or_else(|| Failure::invalid_argument("builder was already built"))?;
+    slot.0 = Some(f(current)?);
+    Ok(())
+}
+
+/// Forbids a character, given as a Unicode code point
+///
+/// # Safety
+///
+/// `builder` must be null or a live builder handle from this library.
+#[no_mangle]
+pub unsafe extern "C" fn huginn_config_builder_add_forbidden_char(
+    builder: *mut HuginnConfigBuilder,
+    code_point: u32,
+) -> HuginnStatus {
+    call(|| {
+        let c = char::from_u32(code_point)
+            .ok_or_else(|| Failure::invalid_argument("code point is not a valid character"))?;
+        // SAFETY: forwarded from the caller
+        unsafe { update_builder(builder, |b| Ok(b.add_forbidden_char(c))) }
+    })
+}
+
+/// Blocks inputs matching a regular expression
+///
+/// A pattern that fails to compile leaves the builder unchanged and
+/// returns `HUGINN_INVALID_PATTERN`.
+///
+/// # Safety
+///
+/// `builder` must be null or a live builder handle from this library, and
+/// `pattern` null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn huginn_config_builder_add_blocked_pattern(
+    builder: *mut HuginnConfigBuilder,
+    pattern: *const c_char,
+) -> HuginnStatus {
+    call(|| {
+        // SAFETY: forwarded from the caller
+        let pattern = unsafe { read_str(pattern, "pattern") }?;
+        // A failed add consumes the builder, so check the pattern on a scratch builder first
+        SecurityConfig::builder()
+            .add_blocked_pattern(pattern)
+            .map_err(|err| Failure {
+                status: HuginnStatus::InvalidPattern,
+                code: "",
+                message: err.to_string(),
+            })?;
+        // SAFETY: forwarded from the caller
+        unsafe {
+            update_builder(builder, |b| {
+                Ok(b.add_blocked_pattern(pattern)
+                    .expect("pattern compiled above"))
+            })
+        }
+    })
+}
+
+/// Builds the configuration and frees the builder
+///
+/// Returns null if `builder` is null.
+///
+/// # Safety
+///
+/// `builder` must be null or a builder handle from this library that is not used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn huginn_config_builder_build(
+    builder: *mut HuginnConfigBuilder,
+) -> *mut HuginnConfig {
+    if builder.is_null() {
+        return ptr::null_mut();
+    }
+    // SAFETY: the caller hands back ownership of a handle created by `Box::into_raw`
+    let builder = unsafe { Box::from_raw(builder) };
+    match builder.0 {
+        Some(builder) => Box::into_raw(Box::new(HuginnConfig(builder.build()))),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Frees a builder without building it; null is ignored
+///
+/// # Safety
+///
+/// `builder` must be null or a builder handle from this library that is not used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn huginn_config_builder_free(builder: *mut HuginnConfigBuilder) {
+    if !builder.is_null() {
+        // SAFETY: the caller hands back ownership of a handle created by `Box::into_raw`
+        drop(unsafe { Box::from_raw(builder) });
+    }
+}
+
+/// Decodes, strips and screens input, writing the sanitized string to `out`
+///
+/// # Safety
+///
+/// `config` must be null or a live handle, `input` null or a NUL-terminated
+/// string, and `out` null or valid for writing a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn huginn_sanitize(
+    config: *const HuginnConfig,
+    input: *const c_char,
+    out: *mut *mut c_char,
+) -> HuginnStatus {
+    call(|| {
+        // SAFETY: forwarded from the caller
+        let (config, input) = unsafe { (config_ref(config)?, read_str(input, "input")?) };
+        let cleaned = huginn::sanitize_and_validate(input, &Passthrough, config)?.cleaned;
+        // SAFETY: forwarded from the caller
+        unsafe { write_string(out, cleaned) }
+    })
+}
+
+/// Sanitizes input and validates it with a built-in validator, writing the validated string to `out`
+///
+/// Validators are `cookie_name`, `cookie_value`, `email`, `email_header`,
+/// `filename`, `header`, `hostname`, `shell_arg` and `url`, each with its
+/// default settings. `out` may be null when only the outcome is needed.
+///
+/// # Safety
+///
+/// `config` must be null or a live handle, `validator` and `input` null or
+/// NUL-terminated strings, and `out` null or valid for writing a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn huginn_validate(
+    config: *const HuginnConfig,
+    validator: *const c_char,
+    input: *const c_char,
+    out: *mut *mut c_char,
+) -> HuginnStatus {
+    call(|| {
+        // SAFETY: forwarded from the caller
+        let (config, validator, input) = unsafe {
+            (
+                config_ref(config)?,
+                read_str(validator, "validator")?,
+                read_str(input, "input")?,
+            )
+        };
+        let cleaned = match validator {
+            "cookie_name" => run(input, &CookieNameValidator, config),
+            "cookie_value" => run(input, &CookieValueValidator::new(), config),
+            "email" => run(input, &EmailValidator::new(), config),
+            "email_header" => run(input, &EmailHeaderValidator::new(), config),
+            "filename" => run(input, &FilenameValidator::new(), config),
+            "header" => run(input, &HeaderValueValidator::new(), config),
+            "hostname" => run(input, &HostnameValidator::new(), config),
+            "shell_arg" => run(input, &ShellArgValidator::new(), config),
+            "url" => run(input, &UrlValidator::new(), config),
+            other => {
+                return Err(Failure::invalid_argument(format!(
+                    "unknown validator '{}'",
+                    other
+                )))
+            }
+        }?;
+        if out.is_null() {
+            return Ok(());
+        }
+        // SAFETY: forwarded from the caller
+        unsafe { write_string(out, cleaned) }
+    })
+}
+
+fn run(
+    input: &str,
+    validator: &impl Validator<String>,
+    config: &SecurityConfig,
+) -> Result<String, ValidationError> {
+    huginn::sanitize_and_validate(input, validator, config).map(|sanitized| sanitized.cleaned)
+}
+
+/// Frees a string returned by this library; null is ignored
+///
+/// # Safety
+///
+/// `value` must be null or a string from this library that is not used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn huginn_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        // SAFETY: the caller hands back ownership of a string created by `CString::into_raw`
+        drop(unsafe { CString::from_raw(value) });
+    }
+}
+
+/// Returns the message of the last failure on this thread, or null
+///
+/// The string stays valid until the next call into the library on this thread.
+#[no_mangle]
+pub extern "C" fn huginn_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |(_, message)| message.as_ptr())
+    })
+}
+
+/// Returns the `HUG-` code of the last rejection on this thread, or null
+///
+/// Only rejections carry a code. The string stays valid until the next
+/// call into the library on this thread.
+#[no_mangle]
+pub extern "C" fn huginn_last_error_code() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .filter(|(code, _)| !code.is_empty())
+            .map_or(ptr::null(), |(code, _)| code.as_ptr())
+    })
+}
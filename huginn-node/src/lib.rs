@@ -0,0 +1,238 @@
+#![warn(missing_docs)]
+
+//! # huginn-node
+//!
+//! Node.js bindings for huginn, built with napi-rs, so Node services apply
+//! the same rules as Rust services.
+//!
+//! ```js
+//! const huginn = require("./huginn.node");
+//!
+//! const config = huginn.SecurityConfig.preset("default");
+//! const result = huginn.validate("Jane@Example.COM", "email", config);
+//! if (result.valid) {
+//!   save(result.cleaned);
+//! } else {
+//!   reply(400, { code: result.code, error: result.hint ?? result.message });
+//! }
+//! ```
+
+use huginn::{
+    validators::{
+        CookieNameValidator, CookieValueValidator, EmailHeaderValidator, EmailValidator,
+        FilenameValidator, HeaderValueValidator, HostnameValidator, ShellArgValidator,
+        UrlValidator,
+    },
+    ValidationError, Validator,
+};
+use napi::{Error, Result};
+use napi_derive::napi;
+
+/// Names accepted by `SecurityConfig.preset`
+const PRESETS: [&str; 4] = ["default", "headers", "sql", "shell"];
+
+/// Names accepted by the `validator` argument of `validate`
+const VALIDATORS: [&str; 9] = [
+    "cookie_name",
+    "cookie_value",
+    "email",
+    "email_header",
+    "filename",
+    "header",
+    "hostname",
+    "shell_arg",
+    "url",
+];
+
+/// Validator passing sanitized input through unchanged
+struct Passthrough;
+
+impl Validator<String> for Passthrough {
+    fn validate(&self, input: &str) -> std::result::Result<String, ValidationError> {
+        Ok(input.to_string())
+    }
+
+    fn target_type(&self) -> &'static str {
+        "text"
+    }
+}
+
+/// Options of the `SecurityConfig` constructor
+#[napi(object)]
+#[derive(Debug, Default)]
+pub struct ConfigOptions {
+    /// Characters to forbid in addition to the defaults
+    pub forbidden_chars: Option<String>,
+    /// Regular expressions to block in addition to the defaults
+    pub blocked_patterns: Option<Vec<String>>,
+    /// Whether to start from the default characters and patterns, true if omitted
+    pub defaults: Option<bool>,
+}
+
+/// Outcome of a check, with the sanitized value or the reason for the rejection
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    /// True if the input passed every check
+    pub valid: bool,
+    /// Sanitized value, if the input passed
+    pub cleaned: Option<String>,
+    /// Stable error code such as `HUG-020`, if the input was rejected
+    pub code: Option<String>,
+    /// Error message, if the input was rejected
+    pub message: Option<String>,
+    /// Suggestion for fixing the input, if the error has one
+    pub hint: Option<String>,
+}
+
+impl From<std::result::Result<String, ValidationError>> for CheckResult {
+    fn from(result: std::result::Result<String, ValidationError>) -> Self {
+        match result {
+            Ok(cleaned) => Self {
+                valid: true,
+                cleaned: Some(cleaned),
+                code: None,
+                message: None,
+                hint: None,
+            },
+            Err(error) => Self {
+                valid: false,
+                cleaned: None,
+                code: Some(error.code().to_string()),
+                message: Some(error.to_string()),
+                hint: error.hint().map(str::to_string),
+            },
+        }
+    }
+}
+
+/// Security configuration: forbidden characters and blocked patterns
+#[napi]
+#[derive(Debug, Clone)]
+pub struct SecurityConfig(huginn::SecurityConfig);
+
+#[napi]
+impl SecurityConfig {
+    /// Builds a configuration, starting from the defaults unless `defaults` is false
+    ///
+    /// Throws if a blocked pattern is not a valid regular expression.
+    #[napi(constructor)]
+    pub fn new(options: Option<ConfigOptions>) -> Result<Self> {
+        let options = options.unwrap_or_default();
+        let mut builder = huginn::SecurityConfig::builder();
+        if options.defaults.unwrap_or(true) {
+            builder = builder
+                .with_default_forbidden_chars()
+                .with_default_blocked_patterns();
+        }
+        for c in options.forbidden_chars.unwrap_or_default().chars() {
+            builder = builder.add_forbidden_char(c);
+        }
+        for pattern in options.blocked_patterns.unwrap_or_default() {
+            builder = builder
+                .add_blocked_pattern(&pattern)
+                .map_err(|err| Error::from_reason(err.to_string()))?;
+        }
+        Ok(Self(builder.build()))
+    }
+
+    /// Returns a preset configuration: "default", "headers", "sql" or "shell"
+    #[napi(factory)]
+    pub fn preset(name: String) -> Result<Self> {
+        let config = match name.as_str() {
+            "default" => huginn::SecurityConfig::default(),
+            "headers" => huginn::SecurityConfig::for_headers(),
+            "sql" => huginn::SecurityConfig::for_sql_params(),
+            "shell" => huginn::SecurityConfig::for_shell_args(),
+            other => {
+                return Err(Error::from_reason(format!(
+                    "unknown preset '{}'; use one of: {}",
+                    other,
+                    PRESETS.join(", ")
+                )))
+            }
+        };
+        Ok(Self(config))
+    }
+
+    /// Returns true if the string is a single forbidden character
+    #[napi]
+    pub fn is_char_forbidden(&self, c: String) -> bool {
+        let mut chars = c.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => self.0.is_char_forbidden(&c),
+            _ => false,
+        }
+    }
+
+    /// Returns the names of the blocked patterns matching the input
+    #[napi]
+    pub fn find_blocked_patterns(&self, input: String) -> Vec<String> {
+        self.0
+            .find_blocked_patterns(&input)
+            .into_iter()
+            .map(|found| found.name)
+            .collect()
+    }
+}
+
+fn config_or_default(config: Option<&SecurityConfig>) -> huginn::SecurityConfig {
+    config.map_or_else(huginn::SecurityConfig::default, |config| config.0.clone())
+}
+
+/// Decodes, strips and screens input with the given or the default configuration
+#[napi]
+pub fn sanitize(input: String, config: Option<&SecurityConfig>) -> CheckResult {
+    run(&input, &Passthrough, &config_or_default(config)).into()
+}
+
+/// Sanitizes input and validates it with a built-in validator
+///
+/// Throws for an unknown validator.
+#[napi]
+pub fn validate(
+    input: String,
+    validator: String,
+    config: Option<&SecurityConfig>,
+) -> Result<CheckResult> {
+    let config = config_or_default(config);
+    let result = match validator.as_str() {
+        "cookie_name" => run(&input, &CookieNameValidator, &config),
+        "cookie_value" => run(&input, &CookieValueValidator::new(), &config),
+        "email" => run(&input, &EmailValidator::new(), &config),
+        "email_header" => run(&input, &EmailHeaderValidator::new(), &config),
+        "filename" => run(&input, &FilenameValidator::new(), &config),
+        "header" => run(&input, &HeaderValueValidator::new(), &config),
+        "hostname" => run(&input, &HostnameValidator::new(), &config),
+        "shell_arg" => run(&input, &ShellArgValidator::new(), &config),
+        "url" => run(&input, &UrlValidator::new(), &config),
+        other => {
+            return Err(Error::from_reason(format!(
+                "unknown validator '{}'; use one of: {}",
+                other,
+                VALIDATORS.join(", ")
+            )))
+        }
+    };
+    Ok(result.into())
+}
+
+fn run(
+    input: &str,
+    validator: &impl Validator<String>,
+    config: &huginn::SecurityConfig,
+) -> std::result::Result<String, ValidationError> {
+    huginn::sanitize_and_validate(input, validator, config).map(|sanitized| sanitized.cleaned)
+}
+
+/// Returns the names of the preset configurations
+#[napi]
+pub fn presets() -> Vec<&'static str> {
+    PRESETS.to_vec()
+}
+
+/// Returns the names of the built-in validators
+#[napi]
+pub fn validators() -> Vec<&'static str> {
+    VALIDATORS.to_vec()
+}
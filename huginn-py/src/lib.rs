@@ -0,0 +1,224 @@
+#![warn(missing_docs)]
+
+//! # huginn-py
+//!
+//! Python bindings for huginn, built with maturin, so data-cleaning scripts
+//! and Python services apply the same rules as Rust services.
+//!
+//! ```python
+//! import huginn
+//!
+//! config = huginn.SecurityConfig.preset("default")
+//! assert huginn.validate("Jane@Example.COM", "email", config) == "Jane@example.com"
+//!
+//! try:
+//!     huginn.validate("../../etc/passwd", "filename")
+//! except huginn.ValidationError as err:
+//!     print(err.code, err, err.hint)
+//! ```
+
+use huginn::{
+    validators::{
+        CookieNameValidator, CookieValueValidator, EmailHeaderValidator, EmailValidator,
+        FilenameValidator, HeaderValueValidator, HostnameValidator, ShellArgValidator,
+        UrlValidator,
+    },
+    SecurityConfig, Validator,
+};
+use pyo3::{create_exception, exceptions::PyValueError, prelude::*};
+
+create_exception!(
+    huginn,
+    ValidationError,
+    PyValueError,
+    "Raised when input is rejected; `code` holds the HUG- error code and `hint` a suggested fix"
+);
+
+/// Names accepted by `SecurityConfig.preset`
+const PRESETS: [&str; 4] = ["default", "headers", "sql", "shell"];
+
+/// Names accepted by the `validator` argument of `validate`
+const VALIDATORS: [&str; 9] = [
+    "cookie_name",
+    "cookie_value",
+    "email",
+    "email_header",
+    "filename",
+    "header",
+    "hostname",
+    "shell_arg",
+    "url",
+];
+
+/// Validator passing sanitized input through unchanged
+struct Passthrough;
+
+impl Validator<String> for Passthrough {
+    fn validate(&self, input: &str) -> Result<String, huginn::ValidationError> {
+        Ok(input.to_string())
+    }
+
+    fn target_type(&self) -> &'static str {
+        "text"
+    }
+}
+
+/// Security configuration: forbidden characters and blocked patterns
+#[pyclass(name = "SecurityConfig", module = "huginn", frozen)]
+#[derive(Debug, Clone)]
+struct PySecurityConfig(SecurityConfig);
+
+#[pymethods]
+impl PySecurityConfig {
+    /// Builds a configuration, starting from the defaults unless `defaults` is false
+    #[new]
+    #[pyo3(signature = (forbidden_chars = "", blocked_patterns = Vec::new(), defaults = true))]
+    fn new(forbidden_chars: &str, blocked_patterns: Vec<String>, defaults: bool) -> PyResult<Self> {
+        let mut builder = SecurityConfig::builder();
+        if defaults {
+            builder = builder
+                .with_default_forbidden_chars()
+                .with_default_blocked_patterns();
+        }
+        for c in forbidden_chars.chars() {
+            builder = builder.add_forbidden_char(c);
+        }
+        for pattern in &blocked_patterns {
+            builder = builder
+                .add_blocked_pattern(pattern)
+                .map_err(|err| PyValueError::new_err(err.to_string()))?;
+        }
+        Ok(Self(builder.build()))
+    }
+
+    /// Returns a preset configuration: "default", "headers", "sql" or "shell"
+    #[staticmethod]
+    fn preset(name: &str) -> PyResult<Self> {
+        let config = match name {
+            "default" => SecurityConfig::default(),
+            "headers" => SecurityConfig::for_headers(),
+            "sql" => SecurityConfig::for_sql_params(),
+            "shell" => SecurityConfig::for_shell_args(),
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown preset '{}'; use one of: {}",
+                    other,
+                    PRESETS.join(", ")
+                )))
+            }
+        };
+        Ok(Self(config))
+    }
+
+    /// Returns true if the character is forbidden
+    fn is_char_forbidden(&self, c: char) -> bool {
+        self.0.is_char_forbidden(&c)
+    }
+
+    /// Returns the names of the blocked patterns matching the input
+    fn find_blocked_patterns(&self, input: &str) -> Vec<String> {
+        self.0
+            .find_blocked_patterns(input)
+            .into_iter()
+            .map(|found| found.name)
+            .collect()
+    }
+
+    fn __repr__(&self) -> &'static str {
+        "SecurityConfig(...)"
+    }
+}
+
+/// Converts a rejection into a `ValidationError` carrying its code and hint
+fn to_py_err(py: Python<'_>, error: huginn::ValidationError) -> PyErr {
+    let err = ValidationError::new_err(error.to_string());
+    let value = err.value(py);
+    // Setting attributes on a fresh exception instance cannot fail
+    let _ = value.setattr("code", error.code());
+    let _ = value.setattr("hint", error.hint());
+    err
+}
+
+fn config_or_default(config: Option<&PySecurityConfig>) -> SecurityConfig {
+    config.map_or_else(SecurityConfig::default, |config| config.0.clone())
+}
+
+/// Decodes, strips and screens input, returning the sanitized string
+///
+/// Raises `ValidationError` if the input is rejected.
+#[pyfunction]
+#[pyo3(signature = (input, config = None))]
+fn sanitize(py: Python<'_>, input: &str, config: Option<&PySecurityConfig>) -> PyResult<String> {
+    let config = config_or_default(config);
+    py.allow_threads(|| huginn::sanitize_and_validate(input, &Passthrough, &config))
+        .map(|sanitized| sanitized.cleaned)
+        .map_err(|error| to_py_err(py, error))
+}
+
+/// Sanitizes input and validates it with a built-in validator, returning the validated string
+///
+/// Raises `ValidationError` if the input is rejected and `ValueError` for
+/// an unknown validator.
+#[pyfunction]
+#[pyo3(signature = (input, validator, config = None))]
+fn validate(
+    py: Python<'_>,
+    input: &str,
+    validator: &str,
+    config: Option<&PySecurityConfig>,
+) -> PyResult<String> {
+    let config = config_or_default(config);
+    let result = py.allow_threads(|| match validator {
+        "cookie_name" => Some(run(input, &CookieNameValidator, &config)),
+        "cookie_value" => Some(run(input, &CookieValueValidator::new(), &config)),
+        "email" => Some(run(input, &EmailValidator::new(), &config)),
+        "email_header" => Some(run(input, &EmailHeaderValidator::new(), &config)),
+        "filename" => Some(run(input, &FilenameValidator::new(), &config)),
+        "header" => Some(run(input, &HeaderValueValidator::new(), &config)),
+        "hostname" => Some(run(input, &HostnameValidator::new(), &config)),
+        "shell_arg" => Some(run(input, &ShellArgValidator::new(), &config)),
+        "url" => Some(run(input, &UrlValidator::new(), &config)),
+        _ => None,
+    });
+    match result {
+        Some(result) => result.map_err(|error| to_py_err(py, error)),
+        None => Err(PyValueError::new_err(format!(
+            "unknown validator '{}'; use one of: {}",
+            validator,
+            VALIDATORS.join(", ")
+        ))),
+    }
+}
+
+fn run(
+    input: &str,
+    validator: &impl Validator<String>,
+    config: &SecurityConfig,
+) -> Result<String, huginn::ValidationError> {
+    huginn::sanitize_and_validate(input, validator, config).map(|sanitized| sanitized.cleaned)
+}
+
+/// Returns the names of the preset configurations
+#[pyfunction]
+fn presets() -> Vec<&'static str> {
+    PRESETS.to_vec()
+}
+
+/// Returns the names of the built-in validators
+#[pyfunction]
+fn validators() -> Vec<&'static str> {
+    VALIDATORS.to_vec()
+}
+
+/// Secure input validation and sanitization
+#[pymodule]
+#[pyo3(name = "huginn")]
+fn huginn_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PySecurityConfig>()?;
+    m.add("ValidationError", m.py().get_type::<ValidationError>())?;
+    m.add_function(wrap_pyfunction!(sanitize, m)?)?;
+    m.add_function(wrap_pyfunction!(validate, m)?)?;
+    m.add_function(wrap_pyfunction!(presets, m)?)?;
+    m.add_function(wrap_pyfunction!(validators, m)?)?;
+    Ok(())
+}
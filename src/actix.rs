@@ -0,0 +1,171 @@
+pub use super::extract::{RequestField, Source};
+use super::{
+    config::SecurityConfig,
+    error::ValidationError,
+    extract::{json_field, FieldError},
+    query::raw_value,
+    validation::sanitize_and_validate_async,
+};
+use actix_web::{
+    dev::Payload,
+    error::{ErrorBadRequest, ErrorUnprocessableEntity},
+    http::{header, StatusCode},
+    web::{self, Bytes},
+    FromRequest, HttpRequest, HttpResponse, ResponseError,
+};
+use futures::future::LocalBoxFuture;
+use std::{fmt, fmt::Debug, marker::PhantomData, ops::Deref};
+
+/// Extractor running one request field through the pipeline
+///
+/// The security configuration is read from app data, registered either as
+/// `web::Data<SecurityConfig>` or as a plain [`SecurityConfig`]; the default
+/// configuration is used when neither is present. Failed validation responds
+/// with `422 Unprocessable Entity` and the serialized [`ValidationError`].
+///
+/// ```rust
+/// use actix_web::{web, App};
+/// use huginn::actix::{RequestField, Sanitized, Source};
+/// use huginn::{SecurityConfig, ValidationError, Validator};
+///
+/// #[derive(Default)]
+/// struct Username;
+///
+/// impl Validator<String> for Username {
+///     fn validate(&self, input: &str) -> Result<String, ValidationError> {
+///         if input.chars().all(|c| c.is_ascii_alphanumeric()) {
+///             Ok(input.to_string())
+///         } else {
+///             Err(ValidationError::invalid_format(self.target_type()))
+///         }
+///     }
+///
+///     fn target_type(&self) -> &'static str {
+///         "username"
+///     }
+/// }
+///
+/// impl RequestField<String> for Username {
+///     const SOURCE: Source = Source::Query("name");
+/// }
+///
+/// async fn profile(name: Sanitized<String, Username>) -> String {
+///     format!("Hello, {}", *name)
+/// }
+///
+/// let app = App::new()
+///     .app_data(web::Data::new(SecurityConfig::default()))
+///     .route("/profile", web::get().to(profile));
+/// ```
+pub struct Sanitized<T, V> {
+    value: T,
+    _validator: PhantomData<fn() -> V>,
+}
+
+impl<T, V> Sanitized<T, V> {
+    /// Consumes the extractor, returning the validated value
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, V> Deref for Sanitized<T, V> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Debug, V> Debug for Sanitized<T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Sanitized").field(&self.value).finish()
+    }
+}
+
+impl<T, V> FromRequest for Sanitized<T, V>
+where
+    T: Debug + Send + Sync + 'static,
+    V: RequestField<T> + 'static,
+{
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let config = config_for(req);
+
+        let raw: LocalBoxFuture<'static, Result<String, actix_web::Error>> = match V::SOURCE {
+            Source::Path(name) => {
+                let value = req.match_info().get(name).map(str::to_string);
+                Box::pin(async move { value.ok_or_else(|| missing(name)) })
+            }
+            Source::Query(name) => {
+                let value = raw_value(req.query_string(), name);
+                Box::pin(async move { value.ok_or_else(|| missing(name)) })
+            }
+            Source::Form(name) => {
+                let is_form = req
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|value| value.starts_with("application/x-www-form-urlencoded"));
+                let body = Bytes::from_request(req, payload);
+                Box::pin(async move {
+                    if !is_form {
+                        return Err(ErrorBadRequest("expected a URL-encoded form body"));
+                    }
+                    let body = body.await?;
+                    let body = std::str::from_utf8(&body).map_err(ErrorBadRequest)?;
+                    raw_value(body, name).ok_or_else(|| missing(name))
+                })
+            }
+            Source::Json(name) => {
+                let body = Bytes::from_request(req, payload);
+                Box::pin(async move {
+                    json_field(&body.await?, name).map_err(|err| match err {
+                        FieldError::Missing(name) => missing(name),
+                        FieldError::Malformed(reason) => ErrorBadRequest(reason),
+                    })
+                })
+            }
+        };
+
+        Box::pin(async move {
+            let raw = raw.await?;
+            let result = sanitize_and_validate_async(&raw, &V::default(), &config).await?;
+            Ok(Self {
+                value: result.cleaned,
+                _validator: PhantomData,
+            })
+        })
+    }
+}
+
+/// Looks up the configuration registered as app data
+fn config_for(req: &HttpRequest) -> SecurityConfig {
+    if let Some(config) = req.app_data::<web::Data<SecurityConfig>>() {
+        return config.get_ref().clone();
+    }
+    req.app_data::<SecurityConfig>()
+        .cloned()
+        .unwrap_or_default()
+}
+
+fn missing(name: &'static str) -> actix_web::Error {
+    ErrorBadRequest(format!("Missing field `{}`", name))
+}
+
+impl ResponseError for ValidationError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::UNPROCESSABLE_ENTITY
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        match serde_json::to_string(self) {
+            Ok(body) => HttpResponse::build(self.status_code())
+                .content_type("application/json")
+                .body(body),
+            Err(_) => ErrorUnprocessableEntity(self.to_string()).error_response(),
+        }
+    }
+}
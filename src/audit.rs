@@ -0,0 +1,248 @@
+use super::{error::ValidationError, log::for_logging, validation::decode_input};
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Characters of context kept on each side of a finding in excerpts
+const EXCERPT_RADIUS: usize = 24;
+
+/// Structured record of one rejected input, passed to an [`AuditSink`]
+#[derive(Debug, Clone)]
+pub struct AuditRecord<'a> {
+    /// Time the input was rejected
+    pub timestamp: SystemTime,
+    /// Target type reported by the validator
+    pub validator: &'static str,
+    /// Name of the pipeline that rejected the input, if set
+    pub context: Option<&'a str>,
+    /// Rejection error
+    pub error: &'a ValidationError,
+    /// Redacted excerpt of the input around the first finding
+    pub excerpt: String,
+}
+
+impl<'a> AuditRecord<'a> {
+    /// Builds a record, taking a redacted excerpt of the input
+    ///
+    /// The excerpt is cut around the first forbidden character or pattern
+    /// match, digits are masked with `#` and control characters escaped, so
+    /// payloads stay recognizable while phone numbers, card numbers and
+    /// similar identifiers do not end up in the audit trail.
+    pub fn new(
+        input: &str,
+        validator: &'static str,
+        context: Option<&'a str>,
+        error: &'a ValidationError,
+    ) -> Self {
+        Self {
+            timestamp: SystemTime::now(),
+            validator,
+            context,
+            error,
+            excerpt: excerpt(input, error),
+        }
+    }
+
+    /// Returns the ids of the rules that rejected the input
+    pub fn rule_ids(&self) -> Vec<&str> {
+        self.error.rule_ids()
+    }
+
+    /// Renders the record as a single line of JSON, without the trailing newline
+    pub fn to_json(&self) -> String {
+        let mut json = String::with_capacity(256);
+        json.push_str("{\"timestamp\":");
+        push_json_str(&mut json, &format_timestamp(self.timestamp));
+        json.push_str(",\"validator\":");
+        push_json_str(&mut json, self.validator);
+        json.push_str(",\"context\":");
+        match self.context {
+            Some(context) => push_json_str(&mut json, context),
+            None => json.push_str("null"),
+        }
+        json.push_str(",\"code\":");
+        push_json_str(&mut json, self.error.code());
+        json.push_str(",\"rules\":[");
+        for (i, rule) in self.rule_ids().into_iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            push_json_str(&mut json, rule);
+        }
+        json.push_str("],\"threats\":[");
+        for (i, threat) in self.error.threats().into_iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            push_json_str(&mut json, threat.name());
+        }
+        json.push_str("],\"message\":");
+        push_json_str(&mut json, &self.error.to_string());
+        json.push_str(",\"excerpt\":");
+        push_json_str(&mut json, &self.excerpt);
+        json.push('}');
+        json
+    }
+}
+
+/// Receiver of a record for every input rejected by a [`Pipeline`](crate::Pipeline)
+pub trait AuditSink: Send + Sync {
+    /// Records one rejection
+    fn record(&self, record: &AuditRecord<'_>);
+}
+
+/// Lets the caller keep a handle to a sink shared with a pipeline
+impl<S: AuditSink + ?Sized> AuditSink for Arc<S> {
+    fn record(&self, record: &AuditRecord<'_>) {
+        (**self).record(record)
+    }
+}
+
+/// Audit sink appending one JSON object per line to a file or other writer
+///
+/// Each line holds `timestamp` (RFC 3339, UTC), `validator`, `context`,
+/// `code`, `rules`, `threats`, `message` and `excerpt`. Lines are flushed as they are
+/// written; write failures are dropped so auditing never fails a request.
+///
+/// ```rust
+/// use huginn::audit::JsonLinesAuditSink;
+/// use huginn::validators::FilenameValidator;
+/// use huginn::{Pipeline, SecurityConfig};
+///
+/// let path = std::env::temp_dir().join("huginn-audit-doctest.jsonl");
+/// # let _ = std::fs::remove_file(&path);
+/// let pipeline = Pipeline::builder(SecurityConfig::default())
+///     .with_context("upload")
+///     .with_audit(JsonLinesAuditSink::open(&path)?)
+///     .build();
+///
+/// let _ = pipeline.process("card 4111111111111111 ../../etc/passwd", &FilenameValidator::new());
+///
+/// let log = std::fs::read_to_string(&path)?;
+/// assert!(log.contains(r#""context":"upload""#));
+/// assert!(log.contains(r#""rules":["path_traversal"]"#));
+/// assert!(log.contains(r#""threats":["path_traversal"]"#));
+/// assert!(!log.contains("4111"));
+/// # std::fs::remove_file(&path)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct JsonLinesAuditSink {
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+impl std::fmt::Debug for JsonLinesAuditSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonLinesAuditSink").finish_non_exhaustive()
+    }
+}
+
+impl JsonLinesAuditSink {
+    /// Writes records to the given writer
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            writer: Mutex::new(Box::new(writer)),
+        }
+    }
+
+    /// Appends records to a file, creating it if needed
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file: File = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+}
+
+impl AuditSink for JsonLinesAuditSink {
+    fn record(&self, record: &AuditRecord<'_>) {
+        let mut line = record.to_json();
+        line.push('\n');
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = writer
+            .write_all(line.as_bytes())
+            .and_then(|_| writer.flush());
+    }
+}
+
+/// Cuts the input around the first finding and masks digits
+fn excerpt(input: &str, error: &ValidationError) -> String {
+    let decoded = decode_input(input);
+    let focus = match error {
+        ValidationError::DangerousCharacters { positions, .. } => {
+            positions.first().map(|p| p.char_offset)
+        }
+        ValidationError::BlockedPattern { matches, .. } => matches.first().map(|m| {
+            decoded
+                .char_indices()
+                .take_while(|(i, _)| *i < m.span.start)
+                .count()
+        }),
+        _ => None,
+    }
+    .unwrap_or(0);
+
+    let start = focus.saturating_sub(EXCERPT_RADIUS);
+    let total = decoded.chars().count();
+    let mut excerpt = String::new();
+    if start > 0 {
+        excerpt.push('…');
+    }
+    excerpt.extend(
+        decoded
+            .chars()
+            .skip(start)
+            .take(EXCERPT_RADIUS * 2)
+            .map(|c| if c.is_ascii_digit() { '#' } else { c }),
+    );
+    if start + EXCERPT_RADIUS * 2 < total {
+        excerpt.push('…');
+    }
+    for_logging(&excerpt).into_owned()
+}
+
+fn push_json_str(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Formats a time as an RFC 3339 UTC timestamp with millisecond precision
+fn format_timestamp(time: SystemTime) -> String {
+    let elapsed = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = elapsed.as_secs();
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since the epoch (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60,
+        elapsed.subsec_millis()
+    )
+}
@@ -0,0 +1,403 @@
+pub use super::extract::{RequestField, Source};
+use super::{
+    config::SecurityConfig,
+    error::ValidationError,
+    extract::{json_field, FieldError},
+    query::raw_value,
+    validation::sanitize_and_validate_async,
+};
+use ::axum::{
+    body::Bytes,
+    extract::{FromRef, FromRequest, FromRequestParts, MatchedPath, OriginalUri, Request},
+    http::{header, request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+};
+use std::{fmt, fmt::Debug, marker::PhantomData, ops::Deref};
+
+/// Extractor running one path or query parameter through the pipeline
+///
+/// The security configuration is taken from the router state, so the state
+/// must provide a [`SecurityConfig`] via [`FromRef`]. Failed validation
+/// rejects the request with `422 Unprocessable Entity` and the serialized
+/// [`ValidationError`] as the body.
+///
+/// The parameter is read still percent-encoded, so it is decoded exactly
+/// once, by the pipeline. The body is left alone, so a handler may take any
+/// number of these next to one body extractor; form and JSON fields are
+/// read with [`SanitizedBody`].
+///
+/// ```rust
+/// use axum::{routing::get, Router};
+/// use huginn::axum::{RequestField, Sanitized, Source};
+/// use huginn::{SecurityConfig, ValidationError, Validator};
+///
+/// #[derive(Default)]
+/// struct Username;
+///
+/// impl Validator<String> for Username {
+///     fn validate(&self, input: &str) -> Result<String, ValidationError> {
+///         if input.chars().all(|c| c.is_ascii_alphanumeric()) {
+///             Ok(input.to_string())
+///         } else {
+///             Err(ValidationError::invalid_format(self.target_type()))
+///         }
+///     }
+///
+///     fn target_type(&self) -> &'static str {
+///         "username"
+///     }
+/// }
+///
+/// impl RequestField<String> for Username {
+///     const SOURCE: Source = Source::Path("name");
+/// }
+///
+/// async fn profile(name: Sanitized<String, Username>) -> String {
+///     format!("Hello, {}", *name)
+/// }
+///
+/// let app: Router = Router::new()
+///     .route("/users/{name}", get(profile))
+///     .with_state(SecurityConfig::default());
+/// ```
+///
+/// Path parameters are found under nested routers too:
+///
+/// ```rust
+/// # use axum::{body::Body, http::Request, routing::get, Router};
+/// # use huginn::axum::{RequestField, Sanitized, Source};
+/// # use huginn::{SecurityConfig, ValidationError, Validator};
+/// # use tower::ServiceExt;
+/// # #[derive(Default)]
+/// # struct Username;
+/// # impl Validator<String> for Username {
+/// #     fn validate(&self, input: &str) -> Result<String, ValidationError> {
+/// #         Ok(input.to_string())
+/// #     }
+/// #     fn target_type(&self) -> &'static str {
+/// #         "username"
+/// #     }
+/// # }
+/// # impl RequestField<String> for Username {
+/// #     const SOURCE: Source = Source::Path("name");
+/// # }
+/// async fn profile(name: Sanitized<String, Username>) -> String {
+///     format!("Hello, {}", *name)
+/// }
+///
+/// let users = Router::new().route("/users/{name}", get(profile));
+/// let app: Router = Router::new()
+///     .nest("/api/{version}", users)
+///     .with_state(SecurityConfig::default());
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let request = Request::get("/api/v1/users/alice").body(Body::empty()).unwrap();
+/// let response = app.oneshot(request).await.unwrap();
+/// let body = axum::body::to_bytes(response.into_body(), 1024).await.unwrap();
+/// assert_eq!(body, "Hello, alice");
+/// # });
+/// ```
+pub struct Sanitized<T, V> {
+    value: T,
+    _validator: PhantomData<fn() -> V>,
+}
+
+impl<T, V> Sanitized<T, V> {
+    /// Consumes the extractor, returning the validated value
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, V> Deref for Sanitized<T, V> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Debug, V> Debug for Sanitized<T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Sanitized").field(&self.value).finish()
+    }
+}
+
+impl<S, T, V> FromRequestParts<S> for Sanitized<T, V>
+where
+    S: Send + Sync,
+    SecurityConfig: FromRef<S>,
+    T: Debug + Send + Sync,
+    V: RequestField<T>,
+{
+    type Rejection = SanitizedRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let raw = match V::SOURCE {
+            Source::Path(name) => path_param(parts, name)?,
+            Source::Query(name) => query_param(parts, name)?,
+            Source::Form(name) | Source::Json(name) => {
+                return Err(SanitizedRejection::BodyField(name))
+            }
+        };
+        let value = screen::<S, T, V>(&raw, state).await?;
+        Ok(Self {
+            value,
+            _validator: PhantomData,
+        })
+    }
+}
+
+/// Extractor running one field of the request body through the pipeline
+///
+/// Works like [`Sanitized`] for [`Source::Form`] and [`Source::Json`]
+/// fields. Since it consumes the body, it must be the last extractor of a
+/// handler and there can be only one; path and query fields, which
+/// [`Sanitized`] reads without the body, are accepted too.
+///
+/// ```rust
+/// use axum::{routing::post, Router};
+/// use huginn::axum::{RequestField, Sanitized, SanitizedBody, Source};
+/// use huginn::{SecurityConfig, ValidationError, Validator};
+///
+/// #[derive(Default)]
+/// struct Team;
+///
+/// impl Validator<String> for Team {
+///     fn validate(&self, input: &str) -> Result<String, ValidationError> {
+///         Ok(input.to_string())
+///     }
+///
+///     fn target_type(&self) -> &'static str {
+///         "team"
+///     }
+/// }
+///
+/// impl RequestField<String> for Team {
+///     const SOURCE: Source = Source::Path("team");
+/// }
+///
+/// #[derive(Default)]
+/// struct Comment;
+///
+/// impl Validator<String> for Comment {
+///     fn validate(&self, input: &str) -> Result<String, ValidationError> {
+///         Ok(input.to_string())
+///     }
+///
+///     fn target_type(&self) -> &'static str {
+///         "comment"
+///     }
+/// }
+///
+/// impl RequestField<String> for Comment {
+///     const SOURCE: Source = Source::Json("comment");
+/// }
+///
+/// async fn post_comment(
+///     team: Sanitized<String, Team>,
+///     comment: SanitizedBody<String, Comment>,
+/// ) -> String {
+///     format!("{}: {}", *team, *comment)
+/// }
+///
+/// let app: Router = Router::new()
+///     .route("/teams/{team}/comments", post(post_comment))
+///     .with_state(SecurityConfig::default());
+/// ```
+pub struct SanitizedBody<T, V> {
+    value: T,
+    _validator: PhantomData<fn() -> V>,
+}
+
+impl<T, V> SanitizedBody<T, V> {
+    /// Consumes the extractor, returning the validated value
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, V> Deref for SanitizedBody<T, V> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Debug, V> Debug for SanitizedBody<T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SanitizedBody").field(&self.value).finish()
+    }
+}
+
+impl<S, T, V> FromRequest<S> for SanitizedBody<T, V>
+where
+    S: Send + Sync,
+    SecurityConfig: FromRef<S>,
+    T: Debug + Send + Sync,
+    V: RequestField<T>,
+{
+    type Rejection = SanitizedRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let raw = match V::SOURCE {
+            Source::Path(name) => path_param(&req.into_parts().0, name)?,
+            Source::Query(name) => query_param(&req.into_parts().0, name)?,
+            Source::Form(name) => {
+                let is_form = req
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|value| value.starts_with("application/x-www-form-urlencoded"));
+                if !is_form {
+                    return Err(SanitizedRejection::Malformed(
+                        "expected a URL-encoded form body".to_string(),
+                    ));
+                }
+                let body = Bytes::from_request(req, state)
+                    .await
+                    .map_err(|err| SanitizedRejection::Malformed(err.body_text()))?;
+                let body = std::str::from_utf8(&body)
+                    .map_err(|err| SanitizedRejection::Malformed(err.to_string()))?;
+                raw_value(body, name).ok_or(SanitizedRejection::MissingField(name))?
+            }
+            Source::Json(name) => {
+                let body = Bytes::from_request(req, state)
+                    .await
+                    .map_err(|err| SanitizedRejection::Malformed(err.body_text()))?;
+                json_field(&body, name)?
+            }
+        };
+        let value = screen::<S, T, V>(&raw, state).await?;
+        Ok(Self {
+            value,
+            _validator: PhantomData,
+        })
+    }
+}
+
+/// Runs a raw, still percent-encoded value through the pipeline
+async fn screen<S, T, V>(raw: &str, state: &S) -> Result<T, SanitizedRejection>
+where
+    SecurityConfig: FromRef<S>,
+    T: Debug + Send + Sync,
+    V: RequestField<T>,
+{
+    let config = SecurityConfig::from_ref(state);
+    let result = sanitize_and_validate_async(raw, &V::default(), &config).await?;
+    Ok(result.cleaned)
+}
+
+/// Reads a path parameter without percent-decoding it
+///
+/// axum's `Path` extractor decodes parameters, so the raw segment is found
+/// by lining the request path up with the matched route instead, leaving
+/// the decoding to the pipeline. The matched route includes the prefixes of
+/// nested routers, so it is lined up with the original path rather than the
+/// one the nested router sees.
+fn path_param(parts: &Parts, name: &'static str) -> Result<String, SanitizedRejection> {
+    let route = parts
+        .extensions
+        .get::<MatchedPath>()
+        .ok_or(SanitizedRejection::MissingField(name))?;
+    let path = parts
+        .extensions
+        .get::<OriginalUri>()
+        .map_or(parts.uri.path(), |uri| uri.0.path());
+    let mut segments = path.split('/');
+    for template in route.as_str().split('/') {
+        let segment = segments.next();
+        let Some(param) = template
+            .strip_prefix('{')
+            .and_then(|param| param.strip_suffix('}'))
+        else {
+            continue;
+        };
+        if param == name {
+            return segment
+                .map(str::to_string)
+                .ok_or(SanitizedRejection::MissingField(name));
+        }
+        if param.strip_prefix('*') == Some(name) {
+            let rest: Vec<&str> = segment.into_iter().chain(segments).collect();
+            return Ok(rest.join("/"));
+        }
+    }
+    Err(SanitizedRejection::MissingField(name))
+}
+
+/// Reads a query parameter without percent-decoding it
+fn query_param(parts: &Parts, name: &'static str) -> Result<String, SanitizedRejection> {
+    parts
+        .uri
+        .query()
+        .and_then(|query| raw_value(query, name))
+        .ok_or(SanitizedRejection::MissingField(name))
+}
+
+/// Rejection produced by the [`Sanitized`] extractor
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SanitizedRejection {
+    /// The value failed sanitization or validation
+    Invalid(ValidationError),
+    /// The request did not contain the field
+    MissingField(&'static str),
+    /// The request could not be parsed
+    Malformed(String),
+    /// A body field was requested from [`Sanitized`] rather than [`SanitizedBody`]
+    BodyField(&'static str),
+}
+
+impl From<ValidationError> for SanitizedRejection {
+    fn from(error: ValidationError) -> Self {
+        Self::Invalid(error)
+    }
+}
+
+impl From<FieldError> for SanitizedRejection {
+    fn from(error: FieldError) -> Self {
+        match error {
+            FieldError::Missing(name) => Self::MissingField(name),
+            FieldError::Malformed(reason) => Self::Malformed(reason),
+        }
+    }
+}
+
+impl fmt::Display for SanitizedRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(error) => fmt::Display::fmt(error, f),
+            Self::MissingField(name) => write!(f, "Missing field `{}`", name),
+            Self::Malformed(reason) => write!(f, "Malformed request: {}", reason),
+            Self::BodyField(name) => write!(
+                f,
+                "Field `{}` is read from the body; extract it with `SanitizedBody`",
+                name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SanitizedRejection {}
+
+impl IntoResponse for SanitizedRejection {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Invalid(error) => match serde_json::to_vec(&error) {
+                Ok(body) => (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    [(header::CONTENT_TYPE, "application/json")],
+                    body,
+                )
+                    .into_response(),
+                Err(_) => (StatusCode::UNPROCESSABLE_ENTITY, error.to_string()).into_response(),
+            },
+            Self::BodyField(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()).into_response()
+            }
+            other => (StatusCode::BAD_REQUEST, other.to_string()).into_response(),
+        }
+    }
+}
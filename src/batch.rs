@@ -0,0 +1,383 @@
+use super::{
+    config::SecurityConfig,
+    error::ValidationError,
+    validation::{sanitize_and_validate, SanitizedInput, Validator},
+};
+use std::fmt::Debug;
+
+#[cfg(feature = "async")]
+use super::validation::sanitize_and_validate_async;
+#[cfg(feature = "async")]
+use futures::stream::{self, Stream, StreamExt};
+#[cfg(feature = "async")]
+use std::{
+    fmt,
+    marker::PhantomData,
+    num::NonZeroUsize,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+#[cfg(feature = "async")]
+use tokio::sync::{mpsc, Mutex};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Processes a slice of inputs in parallel, returning per-item results in input order
+#[cfg(feature = "rayon")]
+pub fn sanitize_and_validate_batch<'a, T, V>(
+    inputs: &[&'a str],
+    validator: &V,
+    config: &SecurityConfig,
+) -> Vec<Result<SanitizedInput<'a, T>, ValidationError>>
+where
+    T: Debug + Send + Sync,
+    V: Validator<T>,
+{
+    inputs
+        .par_iter()
+        .map(|input| sanitize_and_validate(input, validator, config))
+        .collect()
+}
+
+/// Validates inputs asynchronously with at most `concurrency` validations in flight
+///
+/// Results are returned in input order regardless of completion order. A
+/// concurrency of zero is treated as one. Results own their inputs, since
+/// items may be produced by the iterator on the fly.
+///
+/// ```rust
+/// use huginn::{sanitize_and_validate_batch_async, SecurityConfig, Validator, ValidationError};
+///
+/// struct Number;
+///
+/// impl Validator<u32> for Number {
+///     fn validate(&self, input: &str) -> Result<u32, ValidationError> {
+///         input.parse().map_err(|_| ValidationError::invalid_format("u32"))
+///     }
+///
+///     fn target_type(&self) -> &'static str {
+///         "u32"
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let config = SecurityConfig::default();
+/// let results = sanitize_and_validate_batch_async(["1", "x", "3"], &Number, &config, 2).await;
+/// assert_eq!(results[0].as_ref().unwrap().cleaned, 1);
+/// assert!(results[1].is_err());
+/// assert_eq!(results[2].as_ref().unwrap().cleaned, 3);
+/// # }
+/// ```
+#[cfg(feature = "async")]
+pub async fn sanitize_and_validate_batch_async<I, T, V>(
+    inputs: I,
+    validator: &V,
+    config: &SecurityConfig,
+    concurrency: usize,
+) -> Vec<Result<SanitizedInput<'static, T>, ValidationError>>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+    T: Debug + Send + Sync,
+    V: Validator<T>,
+{
+    stream::iter(inputs)
+        .map(|input| async move {
+            sanitize_and_validate_async(input.as_ref(), validator, config)
+                .await
+                .map(SanitizedInput::into_owned)
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await
+}
+
+/// Number of inputs [`validate_stream`] validates concurrently
+#[cfg(feature = "async")]
+pub const DEFAULT_STREAM_BUFFER: usize = 16;
+
+/// Validates a stream of inputs, such as messages from a queue or websocket, as they arrive
+///
+/// Up to [`DEFAULT_STREAM_BUFFER`] inputs are validated concurrently and
+/// results are yielded in input order. Use [`validate_stream_buffered`] to
+/// choose the buffer size.
+///
+/// ```rust
+/// use futures::{stream, StreamExt};
+/// use huginn::validators::FilenameValidator;
+/// use huginn::{validate_stream, SecurityConfig};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let messages = stream::iter(["notes.txt", "../../etc/passwd"].map(String::from));
+/// let config = SecurityConfig::default();
+/// let validator = FilenameValidator::new();
+///
+/// let results: Vec<_> = validate_stream(messages, &validator, &config).collect().await;
+/// assert_eq!(results[0].as_ref().unwrap().cleaned, "notes.txt");
+/// assert!(results[1].is_err());
+/// # }
+/// ```
+#[cfg(feature = "async")]
+pub fn validate_stream<'a, S, T, V>(
+    inputs: S,
+    validator: &'a V,
+    config: &'a SecurityConfig,
+) -> impl Stream<Item = Result<SanitizedInput<'static, T>, ValidationError>> + 'a
+where
+    S: Stream<Item = String> + 'a,
+    T: Debug + Send + Sync + 'a,
+    V: Validator<T>,
+{
+    validate_stream_buffered(inputs, validator, config, DEFAULT_STREAM_BUFFER)
+}
+
+/// Validates a stream of inputs with at most `buffer` validations in flight
+///
+/// Results are yielded in input order; a slow validation holds back the
+/// results behind it, and no further inputs are pulled from the source
+/// while `buffer` validations are pending. A buffer of zero is treated as
+/// one, which validates inputs strictly one after another.
+#[cfg(feature = "async")]
+pub fn validate_stream_buffered<'a, S, T, V>(
+    inputs: S,
+    validator: &'a V,
+    config: &'a SecurityConfig,
+    buffer: usize,
+) -> impl Stream<Item = Result<SanitizedInput<'static, T>, ValidationError>> + 'a
+where
+    S: Stream<Item = String> + 'a,
+    T: Debug + Send + Sync + 'a,
+    V: Validator<T>,
+{
+    inputs
+        .map(move |input| async move {
+            sanitize_and_validate_async(&input, validator, config)
+                .await
+                .map(SanitizedInput::into_owned)
+        })
+        .buffered(buffer.max(1))
+}
+
+/// Outcome of one input handled by a [`BatchProcessor`]
+#[cfg(feature = "async")]
+#[derive(Debug, Clone)]
+pub struct BatchItem<T> {
+    /// Sequence number returned by [`BatchSender::send`]
+    pub id: u64,
+    /// Input as submitted
+    pub input: String,
+    /// Validated value, or the reason the input was rejected
+    pub result: Result<T, ValidationError>,
+}
+
+/// Error returned when submitting to a [`BatchProcessor`] whose workers have stopped
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("batch processor is closed")]
+pub struct BatchClosed(
+    /// Input that could not be submitted
+    pub String,
+);
+
+/// Worker pool validating inputs from a bounded queue, for ingestion services
+///
+/// Inputs are submitted through a [`BatchSender`] and results read from
+/// [`BatchResults`] as workers finish them, so results arrive out of order
+/// and carry the id returned on submission. Both queues are bounded:
+/// [`BatchSender::send`] waits while the input queue is full, and workers
+/// stop taking inputs while nobody reads the results, so memory stays
+/// bounded when producers outrun validation. Results end once every sender
+/// has been dropped and the queued inputs are done.
+///
+/// Workers are Tokio tasks and must be started within a runtime. Validators
+/// doing CPU-heavy work synchronously should be run with
+/// [`blocking`](Self::blocking), which moves each validation to Tokio's
+/// blocking thread pool.
+///
+/// ```rust
+/// use huginn::batch::BatchProcessor;
+/// use huginn::validators::FilenameValidator;
+/// use huginn::SecurityConfig;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let (sender, mut results) = BatchProcessor::new(FilenameValidator::new(), SecurityConfig::default())
+///     .with_workers(4)
+///     .with_capacity(64)
+///     .start();
+///
+/// tokio::spawn(async move {
+///     for name in ["a.txt", "../../etc/passwd", "b.txt"] {
+///         sender.send(name).await.unwrap();
+///     }
+/// });
+///
+/// let mut rejected = Vec::new();
+/// while let Some(item) = results.recv().await {
+///     if item.result.is_err() {
+///         rejected.push(item.id);
+///     }
+/// }
+/// assert_eq!(rejected, [1]);
+/// # }
+/// ```
+#[cfg(feature = "async")]
+pub struct BatchProcessor<T, V> {
+    validator: Arc<V>,
+    config: Arc<SecurityConfig>,
+    workers: usize,
+    capacity: usize,
+    blocking: bool,
+    target: PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "async")]
+impl<T, V> fmt::Debug for BatchProcessor<T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchProcessor")
+            .field("workers", &self.workers)
+            .field("capacity", &self.capacity)
+            .field("blocking", &self.blocking)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T, V> BatchProcessor<T, V>
+where
+    T: Debug + Send + Sync + 'static,
+    V: Validator<T> + 'static,
+{
+    /// Creates processor with one worker per core and room for 1024 queued inputs and results
+    pub fn new(validator: V, config: SecurityConfig) -> Self {
+        Self {
+            validator: Arc::new(validator),
+            config: Arc::new(config),
+            workers: std::thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            capacity: 1024,
+            blocking: false,
+            target: PhantomData,
+        }
+    }
+
+    /// Sets the number of workers; zero is treated as one
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Sets how many inputs, and separately results, may be queued; zero is treated as one
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Validates synchronously on Tokio's blocking thread pool instead of awaiting `validate_async`
+    pub fn blocking(mut self) -> Self {
+        self.blocking = true;
+        self
+    }
+
+    /// Spawns the workers, returning the input and result ends of the pool
+    pub fn start(self) -> (BatchSender, BatchResults<T>) {
+        let (input_tx, input_rx) = mpsc::channel::<(u64, String)>(self.capacity);
+        let (result_tx, result_rx) = mpsc::channel(self.capacity);
+        let input_rx = Arc::new(Mutex::new(input_rx));
+
+        for _ in 0..self.workers {
+            let inputs = Arc::clone(&input_rx);
+            let results = result_tx.clone();
+            let validator = Arc::clone(&self.validator);
+            let config = Arc::clone(&self.config);
+            let blocking = self.blocking;
+            tokio::spawn(async move {
+                loop {
+                    // Hold the lock only while waiting, so other workers can validate
+                    // meanwhile, and stop waiting once nobody reads the results
+                    let next = tokio::select! {
+                        next = async { inputs.lock().await.recv().await } => next,
+                        () = results.closed() => None,
+                    };
+                    let Some((id, input)) = next else {
+                        break;
+                    };
+                    let result = if blocking {
+                        let (validator, config) = (Arc::clone(&validator), Arc::clone(&config));
+                        let owned = input.clone();
+                        tokio::task::spawn_blocking(move || {
+                            sanitize_and_validate(&owned, &*validator, &config)
+                                .map(|sanitized| sanitized.cleaned)
+                        })
+                        .await
+                        .unwrap_or_else(|err| Err(ValidationError::external(err)))
+                    } else {
+                        sanitize_and_validate_async(&input, &*validator, &config)
+                            .await
+                            .map(|sanitized| sanitized.cleaned)
+                    };
+                    if results.send(BatchItem { id, input, result }).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        let sender = BatchSender {
+            inputs: input_tx,
+            next_id: Arc::new(AtomicU64::new(0)),
+        };
+        (sender, BatchResults { results: result_rx })
+    }
+}
+
+/// Submitting end of a [`BatchProcessor`]; clone it to submit from several tasks
+#[cfg(feature = "async")]
+#[derive(Debug, Clone)]
+pub struct BatchSender {
+    inputs: mpsc::Sender<(u64, String)>,
+    next_id: Arc<AtomicU64>,
+}
+
+#[cfg(feature = "async")]
+impl BatchSender {
+    /// Queues an input, waiting while the queue is full, and returns its id
+    pub async fn send<S: Into<String>>(&self, input: S) -> Result<u64, BatchClosed> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.inputs
+            .send((id, input.into()))
+            .await
+            .map(|()| id)
+            .map_err(|err| BatchClosed(err.0 .1))
+    }
+}
+
+/// Receiving end of a [`BatchProcessor`], yielding results as workers finish them
+#[cfg(feature = "async")]
+#[derive(Debug)]
+pub struct BatchResults<T> {
+    results: mpsc::Receiver<BatchItem<T>>,
+}
+
+#[cfg(feature = "async")]
+impl<T> BatchResults<T> {
+    /// Waits for the next result, returning `None` once all inputs are done and every sender is dropped
+    pub async fn recv(&mut self) -> Option<BatchItem<T>> {
+        self.results.recv().await
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> Stream for BatchResults<T> {
+    type Item = BatchItem<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.results.poll_recv(cx)
+    }
+}
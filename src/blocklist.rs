@@ -0,0 +1,176 @@
+use super::{error::ValidationError, validation::Validator};
+use redis::aio::ConnectionLike;
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// Redis structure holding the blocked values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlocklistStore {
+    /// Plain set, queried with `SISMEMBER`
+    Set,
+    /// RedisBloom filter, queried with `BF.EXISTS`; false positives block valid input
+    BloomFilter,
+}
+
+/// Validator rejecting input found in a blocklist shared through Redis
+///
+/// Every instance of a service checks the same Redis key, so a token,
+/// username or URL blocked once is rejected fleet-wide. The blocklist can
+/// store the values themselves or their SHA-256 digests in lowercase hex,
+/// which keeps the raw values out of Redis.
+///
+/// Any async connection works; a `ConnectionManager` or multiplexed
+/// connection is cloned for every lookup. When Redis cannot be reached the
+/// input is rejected with [`ValidationError::External`], unless the
+/// validator was built with [`fail_open`](Self::fail_open). Only
+/// asynchronous validation talks to Redis; synchronous validation fails.
+///
+/// ```rust,no_run
+/// use huginn::blocklist::{BlocklistStore, DistributedBlocklistValidator};
+/// use huginn::{sanitize_and_validate_async, SecurityConfig};
+///
+/// # async fn check() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = redis::Client::open("redis://127.0.0.1/")?;
+/// let connection = client.get_multiplexed_tokio_connection().await?;
+/// let validator = DistributedBlocklistValidator::new(connection, "blocked:usernames")
+///     .with_store(BlocklistStore::Set)
+///     .case_insensitive();
+///
+/// validator.block("mallory").await?;
+/// let config = SecurityConfig::default();
+/// assert!(sanitize_and_validate_async("Mallory", &validator, &config).await.is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct DistributedBlocklistValidator<C> {
+    connection: C,
+    key: String,
+    store: BlocklistStore,
+    hashed: bool,
+    case_insensitive: bool,
+    fail_open: bool,
+}
+
+impl<C> fmt::Debug for DistributedBlocklistValidator<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DistributedBlocklistValidator")
+            .field("key", &self.key)
+            .field("store", &self.store)
+            .field("hashed", &self.hashed)
+            .field("case_insensitive", &self.case_insensitive)
+            .field("fail_open", &self.fail_open)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C> DistributedBlocklistValidator<C>
+where
+    C: ConnectionLike + Clone + Send + Sync,
+{
+    /// Creates validator looking up plain values in the Redis set at `key`
+    pub fn new<S: Into<String>>(connection: C, key: S) -> Self {
+        Self {
+            connection,
+            key: key.into(),
+            store: BlocklistStore::Set,
+            hashed: false,
+            case_insensitive: false,
+            fail_open: false,
+        }
+    }
+
+    /// Sets the Redis structure holding the blocked values
+    pub fn with_store(mut self, store: BlocklistStore) -> Self {
+        self.store = store;
+        self
+    }
+
+    /// Looks up the SHA-256 digest of the input instead of the input itself
+    pub fn hashed(mut self) -> Self {
+        self.hashed = true;
+        self
+    }
+
+    /// Lowercases input before it is looked up; blocked values must be stored lowercase
+    pub fn case_insensitive(mut self) -> Self {
+        self.case_insensitive = true;
+        self
+    }
+
+    /// Accepts input when Redis cannot be reached instead of rejecting it
+    pub fn fail_open(mut self) -> Self {
+        self.fail_open = true;
+        self
+    }
+
+    /// Adds a value to the blocklist, applying the same normalization as lookups
+    pub async fn block(&self, value: &str) -> Result<(), ValidationError> {
+        let command = match self.store {
+            BlocklistStore::Set => "SADD",
+            BlocklistStore::BloomFilter => "BF.ADD",
+        };
+        redis::cmd(command)
+            .arg(&self.key)
+            .arg(self.entry(value))
+            .query_async::<()>(&mut self.connection.clone())
+            .await
+            .map_err(ValidationError::external)
+    }
+
+    /// Returns true if the value is in the blocklist
+    pub async fn contains(&self, value: &str) -> Result<bool, ValidationError> {
+        let command = match self.store {
+            BlocklistStore::Set => "SISMEMBER",
+            BlocklistStore::BloomFilter => "BF.EXISTS",
+        };
+        redis::cmd(command)
+            .arg(&self.key)
+            .arg(self.entry(value))
+            .query_async::<bool>(&mut self.connection.clone())
+            .await
+            .map_err(ValidationError::external)
+    }
+
+    /// Returns the blocklist entry for a value
+    fn entry(&self, value: &str) -> String {
+        let value = if self.case_insensitive {
+            value.to_lowercase()
+        } else {
+            value.to_string()
+        };
+        if self.hashed {
+            Sha256::digest(value.as_bytes())
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect()
+        } else {
+            value
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<C> Validator<String> for DistributedBlocklistValidator<C>
+where
+    C: ConnectionLike + Clone + Send + Sync,
+{
+    fn validate(&self, _input: &str) -> Result<String, ValidationError> {
+        Err(ValidationError::custom(
+            "blocklist lookups are only available asynchronously",
+        ))
+    }
+
+    async fn validate_async(&self, input: &str) -> Result<String, ValidationError> {
+        match self.contains(input).await {
+            Ok(false) => Ok(input.to_string()),
+            Ok(true) => Err(ValidationError::custom("input is blocklisted")),
+            Err(_) if self.fail_open => Ok(input.to_string()),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn target_type(&self) -> &'static str {
+        "blocklist check"
+    }
+}
@@ -0,0 +1,167 @@
+use super::{
+    config::SecurityConfig,
+    error::{CharPosition, ValidationError},
+    validation::{
+        blocked_pattern, dangerous_characters, percent_decode_with, scan_forbidden, Validator,
+    },
+};
+use core::fmt::Debug;
+use heapless::{String, Vec};
+use smallvec::SmallVec;
+use thiserror::Error;
+
+/// Errors raised by the bounded pipeline
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BoundedError {
+    /// The text did not fit in the buffer
+    #[error("Input does not fit in {capacity} bytes")]
+    CapacityExceeded {
+        /// Capacity of the buffer in bytes
+        capacity: usize,
+    },
+
+    /// The input was rejected
+    #[error(transparent)]
+    Rejected(#[from] ValidationError),
+}
+
+/// Validated value with the screened text it was built from
+#[derive(Debug, Clone)]
+pub struct BoundedInput<T, const N: usize> {
+    /// Decoded and screened text handed to the validator
+    pub text: String<N>,
+    /// Validated value
+    pub cleaned: T,
+}
+
+/// Appends sanitized input to a fixed-capacity buffer, returning the number of removed characters
+///
+/// Like [`sanitize_into`](crate::validation::sanitize_into), but never
+/// allocates: if the sanitized input does not fit, the buffer is left as it
+/// was and [`BoundedError::CapacityExceeded`] is returned.
+///
+/// ```rust
+/// use huginn::bounded::{sanitize_into_bounded, BoundedError};
+/// use huginn::SecurityConfig;
+///
+/// let config = SecurityConfig::builder().add_forbidden_char('<').build();
+/// let mut out = heapless::String::<16>::new();
+/// assert_eq!(sanitize_into_bounded("<b>bold", &config, &mut out), Ok(1));
+/// assert_eq!(out, "b>bold");
+///
+/// let overflow = sanitize_into_bounded("far too long for the buffer", &config, &mut out);
+/// assert_eq!(overflow, Err(BoundedError::CapacityExceeded { capacity: 16 }));
+/// assert_eq!(out, "b>bold");
+/// ```
+pub fn sanitize_into_bounded<const N: usize>(
+    input: &str,
+    config: &SecurityConfig,
+    out: &mut String<N>,
+) -> Result<usize, BoundedError> {
+    let start = out.len();
+    let mut removed = 0;
+    let mut run_start = 0;
+    let mut fits = true;
+
+    scan_forbidden(input, config, |ch, byte_offset, _| {
+        fits &= out.push_str(&input[run_start..byte_offset]).is_ok();
+        removed += 1;
+        run_start = byte_offset + ch.len_utf8();
+    });
+    fits &= out.push_str(&input[run_start..]).is_ok();
+
+    if fits {
+        Ok(removed)
+    } else {
+        out.truncate(start);
+        Err(BoundedError::CapacityExceeded { capacity: N })
+    }
+}
+
+/// Main processing pipeline running in a buffer of `N` bytes
+///
+/// Input is decoded into the buffer, screened for forbidden characters and
+/// blocked patterns, then validated. Input that does not fit, before or
+/// after decoding, fails with [`BoundedError::CapacityExceeded`] rather than
+/// growing a buffer. Accepted input is processed without allocating, apart
+/// from what the validator and the regex engine do; only rejections build
+/// an allocated [`ValidationError`].
+///
+/// ```rust
+/// use huginn::bounded::{sanitize_and_validate_bounded, BoundedError};
+/// use huginn::{SecurityConfig, ValidationError, Validator};
+///
+/// struct Username;
+///
+/// impl Validator<usize> for Username {
+///     fn validate(&self, input: &str) -> Result<usize, ValidationError> {
+///         Ok(input.chars().count())
+///     }
+///
+///     fn target_type(&self) -> &'static str {
+///         "username"
+///     }
+/// }
+///
+/// let config = SecurityConfig::builder().add_forbidden_char('<').build();
+///
+/// let accepted = sanitize_and_validate_bounded::<_, 32>("jane%20doe", &Username, &config).unwrap();
+/// assert_eq!(accepted.text, "jane doe");
+/// assert_eq!(accepted.cleaned, 8);
+///
+/// let rejected = sanitize_and_validate_bounded::<_, 32>("%3Cscript", &Username, &config);
+/// assert!(matches!(rejected, Err(BoundedError::Rejected(ValidationError::DangerousCharacters { .. }))));
+///
+/// let exact = sanitize_and_validate_bounded::<_, 4>("jane", &Username, &config);
+/// assert!(exact.is_ok());
+/// let overflow = sanitize_and_validate_bounded::<_, 4>("janet", &Username, &config);
+/// assert_eq!(overflow.unwrap_err(), BoundedError::CapacityExceeded { capacity: 4 });
+/// ```
+pub fn sanitize_and_validate_bounded<T, const N: usize>(
+    input: &str,
+    validator: &impl Validator<T>,
+    config: &SecurityConfig,
+) -> Result<BoundedInput<T, N>, BoundedError>
+where
+    T: Debug + Send + Sync,
+{
+    let text = decode_bounded::<N>(input)?;
+
+    let mut positions: SmallVec<[CharPosition; 8]> = SmallVec::new();
+    scan_forbidden(&text, config, |ch, byte_offset, char_offset| {
+        positions.push(CharPosition {
+            ch,
+            byte_offset,
+            char_offset,
+        })
+    });
+    if !positions.is_empty() {
+        return Err(dangerous_characters(positions).into());
+    }
+
+    let matches = config.find_blocked_patterns(&text);
+    if !matches.is_empty() {
+        return Err(blocked_pattern(matches).into());
+    }
+
+    let cleaned = validator.validate(&text)?;
+    Ok(BoundedInput { text, cleaned })
+}
+
+/// URL-decodes input into a fixed-capacity buffer
+///
+/// Escapes that do not decode to UTF-8 leave the input as is, as in the
+/// unbounded pipeline.
+fn decode_bounded<const N: usize>(input: &str) -> Result<String<N>, BoundedError> {
+    let overflow = BoundedError::CapacityExceeded { capacity: N };
+    if input.contains('%') {
+        let mut decoded: Vec<u8, N> = Vec::new();
+        percent_decode_with(input.as_bytes(), |byte| decoded.push(byte))
+            .map_err(|_| overflow.clone())?;
+        if let Ok(text) = String::from_utf8(decoded) {
+            return Ok(text);
+        }
+    }
+    String::try_from(input).map_err(|_| overflow)
+}
@@ -0,0 +1,99 @@
+use super::{
+    config::SecurityConfig,
+    validation::{sanitize_and_validate, Validator},
+};
+use ::clap::{builder::TypedValueParser, error::ErrorKind, Arg, Command, Error};
+use std::{ffi::OsStr, fmt, fmt::Debug, marker::PhantomData, sync::Arc};
+
+/// Creates a value parser running arguments through the pipeline
+///
+/// ```rust
+/// use clap::{Arg, Command};
+/// use huginn::clap::sanitized_parser;
+/// use huginn::{SecurityConfig, ValidationError, Validator};
+///
+/// struct Hostname;
+///
+/// impl Validator<String> for Hostname {
+///     fn validate(&self, input: &str) -> Result<String, ValidationError> {
+///         if input.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-') {
+///             Ok(input.to_string())
+///         } else {
+///             Err(ValidationError::invalid_format(self.target_type()))
+///         }
+///     }
+///
+///     fn target_type(&self) -> &'static str {
+///         "hostname"
+///     }
+/// }
+///
+/// let config = SecurityConfig::default();
+/// let cmd = Command::new("ping")
+///     .arg(Arg::new("host").value_parser(sanitized_parser(Hostname, &config)));
+///
+/// let matches = cmd.clone().try_get_matches_from(["ping", "example.com"]).unwrap();
+/// assert_eq!(matches.get_one::<String>("host").unwrap(), "example.com");
+/// assert!(cmd.try_get_matches_from(["ping", "example.com;reboot"]).is_err());
+/// ```
+pub fn sanitized_parser<T, V>(validator: V, config: &SecurityConfig) -> SanitizedParser<T, V>
+where
+    V: Validator<T>,
+{
+    SanitizedParser {
+        validator: Arc::new(validator),
+        config: config.clone(),
+        _value: PhantomData,
+    }
+}
+
+/// Value parser created by [`sanitized_parser`]
+pub struct SanitizedParser<T, V> {
+    validator: Arc<V>,
+    config: SecurityConfig,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T, V> Clone for SanitizedParser<T, V> {
+    fn clone(&self) -> Self {
+        Self {
+            validator: Arc::clone(&self.validator),
+            config: self.config.clone(),
+            _value: PhantomData,
+        }
+    }
+}
+
+impl<T, V> Debug for SanitizedParser<T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SanitizedParser")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl<T, V> TypedValueParser for SanitizedParser<T, V>
+where
+    T: Debug + Clone + Send + Sync + 'static,
+    V: Validator<T> + 'static,
+{
+    type Value = T;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<T, Error> {
+        let input = value
+            .to_str()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+
+        sanitize_and_validate(input, &*self.validator, &self.config)
+            .map(|result| result.cleaned)
+            .map_err(|err| {
+                let arg = arg.map_or_else(|| "...".to_string(), |arg| arg.to_string());
+                let mut message = format!("invalid value '{}' for '{}': {}", input, arg, err);
+                if let Some(hint) = err.hint() {
+                    message.push_str(&format!("\n\n  tip: {}", hint));
+                }
+                message.push('\n');
+                Error::raw(ErrorKind::ValueValidation, message).with_cmd(cmd)
+            })
+    }
+}
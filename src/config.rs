@@ -1,39 +1,140 @@
+use super::error::PatternMatch;
+#[cfg(feature = "rayon")]
+use super::scan::{self, ChunkedScan};
+#[cfg(feature = "std")]
+use super::{
+    engine::{self, RegexSet},
+    error::PatternError,
+    expr::{ExprError, ExpressionRule, RuleExpr},
+    pattern::{check_pattern, compile_pattern, LazyPatterns, LazySource, Regex, RegexLimits},
+    rules::{PatternCategory, ThreatClass},
+    validators::SHELL_METACHARS,
+};
+use alloc::{sync::Arc, vec::Vec};
+#[cfg(feature = "std")]
 use lazy_static::lazy_static;
-use regex::Regex;
-use std::{collections::HashSet, sync::Arc};
+#[cfg(feature = "std")]
+use std::thread;
 
+/// Set of forbidden characters
+#[cfg(feature = "std")]
+type CharSet = std::collections::HashSet<char>;
+/// Set of forbidden characters, ordered since `alloc` has no hashed collections
+#[cfg(not(feature = "std"))]
+type CharSet = alloc::collections::BTreeSet<char>;
+
+#[cfg(feature = "std")]
 lazy_static! {
-    static ref DEFAULT_PATTERNS: Vec<Regex> = {
-        vec![
-            // SQL Injection
-            Regex::new(r"(?i)(drop\s+table|delete\s+from|insert\s+into|select\s+\*|union\s+all|update\s+.*\s+set|--|;|\bexec\b)").unwrap(),
-            // XSS
-            Regex::new(r"(?i)(<script>|javascript:|on\w+\s*=|alert\(|eval\(|document\.|window\.)").unwrap(),
-            // Path Traversal
-            Regex::new(r"(\.\./|\.\.\\|%2e%2e%2f|%2e%2e%5c)").unwrap(),
-            // Encoded attacks
-            Regex::new(r"(?:%[0-9a-fA-F]{2}){2,}").unwrap(),
-            // Command Injection
-            Regex::new(r"(?i)(\||&&|;|`|\$\(|\bexec\b|\bsystem\b|\brm\b|\bdel\b)").unwrap(),
-        ]
-    };
+    /// Default configuration compiled once per process
+    pub(crate) static ref DEFAULT_CONFIG: SecurityConfig = SecurityConfig::builder()
+        .with_default_forbidden_chars()
+        .with_default_blocked_patterns()
+        .build();
+
+    /// Configuration for values placed into HTTP headers
+    static ref HEADER_CONFIG: SecurityConfig = SecurityConfig::builder()
+        .with_control_chars()
+        .with_category(PatternCategory::Encoding)
+        .build();
+
+    /// Configuration for values bound as SQL query parameters
+    static ref SQL_PARAM_CONFIG: SecurityConfig = SecurityConfig::builder()
+        .with_control_chars()
+        .with_category(PatternCategory::SqlInjection)
+        .build();
+
+    /// Configuration for values passed as subprocess arguments
+    static ref SHELL_ARG_CONFIG: SecurityConfig = SHELL_METACHARS
+        .iter()
+        .chain(&[' ', '\t'])
+        .fold(SecurityConfig::builder().with_control_chars(), |builder, c| {
+            builder.add_forbidden_char(*c)
+        })
+        .build();
+}
+
+/// Named regular expression used to block dangerous input
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct PatternRule {
+    /// Rule name reported when the pattern matches
+    pub name: String,
+    /// Compiled regular expression
+    pub regex: Regex,
+    /// Built-in category of the rule, `None` for user-supplied patterns
+    pub category: Option<PatternCategory>,
+}
+
+#[cfg(feature = "std")]
+impl PatternRule {
+    /// Creates a named rule from a compiled regex
+    pub fn new<S: Into<String>>(name: S, regex: Regex) -> Self {
+        Self {
+            name: name.into(),
+            regex,
+            category: None,
+        }
+    }
+
+    /// Assigns the rule to a category
+    pub fn with_category(mut self, category: PatternCategory) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Returns the attack type of the rule's findings, `Custom` for user-supplied patterns
+    pub fn threat(&self) -> ThreatClass {
+        self.category
+            .map_or(ThreatClass::Custom, |category| category.threat())
+    }
 }
 
 /// Security configuration parameters
+///
+/// Without the `std` feature only forbidden characters are available:
+/// blocked patterns need a regex backend, which requires `std`.
 #[derive(Debug, Clone)]
 pub struct SecurityConfig {
     /// Set of forbidden characters
-    pub forbidden_chars: Arc<HashSet<char>>,
-    /// Compiled regular expressions for blocking dangerous patterns
-    pub blocked_patterns: Arc<Vec<Regex>>,
+    forbidden_chars: Arc<CharSet>,
+    /// Named regular expressions for blocking dangerous patterns
+    #[cfg(feature = "std")]
+    blocked_patterns: Arc<Vec<PatternRule>>,
+    /// All blocked patterns compiled into one set for single-pass matching
+    ///
+    /// `None` when the combined set exceeds regex size limits, in which case
+    /// patterns are scanned one by one.
+    #[cfg(feature = "std")]
+    pattern_set: Option<Arc<RegexSet>>,
+    /// Byte lookup table of the ASCII forbidden characters
+    ascii_forbidden: [bool; 128],
+    /// Whether every forbidden character is ASCII
+    forbids_only_ascii: bool,
+    /// Patterns compiled on first use, reported after `blocked_patterns`
+    #[cfg(feature = "std")]
+    lazy_patterns: Arc<LazyPatterns>,
+    /// Rule expressions, checked against the whole input after all patterns
+    #[cfg(feature = "std")]
+    pub expression_rules: Arc<Vec<ExpressionRule>>,
+    /// Parallel scanning settings for very large inputs
+    #[cfg(feature = "rayon")]
+    chunked_scan: Option<ChunkedScan>,
 }
 
 impl Default for SecurityConfig {
     /// Creates default configuration with recommended security settings
+    ///
+    /// The configuration is compiled once and shared, so this is a cheap clone.
+    #[cfg(feature = "std")]
     fn default() -> Self {
-        Self::builder()
+        DEFAULT_CONFIG.clone()
+    }
+
+    /// Creates configuration forbidding the default characters
+    #[cfg(not(feature = "std"))]
+    fn default() -> Self {
+        SecurityConfig::builder()
             .with_default_forbidden_chars()
-            .with_default_blocked_patterns()
             .build()
     }
 }
@@ -44,23 +145,272 @@ impl SecurityConfig {
         SecurityConfigBuilder::new()
     }
 
+    /// Returns configuration for values placed into HTTP headers
+    ///
+    /// CR, LF and the other control characters are forbidden so a value cannot
+    /// start a new header line; encoded sequences are blocked. Characters such
+    /// as `;` and `"` that legitimately appear in header values are allowed.
+    #[cfg(feature = "std")]
+    pub fn for_headers() -> Self {
+        HEADER_CONFIG.clone()
+    }
+
+    /// Returns configuration for values bound as SQL query parameters
+    ///
+    /// Quotes, `;` and other characters that are harmless in a bound value are
+    /// allowed, so names like `O'Brien` pass. Control characters are forbidden
+    /// and SQL injection patterns are still blocked, catching payloads aimed
+    /// at queries that later interpolate the stored value.
+    #[cfg(feature = "std")]
+    pub fn for_sql_params() -> Self {
+        SQL_PARAM_CONFIG.clone()
+    }
+
+    /// Returns configuration for values passed as subprocess arguments
+    ///
+    /// Every character a POSIX shell or bash treats specially is forbidden:
+    /// whitespace, control characters, quotes, redirection and chaining
+    /// operators, `$` and backticks, globbing characters, `~`, `!`, `#`, `=`,
+    /// `%`, braces and `^`. Unlike the command injection patterns, this does
+    /// not depend on recognizing a command name.
+    #[cfg(feature = "std")]
+    pub fn for_shell_args() -> Self {
+        SHELL_ARG_CONFIG.clone()
+    }
+
+    /// Returns the set of forbidden characters
+    ///
+    /// A `HashSet`, or a `BTreeSet` without the `std` feature.
+    pub fn forbidden_chars(&self) -> &CharSet {
+        &self.forbidden_chars
+    }
+
+    /// Replaces the forbidden characters, rebuilding the lookup table
+    ///
+    /// ```rust
+    /// use huginn::SecurityConfig;
+    ///
+    /// let config = SecurityConfig::default();
+    /// let mut chars = config.forbidden_chars().clone();
+    /// chars.insert('#');
+    /// let config = config.with_forbidden_chars(chars);
+    /// assert!(config.is_char_forbidden(&'#'));
+    /// ```
+    pub fn with_forbidden_chars(mut self, chars: CharSet) -> Self {
+        (self.ascii_forbidden, self.forbids_only_ascii) = ascii_table(&chars);
+        self.forbidden_chars = Arc::new(chars);
+        self
+    }
+
+    /// Returns the blocked patterns, excluding lazily added ones
+    #[cfg(feature = "std")]
+    pub fn blocked_patterns(&self) -> &[PatternRule] {
+        &self.blocked_patterns
+    }
+
+    /// Replaces the blocked patterns, recompiling the pattern set
+    ///
+    /// Lazily added patterns and rule expressions are kept.
+    ///
+    /// ```rust
+    /// use huginn::{pattern::Regex, PatternRule, SecurityConfig};
+    ///
+    /// let config = SecurityConfig::default();
+    /// let mut patterns = config.blocked_patterns().to_vec();
+    /// patterns.push(PatternRule::new("tenant_secret", Regex::new("acme-internal").unwrap()));
+    /// let config = config.with_blocked_patterns(patterns);
+    /// assert!(config.has_blocked_pattern("acme-internal"));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn with_blocked_patterns(mut self, patterns: Vec<PatternRule>) -> Self {
+        self.pattern_set =
+            engine::regex_set(patterns.iter().map(|r| r.regex.as_str())).map(Arc::new);
+        self.blocked_patterns = Arc::new(patterns);
+        self
+    }
+
     /// Checks if character is forbidden
     #[inline(always)]
     pub fn is_char_forbidden(&self, c: &char) -> bool {
-        self.forbidden_chars.contains(c)
+        if c.is_ascii() {
+            self.ascii_forbidden[*c as usize]
+        } else {
+            !self.forbids_only_ascii && self.forbidden_chars.contains(c)
+        }
+    }
+
+    /// Returns the byte lookup table if it fully describes the forbidden set for the input
+    ///
+    /// This holds when every forbidden character is ASCII or the input itself is
+    /// pure ASCII. Non-ASCII bytes never match the table, so UTF-8 input can then
+    /// be scanned byte by byte without decoding.
+    #[inline(always)]
+    pub(crate) fn ascii_forbidden_for(&self, input: &str) -> Option<&[bool; 128]> {
+        (self.forbids_only_ascii || input.is_ascii()).then_some(&self.ascii_forbidden)
     }
 
     /// Checks if input matches any blocked pattern
+    ///
+    /// Always false without the `std` feature.
     pub fn has_blocked_pattern(&self, input: &str) -> bool {
-        self.blocked_patterns.iter().any(|re| re.is_match(input))
+        #[cfg(feature = "rayon")]
+        if let Some(scan) = self.chunked_scan.filter(|scan| scan.applies_to(input)) {
+            return scan::has_blocked_pattern(self, input, &scan)
+                || self.has_blocked_expression(input);
+        }
+        self.has_blocked_pattern_in(input) || self.has_blocked_expression(input)
+    }
+
+    /// Returns every blocked pattern matching the input with its first matched span
+    ///
+    /// Always empty without the `std` feature.
+    pub fn find_blocked_patterns(&self, input: &str) -> Vec<PatternMatch> {
+        #[cfg(feature = "rayon")]
+        if let Some(scan) = self.chunked_scan.filter(|scan| scan.applies_to(input)) {
+            let mut matches = scan::find_blocked_patterns(self, input, &scan);
+            matches.extend(self.find_blocked_expressions(input));
+            return matches;
+        }
+        let mut matches = self.find_blocked_patterns_in(input);
+        matches.extend(self.find_blocked_expressions(input));
+        matches
+    }
+
+    /// Compiles lazily added patterns now, reporting the first that failed
+    ///
+    /// Patterns that fail to compile match every input, so the configuration
+    /// blocks everything rather than letting input through unchecked. Call
+    /// this after building a lazy configuration to surface such failures.
+    #[cfg(feature = "std")]
+    pub fn warm_up(&self) -> Result<(), PatternError> {
+        self.lazy_patterns.warm_up()
+    }
+
+    /// Compiles lazily added patterns on a background thread
+    #[cfg(feature = "std")]
+    pub fn warm_up_in_background(&self) -> thread::JoinHandle<Result<(), PatternError>> {
+        let lazy_patterns = Arc::clone(&self.lazy_patterns);
+        thread::spawn(move || lazy_patterns.warm_up())
+    }
+
+    /// Returns the sources of lazily added patterns, in the order they were added
+    #[cfg(feature = "serde")]
+    pub(crate) fn lazy_sources(&self) -> &[LazySource] {
+        self.lazy_patterns.sources()
+    }
+
+    /// Checks a single contiguous slice for blocked patterns
+    #[cfg(feature = "std")]
+    pub(crate) fn has_blocked_pattern_in(&self, input: &str) -> bool {
+        let eager = match &self.pattern_set {
+            Some(set) => set.is_match(input),
+            None => self
+                .blocked_patterns
+                .iter()
+                .any(|rule| rule.regex.is_match(input)),
+        };
+        eager || self.lazy_patterns.is_match(input)
+    }
+
+    /// Finds blocked patterns in a single contiguous slice
+    #[cfg(feature = "std")]
+    pub(crate) fn find_blocked_patterns_in(&self, input: &str) -> Vec<PatternMatch> {
+        let mut matches: Vec<PatternMatch> = match &self.pattern_set {
+            Some(set) => set
+                .matches(input)
+                .into_iter()
+                .filter_map(|index| self.match_rule(index, input))
+                .collect(),
+            None => (0..self.blocked_patterns.len())
+                .filter_map(|index| self.match_rule(index, input))
+                .collect(),
+        };
+        matches.extend(self.lazy_patterns.find(input, self.blocked_patterns.len()));
+        matches
+    }
+
+    /// Checks the whole input against the rule expressions
+    ///
+    /// Expressions see the whole input rather than chunks, since conditions
+    /// such as `length` and `not` do not hold piecewise.
+    #[cfg(feature = "std")]
+    fn has_blocked_expression(&self, input: &str) -> bool {
+        self.expression_rules
+            .iter()
+            .any(|rule| rule.expr.is_match(input))
+    }
+
+    /// Finds matching rule expressions, indexed after all patterns
+    #[cfg(feature = "std")]
+    fn find_blocked_expressions(&self, input: &str) -> Vec<PatternMatch> {
+        let offset = self.blocked_patterns.len() + self.lazy_patterns.len();
+        self.expression_rules
+            .iter()
+            .enumerate()
+            .filter_map(|(i, rule)| {
+                rule.expr.find(input).map(|span| PatternMatch {
+                    name: rule.name.clone(),
+                    index: offset + i,
+                    span,
+                    threat: ThreatClass::Custom,
+                })
+            })
+            .collect()
+    }
+
+    /// Never finds a match, since expressions cannot be added without `std`
+    #[cfg(not(feature = "std"))]
+    fn has_blocked_expression(&self, _input: &str) -> bool {
+        false
+    }
+
+    /// Never finds a match, since expressions cannot be added without `std`
+    #[cfg(not(feature = "std"))]
+    fn find_blocked_expressions(&self, _input: &str) -> Vec<PatternMatch> {
+        Vec::new()
+    }
+
+    /// Never finds a match, since patterns cannot be added without `std`
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn has_blocked_pattern_in(&self, _input: &str) -> bool {
+        false
+    }
+
+    /// Never finds a match, since patterns cannot be added without `std`
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn find_blocked_patterns_in(&self, _input: &str) -> Vec<PatternMatch> {
+        Vec::new()
+    }
+
+    /// Extracts the first match span of a single rule
+    #[cfg(feature = "std")]
+    fn match_rule(&self, index: usize, input: &str) -> Option<PatternMatch> {
+        let rule = &self.blocked_patterns[index];
+        rule.regex.find(input).map(|m| PatternMatch {
+            name: rule.name.clone(),
+            index,
+            span: m.range(),
+            threat: rule.threat(),
+        })
     }
 }
 
 /// Builder pattern for SecurityConfig
 #[derive(Debug, Default)]
 pub struct SecurityConfigBuilder {
-    forbidden_chars: HashSet<char>,
-    blocked_patterns: Vec<Regex>,
+    forbidden_chars: CharSet,
+    #[cfg(feature = "std")]
+    blocked_patterns: Vec<PatternRule>,
+    #[cfg(feature = "std")]
+    regex_limits: RegexLimits,
+    #[cfg(feature = "std")]
+    lazy_compilation: bool,
+    #[cfg(feature = "std")]
+    lazy_patterns: Vec<LazySource>,
+    #[cfg(feature = "std")]
+    expression_rules: Vec<ExpressionRule>,
+    #[cfg(feature = "rayon")]
+    chunked_scan: Option<ChunkedScan>,
 }
 
 impl SecurityConfigBuilder {
@@ -76,9 +426,31 @@ impl SecurityConfigBuilder {
         self
     }
 
-    /// Adds default blocked patterns
-    pub fn with_default_blocked_patterns(mut self) -> Self {
-        self.blocked_patterns.extend(DEFAULT_PATTERNS.clone());
+    /// Adds ASCII control characters except horizontal tab, including DEL
+    pub fn with_control_chars(mut self) -> Self {
+        self.forbidden_chars.extend(
+            (0u8..0x20)
+                .chain([0x7f])
+                .filter(|&b| b != b'\t')
+                .map(char::from),
+        );
+        self
+    }
+
+    /// Adds default blocked patterns (every category in [`PatternCategory::DEFAULT`])
+    #[cfg(feature = "std")]
+    pub fn with_default_blocked_patterns(self) -> Self {
+        PatternCategory::DEFAULT
+            .iter()
+            .fold(self, |builder, category| builder.with_category(*category))
+    }
+
+    /// Adds the built-in rules of a pattern category
+    #[cfg(feature = "std")]
+    pub fn with_category(mut self, category: PatternCategory) -> Self {
+        for rule in category.rules() {
+            self.push_rule(rule.clone());
+        }
         self
     }
 
@@ -88,17 +460,168 @@ impl SecurityConfigBuilder {
         self
     }
 
-    /// Adds a blocked pattern
-    pub fn add_blocked_pattern(mut self, pattern: &str) -> Result<Self, regex::Error> {
-        self.blocked_patterns.push(Regex::new(pattern)?);
+    /// Sets the limits applied to patterns added after this call
+    #[cfg(feature = "std")]
+    pub fn with_regex_limits(mut self, limits: RegexLimits) -> Self {
+        self.regex_limits = limits;
+        self
+    }
+
+    /// Scans inputs beyond the configured size in parallel, overlapping chunks
+    #[cfg(feature = "rayon")]
+    pub fn with_chunked_scan(mut self, scan: ChunkedScan) -> Self {
+        self.chunked_scan = Some(scan);
+        self
+    }
+
+    /// Defers compilation of patterns added after this call until first use
+    ///
+    /// Syntax and complexity are still checked immediately; only compilation is
+    /// deferred. A pattern that still fails to compile, for example by
+    /// exceeding the size limit, fails closed: it is reported as matching
+    /// every input. See [`SecurityConfig::warm_up`].
+    ///
+    /// ```rust
+    /// use huginn::SecurityConfig;
+    ///
+    /// let config = SecurityConfig::builder()
+    ///     .lazy_compilation(true)
+    ///     .add_named_blocked_pattern("tenant_secret", r"(?i)acme-internal")?
+    ///     .build();
+    /// config.warm_up_in_background();
+    /// assert!(config.has_blocked_pattern("see ACME-INTERNAL docs"));
+    /// # Ok::<(), huginn::PatternError>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn lazy_compilation(mut self, enabled: bool) -> Self {
+        self.lazy_compilation = enabled;
+        self
+    }
+
+    /// Adds a blocked pattern named after its source text
+    ///
+    /// Patterns that exceed the configured [`RegexLimits`] are rejected.
+    #[cfg(feature = "std")]
+    pub fn add_blocked_pattern(self, pattern: &str) -> Result<Self, PatternError> {
+        self.add_named_blocked_pattern(pattern, pattern)
+    }
+
+    /// Adds a blocked pattern reported under the given name
+    ///
+    /// Patterns identical to one already added are ignored.
+    #[cfg(feature = "std")]
+    pub fn add_named_blocked_pattern(
+        mut self,
+        name: &str,
+        pattern: &str,
+    ) -> Result<Self, PatternError> {
+        if self.lazy_compilation {
+            check_pattern(pattern, &self.regex_limits)?;
+            if !self.has_pattern(pattern) {
+                self.lazy_patterns.push(LazySource {
+                    name: name.to_string(),
+                    pattern: pattern.to_string(),
+                    limits: self.regex_limits,
+                });
+            }
+            return Ok(self);
+        }
+
+        let regex = compile_pattern(pattern, &self.regex_limits)?;
+        self.push_rule(PatternRule::new(name, regex));
+        Ok(self)
+    }
+
+    /// Adds a rule expression reported under the given name
+    ///
+    /// Patterns inside the expression are held to the configured
+    /// [`RegexLimits`]. Expressions identical to one already added are
+    /// ignored. See [`RuleExpr`] for the syntax.
+    ///
+    /// ```rust
+    /// use huginn::SecurityConfig;
+    ///
+    /// let config = SecurityConfig::builder()
+    ///     .add_rule_expression(
+    ///         "union_select",
+    ///         r#"contains("union") and contains("select") within 40 chars"#,
+    ///     )?
+    ///     .add_rule_expression("oversized", "length > 1000")?
+    ///     .build();
+    /// assert!(config.has_blocked_pattern("1 UNION ALL SELECT password FROM users"));
+    /// assert!(!config.has_blocked_pattern("a short note"));
+    /// # Ok::<(), huginn::expr::ExprError>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn add_rule_expression(mut self, name: &str, source: &str) -> Result<Self, ExprError> {
+        let expr = RuleExpr::parse_with_limits(source, &self.regex_limits)?;
+        self.push_expression(ExpressionRule::new(name, expr));
         Ok(self)
     }
 
+    /// Adds a rule expression unless one with identical source is already present
+    #[cfg(feature = "std")]
+    pub(crate) fn push_expression(&mut self, rule: ExpressionRule) {
+        if !self
+            .expression_rules
+            .iter()
+            .any(|existing| existing.expr.source() == rule.expr.source())
+        {
+            self.expression_rules.push(rule);
+        }
+    }
+
+    /// Checks whether a pattern with identical source was already added
+    #[cfg(feature = "std")]
+    fn has_pattern(&self, source: &str) -> bool {
+        self.blocked_patterns
+            .iter()
+            .any(|existing| existing.regex.as_str() == source)
+            || self
+                .lazy_patterns
+                .iter()
+                .any(|existing| existing.pattern == source)
+    }
+
+    /// Adds a rule unless a pattern with identical source is already present
+    #[cfg(feature = "std")]
+    pub(crate) fn push_rule(&mut self, rule: PatternRule) {
+        if !self.has_pattern(rule.regex.as_str()) {
+            self.blocked_patterns.push(rule);
+        }
+    }
+
     /// Finalizes the configuration
     pub fn build(self) -> SecurityConfig {
+        #[cfg(feature = "std")]
+        let pattern_set =
+            engine::regex_set(self.blocked_patterns.iter().map(|r| r.regex.as_str())).map(Arc::new);
+
+        let (ascii_forbidden, forbids_only_ascii) = ascii_table(&self.forbidden_chars);
+
         SecurityConfig {
             forbidden_chars: Arc::new(self.forbidden_chars),
+            #[cfg(feature = "std")]
             blocked_patterns: Arc::new(self.blocked_patterns),
+            #[cfg(feature = "std")]
+            pattern_set,
+            ascii_forbidden,
+            forbids_only_ascii,
+            #[cfg(feature = "std")]
+            lazy_patterns: Arc::new(LazyPatterns::new(self.lazy_patterns)),
+            #[cfg(feature = "std")]
+            expression_rules: Arc::new(self.expression_rules),
+            #[cfg(feature = "rayon")]
+            chunked_scan: self.chunked_scan,
         }
     }
 }
+
+/// Builds the ASCII lookup table of a character set and whether it covers every character
+fn ascii_table(chars: &CharSet) -> ([bool; 128], bool) {
+    let mut table = [false; 128];
+    for c in chars.iter().filter(|c| c.is_ascii()) {
+        table[*c as usize] = true;
+    }
+    (table, chars.iter().all(char::is_ascii))
+}
@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+#[cfg(feature = "async")]
+use tokio_util::sync::CancellationToken;
+
+/// Request details passed along with an input, such as client address or user id
+///
+/// The pipeline does not interpret the fields; they are handed to rejection
+/// callbacks so applications can decide whom to challenge or ban. A
+/// cancellation token set on the context aborts asynchronous validation once
+/// cancelled, for example when the client disconnects.
+///
+/// ```rust
+/// use huginn::ValidationContext;
+///
+/// let context = ValidationContext::new()
+///     .with("ip", "203.0.113.7")
+///     .with("user", "alice");
+/// assert_eq!(context.get("ip"), Some("203.0.113.7"));
+/// assert_eq!(context.get("session"), None);
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ValidationContext {
+    fields: BTreeMap<String, String>,
+    #[cfg(feature = "async")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    cancel: Option<CancellationToken>,
+}
+
+impl ValidationContext {
+    /// Creates empty context
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a field, replacing any previous value
+    pub fn with<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    /// Sets a field in place, replacing any previous value
+    pub fn insert<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) {
+        self.fields.insert(key.into(), value.into());
+    }
+
+    /// Returns the value of a field
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields.get(key).map(String::as_str)
+    }
+
+    /// Sets the token that aborts asynchronous validation when cancelled
+    #[cfg(feature = "async")]
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Returns the cancellation token, if set
+    #[cfg(feature = "async")]
+    pub fn cancellation(&self) -> Option<&CancellationToken> {
+        self.cancel.as_ref()
+    }
+
+    /// Returns true if no fields are set
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Iterates over the fields in key order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.fields.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
@@ -0,0 +1,216 @@
+use super::{
+    config::SecurityConfig,
+    error::{ValidationError, ValidationErrors},
+    validation::{boxed_check, prepare_input, BoxedCheck, Validator},
+};
+use std::{borrow::Cow, fmt, fmt::Debug, io};
+
+/// Characters that make spreadsheet applications evaluate a cell as a formula
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// Returns true if a cell would be evaluated as a formula when opened in a spreadsheet
+///
+/// Leading spaces are ignored. Plain numbers such as `-5` or `+1.5` are not
+/// considered formulas.
+///
+/// ```rust
+/// use huginn::csv::is_formula;
+///
+/// assert!(is_formula("=HYPERLINK(\"http://evil.example\")"));
+/// assert!(is_formula("  @SUM(A1:A2)"));
+/// assert!(!is_formula("-42.5"));
+/// ```
+pub fn is_formula(cell: &str) -> bool {
+    let trimmed = cell.trim_start_matches(' ');
+    trimmed.starts_with(FORMULA_PREFIXES) && trimmed.trim().parse::<f64>().is_err()
+}
+
+/// Prefixes formula cells with `'` so spreadsheets display them as text
+pub fn neutralize_formula(cell: &str) -> Cow<'_, str> {
+    if is_formula(cell) {
+        Cow::Owned(format!("'{}", cell))
+    } else {
+        Cow::Borrowed(cell)
+    }
+}
+
+/// How cells that look like spreadsheet formulas are handled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FormulaPolicy {
+    /// Report formula cells as errors
+    #[default]
+    Reject,
+    /// Prefix formula cells with `'` in the output
+    Neutralize,
+}
+
+/// Rules applied to every cell of a CSV document
+///
+/// ```rust
+/// use huginn::csv::{sanitize_csv, CsvSchema, FormulaPolicy};
+/// use huginn::SecurityConfig;
+///
+/// let data = "name,comment\nalice,hello\nbob,=cmd|' /C calc'!A0\n";
+/// let config = SecurityConfig::default();
+///
+/// let errors = sanitize_csv(data.as_bytes(), &config, &CsvSchema::new()).unwrap_err();
+/// assert_eq!(errors.for_field("[2].comment").count(), 1);
+///
+/// // Formulas are recognized after URL decoding, as the cell is returned
+/// assert!(sanitize_csv("total\n%3DHYPERLINK(A1)\n".as_bytes(), &config, &CsvSchema::new()).is_err());
+///
+/// let schema = CsvSchema::new().with_formula_policy(FormulaPolicy::Neutralize);
+/// let rows = sanitize_csv("total\n=1+2\n".as_bytes(), &config, &schema).unwrap();
+/// assert_eq!(rows[1][0], "'=1+2");
+/// ```
+pub struct CsvSchema {
+    columns: Vec<(String, BoxedCheck)>,
+    formula_policy: FormulaPolicy,
+    has_headers: bool,
+}
+
+impl Default for CsvSchema {
+    fn default() -> Self {
+        Self {
+            columns: Vec::new(),
+            formula_policy: FormulaPolicy::default(),
+            has_headers: true,
+        }
+    }
+}
+
+impl Debug for CsvSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CsvSchema")
+            .field(
+                "columns",
+                &self
+                    .columns
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .field("formula_policy", &self.formula_policy)
+            .field("has_headers", &self.has_headers)
+            .finish()
+    }
+}
+
+impl CsvSchema {
+    /// Creates new schema that screens every cell and rejects formulas
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validates cells of the column with the given header name
+    pub fn with_column<T, V>(mut self, name: &str, validator: V) -> Self
+    where
+        T: Debug + Send + Sync,
+        V: Validator<T> + 'static,
+    {
+        self.columns
+            .push((name.to_string(), boxed_check(validator)));
+        self
+    }
+
+    /// Sets how formula cells are handled
+    pub fn with_formula_policy(mut self, policy: FormulaPolicy) -> Self {
+        self.formula_policy = policy;
+        self
+    }
+
+    /// Sets whether the first record is a header row (true by default)
+    pub fn has_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+}
+
+/// Reads a CSV document and screens every cell, collecting all failures
+///
+/// Returns the records, including the header row when present. Errors are
+/// keyed as `[row].column` using the header name, or `[row][column]` without
+/// headers, where rows count from zero including the header row.
+pub fn sanitize_csv(
+    reader: impl io::Read,
+    config: &SecurityConfig,
+    schema: &CsvSchema,
+) -> Result<Vec<Vec<String>>, ValidationErrors> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(reader);
+
+    let mut rows = Vec::new();
+    let mut headers: Vec<String> = Vec::new();
+    let mut errors = ValidationErrors::new();
+
+    for (row, record) in reader.records().enumerate() {
+        let record = match record {
+            Ok(record) => record,
+            Err(err) => {
+                errors.push_field(
+                    format!("[{}]", row),
+                    ValidationError::custom(err.to_string()),
+                );
+                break;
+            }
+        };
+
+        let is_header = schema.has_headers && row == 0;
+        let mut cells = Vec::with_capacity(record.len());
+        for (column, cell) in record.iter().enumerate() {
+            let name = headers.get(column).map(String::as_str);
+            let path = match name {
+                Some(name) => format!("[{}].{}", row, name),
+                None => format!("[{}][{}]", row, column),
+            };
+            let check = name
+                .filter(|_| !is_header)
+                .and_then(|name| schema.columns.iter().find(|(n, _)| n == name))
+                .map(|(_, check)| check);
+            match sanitize_cell(cell, config, schema.formula_policy, check) {
+                Ok(cell) => cells.push(cell),
+                Err(error) => {
+                    errors.push_field(path, error);
+                    cells.push(cell.to_string());
+                }
+            }
+        }
+
+        if is_header {
+            headers = cells.clone();
+        }
+        rows.push(cells);
+    }
+
+    if errors.is_empty() {
+        Ok(rows)
+    } else {
+        Err(errors)
+    }
+}
+
+fn sanitize_cell(
+    cell: &str,
+    config: &SecurityConfig,
+    policy: FormulaPolicy,
+    check: Option<&BoxedCheck>,
+) -> Result<String, ValidationError> {
+    let cleaned = match check {
+        Some(check) => check(cell, config)?,
+        None => prepare_input(cell, config, &[])?.into_owned(),
+    };
+
+    if !is_formula(&cleaned) {
+        return Ok(cleaned);
+    }
+    match policy {
+        FormulaPolicy::Reject => Err(ValidationError::custom(
+            "Cell starts with a spreadsheet formula",
+        )
+        .with_hint("remove the leading =, +, -, @ or control character")),
+        FormulaPolicy::Neutralize => Ok(format!("'{}", cleaned)),
+    }
+}
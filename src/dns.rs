@@ -0,0 +1,342 @@
+use super::{
+    error::ValidationError,
+    validation::Validator,
+    validators::{EmailValidator, HostnameValidator},
+};
+use hickory_resolver::{ResolveError, TokioResolver};
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// What a lookup found for a domain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Found {
+    /// The domain has records of the requested type
+    Records,
+    /// The domain has a null MX record (RFC 7505), declaring it accepts no mail
+    NullMx,
+    /// The domain does not exist or has no records of the requested type
+    Nothing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Query {
+    Address,
+    Mail,
+}
+
+/// Cached answers by query and domain, with their expiry
+type Cache = HashMap<(Query, String), (Found, Instant)>;
+
+/// Cached DNS lookups used by [`DnsHostnameValidator`] and [`DnsEmailValidator`]
+///
+/// Answers, including missing domains, are cached for the configured time
+/// so repeated inputs do not hit the resolver; lookups that fail for
+/// other reasons are not cached. Clones share the resolver and cache, so
+/// create one `DnsLookup` and hand it to every validator.
+#[derive(Clone)]
+pub struct DnsLookup {
+    resolver: TokioResolver,
+    timeout: Duration,
+    ttl: Duration,
+    capacity: usize,
+    cache: Arc<Mutex<Cache>>,
+}
+
+impl fmt::Debug for DnsLookup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DnsLookup")
+            .field("timeout", &self.timeout)
+            .field("ttl", &self.ttl)
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DnsLookup {
+    /// Creates lookups through the given resolver, with a 2 second timeout and 5 minute cache
+    pub fn new(resolver: TokioResolver) -> Self {
+        Self {
+            resolver,
+            timeout: Duration::from_secs(2),
+            ttl: Duration::from_secs(300),
+            capacity: 10_000,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Creates lookups through the resolver configured for the system, such as `/etc/resolv.conf`
+    pub fn from_system_conf() -> Result<Self, ValidationError> {
+        let resolver = TokioResolver::builder_tokio()
+            .map_err(ValidationError::external)?
+            .build();
+        Ok(Self::new(resolver))
+    }
+
+    /// Sets the time allowed for each lookup
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets how long answers are cached; zero disables caching
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets the number of cached answers kept before expired ones are dropped
+    pub fn with_cache_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Returns true if the domain has an IPv4 or IPv6 address
+    pub async fn resolves(&self, domain: &str) -> Result<bool, ValidationError> {
+        let found = self
+            .cached(Query::Address, domain, async {
+                records(
+                    self.resolver
+                        .lookup_ip(domain)
+                        .await
+                        .map(|ips| ips.iter().count()),
+                )
+            })
+            .await?;
+        Ok(found == Found::Records)
+    }
+
+    /// Returns true if the domain accepts mail according to its MX records
+    ///
+    /// A null MX record counts as no MX records. With `implicit_mx`, a
+    /// domain without MX records that has an address accepts mail, as
+    /// RFC 5321 specifies.
+    pub async fn accepts_mail(
+        &self,
+        domain: &str,
+        implicit_mx: bool,
+    ) -> Result<bool, ValidationError> {
+        let found = self
+            .cached(Query::Mail, domain, async {
+                let mx = match self.resolver.mx_lookup(domain).await {
+                    Ok(mx) => mx,
+                    Err(err) => return records(Err(err)),
+                };
+                let mut exchanges = mx.iter().map(|mx| mx.exchange());
+                match (exchanges.next(), exchanges.next()) {
+                    (Some(exchange), None) if exchange.is_root() => Ok(Found::NullMx),
+                    (Some(_), _) => Ok(Found::Records),
+                    (None, _) => Ok(Found::Nothing),
+                }
+            })
+            .await?;
+        match found {
+            Found::Records => Ok(true),
+            Found::NullMx => Ok(false),
+            Found::Nothing if implicit_mx => self.resolves(domain).await,
+            Found::Nothing => Ok(false),
+        }
+    }
+
+    /// Returns the cached answer for the query, looking it up if missing or expired
+    async fn cached<F>(
+        &self,
+        query: Query,
+        domain: &str,
+        lookup: F,
+    ) -> Result<Found, ValidationError>
+    where
+        F: Future<Output = Result<Found, ValidationError>>,
+    {
+        let key = (query, domain.to_string());
+        let now = Instant::now();
+        if let Some((found, expires)) = self.lock().get(&key) {
+            if *expires > now {
+                return Ok(*found);
+            }
+        }
+
+        let found = tokio::time::timeout(self.timeout, lookup)
+            .await
+            .map_err(|_| ValidationError::timeout(self.timeout))??;
+
+        if !self.ttl.is_zero() {
+            let mut cache = self.lock();
+            if cache.len() >= self.capacity {
+                cache.retain(|_, (_, expires)| *expires > now);
+            }
+            if cache.len() < self.capacity {
+                cache.insert(key, (found, now + self.ttl));
+            }
+        }
+        Ok(found)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Cache> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Maps a lookup result to whether records were found, keeping resolver failures as errors
+fn records(result: Result<usize, ResolveError>) -> Result<Found, ValidationError> {
+    match result {
+        Ok(0) => Ok(Found::Nothing),
+        Ok(_) => Ok(Found::Records),
+        Err(err) if err.is_no_records_found() => Ok(Found::Nothing),
+        Err(err) => Err(ValidationError::transient(format!(
+            "DNS lookup failed: {}",
+            err
+        ))),
+    }
+}
+
+/// Validator accepting host names that resolve to an address
+///
+/// The name is first checked with [`HostnameValidator`], then looked up
+/// through a [`DnsLookup`]. Names that do not exist or have no address are
+/// rejected; lookups that fail or time out end in
+/// [`ValidationError::Transient`] or [`ValidationError::Timeout`], so
+/// callers can tell an unreachable resolver from a bad name. Only
+/// asynchronous validation looks the name up; synchronous validation fails.
+///
+/// ```rust,no_run
+/// use huginn::dns::{DnsHostnameValidator, DnsLookup};
+/// use huginn::{sanitize_and_validate_async, SecurityConfig, ValidationError};
+///
+/// # async fn check() -> Result<(), ValidationError> {
+/// let lookup = DnsLookup::from_system_conf()?;
+/// let validator = DnsHostnameValidator::new(lookup);
+///
+/// let config = SecurityConfig::default();
+/// let host = sanitize_and_validate_async("Example.com", &validator, &config).await?;
+/// assert_eq!(host.cleaned, "example.com");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DnsHostnameValidator {
+    syntax: HostnameValidator,
+    lookup: DnsLookup,
+}
+
+impl DnsHostnameValidator {
+    /// Creates validator checking names with the default [`HostnameValidator`]
+    pub fn new(lookup: DnsLookup) -> Self {
+        Self {
+            syntax: HostnameValidator::new(),
+            lookup,
+        }
+    }
+
+    /// Replaces the syntactic checks run before the lookup
+    pub fn with_syntax(mut self, syntax: HostnameValidator) -> Self {
+        self.syntax = syntax;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl Validator<String> for DnsHostnameValidator {
+    fn validate(&self, _input: &str) -> Result<String, ValidationError> {
+        Err(ValidationError::custom(
+            "DNS lookups are only available asynchronously",
+        ))
+    }
+
+    async fn validate_async(&self, input: &str) -> Result<String, ValidationError> {
+        let name = self.syntax.validate(input)?;
+        if self.lookup.resolves(&name).await? {
+            Ok(name)
+        } else {
+            Err(ValidationError::invalid_format(self.target_type())
+                .with_hint("use a host name that exists"))
+        }
+    }
+
+    fn target_type(&self) -> &'static str {
+        "host name"
+    }
+}
+
+/// Validator accepting email addresses whose domain accepts mail
+///
+/// The address is first checked with [`EmailValidator`], then the MX
+/// records of its domain are looked up through a [`DnsLookup`]. Domains
+/// without MX records are accepted if they have an address, as RFC 5321
+/// allows, unless [`require_mx`](Self::require_mx) is set; domains with a
+/// null MX record are always rejected. Lookups that fail or time out end
+/// in [`ValidationError::Transient`] or [`ValidationError::Timeout`]. Only
+/// asynchronous validation looks the domain up; synchronous validation
+/// fails.
+///
+/// ```rust,no_run
+/// use huginn::dns::{DnsEmailValidator, DnsLookup};
+/// use huginn::{sanitize_and_validate_async, SecurityConfig, ValidationError};
+/// use std::time::Duration;
+///
+/// # async fn check() -> Result<(), ValidationError> {
+/// let lookup = DnsLookup::from_system_conf()?.with_timeout(Duration::from_millis(500));
+/// let validator = DnsEmailValidator::new(lookup).require_mx();
+///
+/// let config = SecurityConfig::default();
+/// let result = sanitize_and_validate_async("jane@example.invalid", &validator, &config).await;
+/// assert!(result.is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DnsEmailValidator {
+    syntax: EmailValidator,
+    lookup: DnsLookup,
+    require_mx: bool,
+}
+
+impl DnsEmailValidator {
+    /// Creates validator checking addresses with the default [`EmailValidator`]
+    pub fn new(lookup: DnsLookup) -> Self {
+        Self {
+            syntax: EmailValidator::new(),
+            lookup,
+            require_mx: false,
+        }
+    }
+
+    /// Replaces the syntactic checks run before the lookup
+    pub fn with_syntax(mut self, syntax: EmailValidator) -> Self {
+        self.syntax = syntax;
+        self
+    }
+
+    /// Rejects domains without MX records even if they have an address
+    pub fn require_mx(mut self) -> Self {
+        self.require_mx = true;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl Validator<String> for DnsEmailValidator {
+    fn validate(&self, _input: &str) -> Result<String, ValidationError> {
+        Err(ValidationError::custom(
+            "DNS lookups are only available asynchronously",
+        ))
+    }
+
+    async fn validate_async(&self, input: &str) -> Result<String, ValidationError> {
+        let (local, domain) = self.syntax.parse(input)?;
+        if self.lookup.accepts_mail(&domain, !self.require_mx).await? {
+            Ok(format!("{}@{}", local, domain))
+        } else {
+            Err(ValidationError::invalid_format(self.target_type())
+                .with_hint("use an address whose domain accepts mail"))
+        }
+    }
+
+    fn target_type(&self) -> &'static str {
+        "email address"
+    }
+}
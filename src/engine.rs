@@ -0,0 +1,90 @@
+use super::{error::PatternError, pattern::RegexLimits};
+
+#[cfg(feature = "regex")]
+pub use regex::{escape, Error as RegexError, Regex, RegexSet};
+
+#[cfg(all(feature = "regex-lite", not(feature = "regex")))]
+pub use regex_lite::{escape, Error as RegexError, Regex};
+
+/// Stand-in for the multi-pattern set, which `regex-lite` does not provide
+///
+/// It is never constructed, so configurations always match their rules one
+/// by one.
+#[cfg(all(feature = "regex-lite", not(feature = "regex")))]
+#[derive(Debug)]
+pub enum RegexSet {}
+
+#[cfg(all(feature = "regex-lite", not(feature = "regex")))]
+impl RegexSet {
+    pub fn is_match(&self, _input: &str) -> bool {
+        match *self {}
+    }
+
+    pub fn matches(&self, _input: &str) -> Vec<usize> {
+        match *self {}
+    }
+}
+
+/// Compiles patterns into one set for single-pass matching
+///
+/// `None` when the set exceeds the size limits, in which case patterns are
+/// matched one by one.
+#[cfg(feature = "regex")]
+pub fn regex_set<'a>(patterns: impl IntoIterator<Item = &'a str>) -> Option<RegexSet> {
+    RegexSet::new(patterns).ok()
+}
+
+/// Always `None`, since `regex-lite` has no multi-pattern set
+#[cfg(all(feature = "regex-lite", not(feature = "regex")))]
+pub fn regex_set<'a>(_patterns: impl IntoIterator<Item = &'a str>) -> Option<RegexSet> {
+    None
+}
+
+/// Checks the syntax and nesting depth of a pattern
+#[cfg(feature = "regex")]
+pub fn check_syntax(pattern: &str, limits: &RegexLimits) -> Result<(), PatternError> {
+    regex_syntax::ParserBuilder::new()
+        .nest_limit(limits.nest_limit)
+        .build()
+        .parse(pattern)
+        .map(drop)
+        .map_err(|err| PatternError::Invalid(RegexError::Syntax(err.to_string())))
+}
+
+/// Checks the syntax and nesting depth of a pattern
+///
+/// `regex-lite` exposes no parser, so the pattern is compiled and dropped.
+#[cfg(all(feature = "regex-lite", not(feature = "regex")))]
+pub fn check_syntax(pattern: &str, limits: &RegexLimits) -> Result<(), PatternError> {
+    build(pattern, limits).map(drop)
+}
+
+/// Compiles a pattern within the size and nesting limits
+#[cfg(feature = "regex")]
+pub fn build(pattern: &str, limits: &RegexLimits) -> Result<Regex, PatternError> {
+    regex::RegexBuilder::new(pattern)
+        .size_limit(limits.size_limit)
+        .dfa_size_limit(limits.dfa_size_limit)
+        .nest_limit(limits.nest_limit)
+        .build()
+        .map_err(|err| match err {
+            RegexError::CompiledTooBig(limit) => PatternError::TooComplex {
+                pattern: pattern.to_string(),
+                reason: format!("compiled pattern exceeds size limit of {} bytes", limit),
+            },
+            other => PatternError::Invalid(other),
+        })
+}
+
+/// Compiles a pattern within the size and nesting limits
+///
+/// `regex-lite` reports an exceeded size limit as an ordinary error, so it
+/// surfaces as [`PatternError::Invalid`].
+#[cfg(all(feature = "regex-lite", not(feature = "regex")))]
+pub fn build(pattern: &str, limits: &RegexLimits) -> Result<Regex, PatternError> {
+    regex_lite::RegexBuilder::new(pattern)
+        .size_limit(limits.size_limit)
+        .nest_limit(limits.nest_limit)
+        .build()
+        .map_err(PatternError::Invalid)
+}
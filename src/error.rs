@@ -1,7 +1,55 @@
+use super::rules::ThreatClass;
+use alloc::{
+    string::String,
+    sync::Arc,
+    vec::{self, Vec},
+};
+use core::{error::Error as StdError, fmt, ops::Range, time::Duration};
 use thiserror::Error;
 
+/// Location of a forbidden character within the screened input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CharPosition {
+    /// Forbidden character
+    pub ch: char,
+    /// Byte offset of the character
+    pub byte_offset: usize,
+    /// Character (Unicode scalar) offset of the character
+    pub char_offset: usize,
+}
+
+/// Blocked pattern that matched the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PatternMatch {
+    /// Name of the matching rule
+    pub name: String,
+    /// Position of the rule in the configuration
+    pub index: usize,
+    /// Byte range of the first match within the screened input
+    pub span: Range<usize>,
+    /// Attack type of the rule
+    pub threat: ThreatClass,
+}
+
 /// Comprehensive validation error types
+///
+/// With the `serde` feature enabled, errors serialize as
+/// `{ "code": ..., "message": ..., "details": { ... } }`.
+///
+/// ```rust
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use huginn::ValidationError;
+///
+/// let json = serde_json::to_value(ValidationError::custom("nope")).unwrap();
+/// assert_eq!(json["code"], "HUG-100");
+/// assert_eq!(json["details"]["message"], "nope");
+/// # }
+/// ```
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ValidationError {
     /// Input contains forbidden characters
     #[error("Input contains {count} dangerous characters: {symbols}")]
@@ -10,6 +58,10 @@ pub enum ValidationError {
         symbols: String,
         /// Total count of dangerous characters
         count: usize,
+        /// Offsets of each dangerous character within the decoded input
+        positions: Vec<CharPosition>,
+        /// Optional suggestion for fixing the input
+        hint: Option<String>,
     },
 
     /// Input format mismatch
@@ -17,13 +69,17 @@ pub enum ValidationError {
     InvalidFormat {
         /// Expected data type name
         target_type: &'static str,
+        /// Optional suggestion for fixing the input
+        hint: Option<String>,
     },
 
     /// Blocked pattern detected
-    #[error("Input matches blocked pattern: {pattern}")]
+    #[error("Input matches blocked pattern: {}", pattern_names(matches))]
     BlockedPattern {
-        /// Pattern that triggered the block
-        pattern: String,
+        /// Every pattern that matched the input
+        matches: Vec<PatternMatch>,
+        /// Optional suggestion for fixing the input
+        hint: Option<String>,
     },
 
     /// Custom validation failure
@@ -31,7 +87,38 @@ pub enum ValidationError {
     Custom {
         /// Custom error message
         message: String,
+        /// Optional suggestion for fixing the input
+        hint: Option<String>,
+    },
+
+    /// Validation could not complete because a dependency was temporarily unavailable
+    #[error("Validation temporarily unavailable: {message}")]
+    Transient {
+        /// Description of the transient failure
+        message: String,
     },
+
+    /// Asynchronous validation did not finish within the allowed time
+    #[error("Validation timed out after {after:?}")]
+    Timeout {
+        /// Time allowed for validation
+        after: Duration,
+    },
+
+    /// Too many inputs were submitted under the same rate limiting key
+    #[error("Rate limit exceeded, retry after {retry_after:?}")]
+    RateLimited {
+        /// Time until the next input is accepted
+        retry_after: Duration,
+    },
+
+    /// Asynchronous validation was cancelled before it finished
+    #[error("Validation was cancelled")]
+    Cancelled,
+
+    /// Failure of a third-party dependency (HTTP, database, ...) used by a validator
+    #[error("External validation failed: {0}")]
+    External(#[source] ExternalError),
 }
 
 impl ValidationError {
@@ -39,6 +126,373 @@ impl ValidationError {
     pub fn custom<S: Into<String>>(message: S) -> Self {
         Self::Custom {
             message: message.into(),
+            hint: None,
+        }
+    }
+
+    /// Creates format validation error for the given type
+    pub fn invalid_format(target_type: &'static str) -> Self {
+        Self::InvalidFormat {
+            target_type,
+            hint: None,
+        }
+    }
+
+    /// Attaches a suggestion for fixing the input
+    ///
+    /// Variants without a hint slot (`Transient`, `Timeout`, `RateLimited`, `Cancelled`,
+    /// `External`) are returned unchanged.
+    pub fn with_hint<S: Into<String>>(mut self, new_hint: S) -> Self {
+        match &mut self {
+            Self::DangerousCharacters { hint, .. }
+            | Self::InvalidFormat { hint, .. }
+            | Self::BlockedPattern { hint, .. }
+            | Self::Custom { hint, .. } => *hint = Some(new_hint.into()),
+            Self::Transient { .. }
+            | Self::Timeout { .. }
+            | Self::RateLimited { .. }
+            | Self::Cancelled
+            | Self::External(_) => {}
+        }
+        self
+    }
+
+    /// Returns the suggestion for fixing the input, if any
+    pub fn hint(&self) -> Option<&str> {
+        match self {
+            Self::DangerousCharacters { hint, .. }
+            | Self::InvalidFormat { hint, .. }
+            | Self::BlockedPattern { hint, .. }
+            | Self::Custom { hint, .. } => hint.as_deref(),
+            Self::Transient { .. }
+            | Self::Timeout { .. }
+            | Self::RateLimited { .. }
+            | Self::Cancelled
+            | Self::External(_) => None,
+        }
+    }
+
+    /// Creates transient (retryable) validation error
+    pub fn transient<S: Into<String>>(message: S) -> Self {
+        Self::Transient {
+            message: message.into(),
+        }
+    }
+
+    /// Creates error for validation that did not finish within the allowed time
+    pub fn timeout(after: Duration) -> Self {
+        Self::Timeout { after }
+    }
+
+    /// Creates error for input submitted too often under one rate limiting key
+    pub fn rate_limited(retry_after: Duration) -> Self {
+        Self::RateLimited { retry_after }
+    }
+
+    /// Returns true if the failure is transient and the same input may succeed on retry
+    ///
+    /// This holds for `Transient`, `Timeout`, `RateLimited` and `Cancelled`;
+    /// every other variant means the input itself was rejected.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::Transient { .. }
+                | Self::Timeout { .. }
+                | Self::RateLimited { .. }
+                | Self::Cancelled
+        )
+    }
+
+    /// Wraps a third-party error raised while validating
+    pub fn external<E>(error: E) -> Self
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        Self::External(ExternalError(Arc::new(error)))
+    }
+
+    /// Returns the ids of the rules that rejected the input
+    ///
+    /// These are the names of matched blocked patterns, or
+    /// `dangerous_characters` for forbidden characters. Validator failures
+    /// have no rule id; use the error code instead.
+    pub fn rule_ids(&self) -> Vec<&str> {
+        match self {
+            Self::BlockedPattern { matches, .. } => {
+                matches.iter().map(|m| m.name.as_str()).collect()
+            }
+            Self::DangerousCharacters { .. } => alloc::vec!["dangerous_characters"],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns the distinct attack types of blocked pattern findings, in match order
+    ///
+    /// Errors other than `BlockedPattern` carry no classification.
+    pub fn threats(&self) -> Vec<ThreatClass> {
+        let mut threats = Vec::new();
+        if let Self::BlockedPattern { matches, .. } = self {
+            for m in matches {
+                if !threats.contains(&m.threat) {
+                    threats.push(m.threat);
+                }
+            }
+        }
+        threats
+    }
+
+    /// Returns a stable machine-readable error code
+    ///
+    /// | Code      | Variant               |
+    /// |-----------|-----------------------|
+    /// | `HUG-001` | `DangerousCharacters` |
+    /// | `HUG-010` | `BlockedPattern`      |
+    /// | `HUG-020` | `InvalidFormat`       |
+    /// | `HUG-100` | `Custom`              |
+    /// | `HUG-101` | `External`            |
+    /// | `HUG-102` | `Transient`           |
+    /// | `HUG-103` | `Timeout`             |
+    /// | `HUG-104` | `RateLimited`         |
+    /// | `HUG-105` | `Cancelled`           |
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::DangerousCharacters { .. } => "HUG-001",
+            Self::BlockedPattern { .. } => "HUG-010",
+            Self::InvalidFormat { .. } => "HUG-020",
+            Self::Custom { .. } => "HUG-100",
+            Self::External(_) => "HUG-101",
+            Self::Transient { .. } => "HUG-102",
+            Self::Timeout { .. } => "HUG-103",
+            Self::RateLimited { .. } => "HUG-104",
+            Self::Cancelled => "HUG-105",
+        }
+    }
+}
+
+/// Errors raised while adding patterns to a configuration
+#[cfg(feature = "std")]
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum PatternError {
+    /// Pattern is not a valid regular expression
+    #[error("Invalid pattern: {0}")]
+    Invalid(#[from] crate::pattern::RegexError),
+
+    /// Pattern was rejected by the complexity guard
+    #[error("Pattern {pattern:?} rejected as too complex: {reason}")]
+    TooComplex {
+        /// Rejected pattern source
+        pattern: String,
+        /// Description of the limit that was exceeded
+        reason: String,
+    },
+}
+
+/// Shared handle to a third-party error wrapped by [`ValidationError::External`]
+///
+/// Displays as the wrapped error; two handles compare equal only when they
+/// point to the same underlying error.
+#[derive(Debug, Clone)]
+pub struct ExternalError(Arc<dyn StdError + Send + Sync>);
+
+impl ExternalError {
+    /// Returns a reference to the wrapped error
+    pub fn get_ref(&self) -> &(dyn StdError + Send + Sync + 'static) {
+        &*self.0
+    }
+
+    /// Attempts to downcast the wrapped error to a concrete type
+    pub fn downcast_ref<E: StdError + 'static>(&self) -> Option<&E> {
+        self.0.downcast_ref()
+    }
+}
+
+impl fmt::Display for ExternalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl StdError for ExternalError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.0.source()
+    }
+}
+
+impl PartialEq for ExternalError {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for ExternalError {}
+
+/// Collection of validation failures, optionally keyed by field path
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors {
+    errors: Vec<(Option<String>, ValidationError)>,
+}
+
+impl ValidationErrors {
+    /// Creates new empty collection
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an error not tied to any field
+    pub fn push(&mut self, error: ValidationError) {
+        self.errors.push((None, error));
+    }
+
+    /// Adds an error for the given field path
+    pub fn push_field<S: Into<String>>(&mut self, field: S, error: ValidationError) {
+        self.errors.push((Some(field.into()), error));
+    }
+
+    /// Returns true if no errors were recorded
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the number of recorded errors
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Iterates over errors with their field paths
+    pub fn iter(&self) -> impl Iterator<Item = (Option<&str>, &ValidationError)> {
+        self.errors
+            .iter()
+            .map(|(field, error)| (field.as_deref(), error))
+    }
+
+    /// Iterates over errors recorded for the given field path
+    pub fn for_field<'a>(&'a self, field: &'a str) -> impl Iterator<Item = &'a ValidationError> {
+        self.errors
+            .iter()
+            .filter(move |(f, _)| f.as_deref() == Some(field))
+            .map(|(_, error)| error)
+    }
+
+    /// Consumes the collection, returning errors with their field paths
+    pub fn into_vec(self) -> Vec<(Option<String>, ValidationError)> {
+        self.errors
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} validation error(s)", self.errors.len())?;
+        for (i, (field, error)) in self.errors.iter().enumerate() {
+            f.write_str(if i == 0 { ": " } else { "; " })?;
+            match field {
+                Some(field) => write!(f, "{}: {}", field, error)?,
+                None => write!(f, "{}", error)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl StdError for ValidationErrors {}
+
+impl From<ValidationError> for ValidationErrors {
+    fn from(error: ValidationError) -> Self {
+        let mut errors = Self::new();
+        errors.push(error);
+        errors
+    }
+}
+
+impl FromIterator<ValidationError> for ValidationErrors {
+    fn from_iter<I: IntoIterator<Item = ValidationError>>(iter: I) -> Self {
+        Self {
+            errors: iter.into_iter().map(|error| (None, error)).collect(),
+        }
+    }
+}
+
+impl Extend<ValidationError> for ValidationErrors {
+    fn extend<I: IntoIterator<Item = ValidationError>>(&mut self, iter: I) {
+        self.errors
+            .extend(iter.into_iter().map(|error| (None, error)));
+    }
+}
+
+impl IntoIterator for ValidationErrors {
+    type Item = (Option<String>, ValidationError);
+    type IntoIter = vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+fn pattern_names(matches: &[PatternMatch]) -> String {
+    matches
+        .iter()
+        .map(|m| m.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::ValidationError;
+    use serde::ser::{Serialize, SerializeMap, SerializeStruct, Serializer};
+
+    impl Serialize for ValidationError {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("ValidationError", 3)?;
+            state.serialize_field("code", self.code())?;
+            state.serialize_field("message", &self.to_string())?;
+            state.serialize_field("details", &Details(self))?;
+            state.end()
+        }
+    }
+
+    /// Variant-specific payload of a serialized error
+    struct Details<'a>(&'a ValidationError);
+
+    impl Serialize for Details<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut map = serializer.serialize_map(None)?;
+            match self.0 {
+                ValidationError::DangerousCharacters {
+                    symbols,
+                    count,
+                    positions,
+                    ..
+                } => {
+                    map.serialize_entry("symbols", symbols)?;
+                    map.serialize_entry("count", count)?;
+                    map.serialize_entry("positions", positions)?;
+                }
+                ValidationError::InvalidFormat { target_type, .. } => {
+                    map.serialize_entry("target_type", target_type)?;
+                }
+                ValidationError::BlockedPattern { matches, .. } => {
+                    map.serialize_entry("matches", matches)?;
+                }
+                ValidationError::Custom { message, .. }
+                | ValidationError::Transient { message } => {
+                    map.serialize_entry("message", message)?;
+                }
+                ValidationError::Timeout { after } => {
+                    map.serialize_entry("timeout_ms", &after.as_millis())?;
+                }
+                ValidationError::RateLimited { retry_after } => {
+                    map.serialize_entry("retry_after_ms", &retry_after.as_millis())?;
+                }
+                ValidationError::Cancelled => {}
+                ValidationError::External(source) => {
+                    map.serialize_entry("source", &source.to_string())?;
+                }
+            }
+            if let Some(hint) = self.0.hint() {
+                map.serialize_entry("hint", hint)?;
+            }
+            map.end()
         }
     }
 }
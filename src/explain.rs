@@ -0,0 +1,97 @@
+use super::{
+    config::SecurityConfig,
+    error::{CharPosition, PatternMatch, ValidationError, ValidationErrors},
+    rules::ThreatClass,
+    validation::{blocked_pattern, dangerous_characters, decode_input, sanitize_cow, Validator},
+};
+use smallvec::SmallVec;
+use std::fmt::Debug;
+
+/// Every finding of a dry run of the pipeline over one input
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ValidationReport {
+    /// Input after URL decoding
+    pub decoded: String,
+    /// Input after forbidden characters were stripped, as seen by later stages
+    pub cleaned: String,
+    /// Forbidden characters found in the decoded input
+    pub removed: Vec<CharPosition>,
+    /// Blocked patterns matching the cleaned input
+    pub matches: Vec<PatternMatch>,
+    /// Error returned by the validator for the cleaned input, if any
+    pub validator_error: Option<ValidationError>,
+}
+
+impl ValidationReport {
+    /// Returns whether the pipeline would have rejected the input
+    pub fn would_reject(&self) -> bool {
+        !self.removed.is_empty() || !self.matches.is_empty() || self.validator_error.is_some()
+    }
+
+    /// Returns the distinct attack types of the matched patterns, in match order
+    pub fn threats(&self) -> Vec<ThreatClass> {
+        if self.matches.is_empty() {
+            return Vec::new();
+        }
+        blocked_pattern(self.matches.clone()).threats()
+    }
+
+    /// Returns the findings as errors, in pipeline stage order
+    ///
+    /// The first error is the one the pipeline would have returned.
+    pub fn errors(&self) -> ValidationErrors {
+        let mut errors = ValidationErrors::new();
+        if !self.removed.is_empty() {
+            errors.push(dangerous_characters(SmallVec::from_slice(&self.removed)));
+        }
+        if !self.matches.is_empty() {
+            errors.push(blocked_pattern(self.matches.clone()));
+        }
+        if let Some(error) = &self.validator_error {
+            errors.push(error.clone());
+        }
+        errors
+    }
+}
+
+/// Runs the full pipeline over input without rejecting it, reporting every finding
+///
+/// Every stage runs regardless of earlier findings: forbidden characters are
+/// stripped and recorded, blocked patterns are matched against the stripped
+/// input, and the validator runs on it. Use it to try new rules against real
+/// traffic before enforcing them.
+///
+/// ```rust
+/// use huginn::validators::FilenameValidator;
+/// use huginn::{explain, SecurityConfig, ThreatClass};
+///
+/// let report = explain("<b>..%2F..%2Fetc/passwd", &FilenameValidator::new(), &SecurityConfig::default());
+/// assert!(report.would_reject());
+/// assert_eq!(report.removed.len(), 2);
+/// assert_eq!(report.matches[0].name, "path_traversal");
+/// assert_eq!(report.threats(), [ThreatClass::PathTraversal]);
+/// assert!(report.validator_error.is_some());
+/// assert_eq!(report.errors().len(), 3);
+/// ```
+pub fn explain<T>(
+    input: &str,
+    validator: &impl Validator<T>,
+    config: &SecurityConfig,
+) -> ValidationReport
+where
+    T: Debug + Send + Sync,
+{
+    let decoded = decode_input(input);
+    let (cleaned, removed) = sanitize_cow(decoded.clone(), config);
+    let matches = config.find_blocked_patterns(&cleaned);
+    let validator_error = validator.validate(&cleaned).err();
+
+    ValidationReport {
+        decoded: decoded.into_owned(),
+        cleaned: cleaned.into_owned(),
+        removed: removed.into_vec(),
+        matches,
+        validator_error,
+    }
+}
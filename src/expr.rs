@@ -0,0 +1,532 @@
+use super::{
+    engine,
+    error::PatternError,
+    pattern::{compile_pattern, Regex, RegexLimits},
+    risk::shannon_entropy,
+};
+use std::{collections::VecDeque, fmt, ops::Range, str::FromStr};
+use thiserror::Error;
+
+/// Longest expression source accepted, in bytes
+const MAX_SOURCE_LEN: usize = 4096;
+
+/// Deepest nesting of parentheses and `not`
+const MAX_DEPTH: usize = 32;
+
+/// Errors raised while parsing a rule expression
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ExprError {
+    /// The expression is not well formed
+    #[error("Invalid rule expression at offset {offset}: {message}")]
+    Syntax {
+        /// Byte offset of the problem in the source
+        offset: usize,
+        /// Description of the problem
+        message: String,
+    },
+
+    /// A `contains` or `matches` pattern was rejected
+    #[error("Invalid pattern in rule expression: {0}")]
+    Pattern(#[from] PatternError),
+}
+
+/// Condition on input text, written in a small expression language
+///
+/// Expressions let rules go beyond a single regular expression while
+/// staying plain text, so they can live in configuration files and
+/// rulesets. The conditions are:
+///
+/// - `contains("text")`: the input contains the text, ignoring case
+/// - `matches("regex")`: the regular expression matches the input
+/// - `length > 1000`: the input has more than 1000 characters
+/// - `entropy > 4.5`: the Shannon entropy exceeds 4.5 bits per character
+///
+/// `length` and `entropy` compare with `>`, `>=`, `<`, `<=`, `==` or `!=`.
+/// Conditions combine with `not`, `and` and `or` (binding in that order)
+/// and parentheses. Text conditions joined with `and` may end in
+/// `within N chars`, requiring all of them to occur within a stretch of N
+/// characters. Keywords are case-insensitive, and strings escape `"` and
+/// `\` with a backslash.
+///
+/// ```rust
+/// use huginn::expr::RuleExpr;
+///
+/// let union_select = RuleExpr::parse(r#"contains("union") and contains("select") within 40 chars"#)?;
+/// assert!(union_select.is_match("1 UNION ALL SELECT password FROM users"));
+/// assert!(!union_select.is_match("Select your seat early; the train leaves from Union Station at noon"));
+///
+/// let blob = RuleExpr::parse("length > 1000 or (length > 32 and entropy > 4.5)")?;
+/// assert!(blob.is_match("Jx8#kP2$vQ9!mZ4@wL7&nR3*tY6^bH1%cF5(dG0)eS8+aK2=uW9~iO4|pE7?"));
+/// assert!(!blob.is_match("a perfectly ordinary sentence that happens to be long-ish"));
+/// # Ok::<(), huginn::expr::ExprError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RuleExpr {
+    source: String,
+    root: Node,
+}
+
+impl RuleExpr {
+    /// Parses an expression, holding its patterns to the default [`RegexLimits`]
+    pub fn parse(source: &str) -> Result<Self, ExprError> {
+        Self::parse_with_limits(source, &RegexLimits::default())
+    }
+
+    /// Parses an expression, holding its patterns to the given limits
+    pub fn parse_with_limits(source: &str, limits: &RegexLimits) -> Result<Self, ExprError> {
+        if source.len() > MAX_SOURCE_LEN {
+            return Err(syntax(0, "expression is too long"));
+        }
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            position: 0,
+            depth: 0,
+            end: source.len(),
+            limits,
+        };
+        let root = parser.disjunction()?;
+        if let Some((_, offset)) = parser.tokens.get(parser.position) {
+            return Err(syntax(*offset, "expected `and`, `or` or the end"));
+        }
+        Ok(Self {
+            source: source.to_string(),
+            root,
+        })
+    }
+
+    /// Returns the source text
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns true if the input meets the condition
+    pub fn is_match(&self, input: &str) -> bool {
+        self.find(input).is_some()
+    }
+
+    /// Returns the byte range the condition was met on, if it was
+    ///
+    /// Text conditions report where the text occurs; `length`, `entropy`
+    /// and `not` conditions report the whole input.
+    pub fn find(&self, input: &str) -> Option<Range<usize>> {
+        self.root.find(input)
+    }
+}
+
+impl fmt::Display for RuleExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl FromStr for RuleExpr {
+    type Err = ExprError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Self::parse(source)
+    }
+}
+
+/// Named rule expression used to block input
+#[derive(Debug, Clone)]
+pub struct ExpressionRule {
+    /// Rule name reported when the expression matches
+    pub name: String,
+    /// Parsed expression
+    pub expr: RuleExpr,
+}
+
+impl ExpressionRule {
+    /// Creates a named rule from a parsed expression
+    pub fn new<S: Into<String>>(name: S, expr: RuleExpr) -> Self {
+        Self {
+            name: name.into(),
+            expr,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Any(Vec<Node>),
+    All(Vec<Node>),
+    Not(Box<Node>),
+    Text(Regex),
+    Near { terms: Vec<Regex>, chars: usize },
+    Compare { metric: Metric, op: Op, value: f64 },
+}
+
+impl Node {
+    fn find(&self, input: &str) -> Option<Range<usize>> {
+        match self {
+            Self::Any(nodes) => nodes.iter().find_map(|node| node.find(input)),
+            Self::All(nodes) => {
+                nodes
+                    .iter()
+                    .try_fold(None, |span: Option<Range<usize>>, node| {
+                        let found = node.find(input)?;
+                        Some(Some(match span {
+                            Some(Range { start, end }) => {
+                                start.min(found.start)..end.max(found.end)
+                            }
+                            None => found,
+                        }))
+                    })?
+            }
+            Self::Not(node) => match node.find(input) {
+                Some(_) => None,
+                None => Some(0..input.len()),
+            },
+            Self::Text(regex) => regex.find(input).map(|m| m.range()),
+            Self::Near { terms, chars } => near(input, terms, *chars),
+            Self::Compare { metric, op, value } => op
+                .holds(metric.measure(input), *value)
+                .then_some(0..input.len()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Metric {
+    Length,
+    Entropy,
+}
+
+impl Metric {
+    fn measure(self, input: &str) -> f64 {
+        match self {
+            Self::Length => input.chars().count() as f64,
+            Self::Entropy => shannon_entropy(input),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl Op {
+    fn holds(self, left: f64, right: f64) -> bool {
+        match self {
+            Self::Gt => left > right,
+            Self::Ge => left >= right,
+            Self::Lt => left < right,
+            Self::Le => left <= right,
+            Self::Eq => left == right,
+            Self::Ne => left != right,
+        }
+    }
+}
+
+/// Finds the first stretch of at most `chars` characters containing a match of every term
+///
+/// Occurrences are visited by start position with a sliding window over
+/// them, keeping the window's furthest end in a monotonic queue, so the
+/// search is linear in the number of occurrences.
+fn near(input: &str, terms: &[Regex], chars: usize) -> Option<Range<usize>> {
+    let mut found: Vec<(Range<usize>, usize)> = terms
+        .iter()
+        .enumerate()
+        .flat_map(|(term, regex)| regex.find_iter(input).map(move |m| (m.range(), term)))
+        .collect();
+    found.sort_by_key(|(span, _)| span.start);
+
+    let boundaries: Option<Vec<usize>> =
+        (!input.is_ascii()).then(|| input.char_indices().map(|(i, _)| i).collect());
+    let char_at = |byte: usize| match &boundaries {
+        Some(boundaries) => boundaries.partition_point(|&i| i < byte),
+        None => byte,
+    };
+
+    let mut counts = vec![0usize; terms.len()];
+    let mut covered = 0;
+    let mut furthest: VecDeque<usize> = VecDeque::new();
+    let mut left = 0;
+    for right in 0..found.len() {
+        let (span, term) = &found[right];
+        if counts[*term] == 0 {
+            covered += 1;
+        }
+        counts[*term] += 1;
+        while furthest.back().is_some_and(|&i| found[i].0.end <= span.end) {
+            furthest.pop_back();
+        }
+        furthest.push_back(right);
+
+        while covered == terms.len() {
+            let start = found[left].0.start;
+            let end = found[furthest[0]].0.end;
+            if char_at(end) - char_at(start) <= chars {
+                return Some(start..end);
+            }
+            let term = found[left].1;
+            counts[term] -= 1;
+            if counts[term] == 0 {
+                covered -= 1;
+            }
+            if furthest.front() == Some(&left) {
+                furthest.pop_front();
+            }
+            left += 1;
+        }
+    }
+    None
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Text(String),
+    Number(f64),
+    Open,
+    Close,
+    Compare(Op),
+}
+
+fn syntax(offset: usize, message: &str) -> ExprError {
+    ExprError::Syntax {
+        offset,
+        message: message.to_string(),
+    }
+}
+
+/// Splits the source into tokens with their byte offsets
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ExprError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(offset, c)) = chars.peek() {
+        let token = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '(' | ')' => {
+                chars.next();
+                if c == '(' {
+                    Token::Open
+                } else {
+                    Token::Close
+                }
+            }
+            '"' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, escaped @ ('"' | '\\'))) => text.push(escaped),
+                            Some((at, _)) => {
+                                return Err(syntax(at, "only \\\" and \\\\ are escapes"))
+                            }
+                            None => return Err(syntax(source.len(), "unterminated string")),
+                        },
+                        Some((_, c)) => text.push(c),
+                        None => return Err(syntax(source.len(), "unterminated string")),
+                    }
+                }
+                Token::Text(text)
+            }
+            '0'..='9' => {
+                let mut end = offset;
+                while let Some(&(at, c)) = chars.peek() {
+                    if !(c.is_ascii_digit() || c == '.') {
+                        break;
+                    }
+                    end = at + 1;
+                    chars.next();
+                }
+                let number = source[offset..end]
+                    .parse()
+                    .map_err(|_| syntax(offset, "invalid number"))?;
+                Token::Number(number)
+            }
+            '>' | '<' | '=' | '!' => {
+                chars.next();
+                let equals = chars.next_if(|&(_, c)| c == '=').is_some();
+                match (c, equals) {
+                    ('>', false) => Token::Compare(Op::Gt),
+                    ('>', true) => Token::Compare(Op::Ge),
+                    ('<', false) => Token::Compare(Op::Lt),
+                    ('<', true) => Token::Compare(Op::Le),
+                    ('=', _) => Token::Compare(Op::Eq),
+                    ('!', true) => Token::Compare(Op::Ne),
+                    _ => return Err(syntax(offset, "use `not` for negation")),
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut word = String::new();
+                while let Some((_, c)) = chars.next_if(|&(_, c)| c.is_alphanumeric() || c == '_') {
+                    word.extend(c.to_lowercase());
+                }
+                Token::Word(word)
+            }
+            _ => return Err(syntax(offset, "unexpected character")),
+        };
+        tokens.push((token, offset));
+    }
+    Ok(tokens)
+}
+
+/// Recursive-descent parser over the tokens
+struct Parser<'a> {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    depth: usize,
+    end: usize,
+    limits: &'a RegexLimits,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map_or(self.end, |(_, offset)| *offset)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    /// Consumes the next token if it is the given keyword
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Word(word)) if word == keyword);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, expected: Token, message: &str) -> Result<(), ExprError> {
+        let offset = self.offset();
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            _ => Err(syntax(offset, message)),
+        }
+    }
+
+    fn disjunction(&mut self) -> Result<Node, ExprError> {
+        let mut nodes = vec![self.conjunction()?];
+        while self.keyword("or") {
+            nodes.push(self.conjunction()?);
+        }
+        Ok(match nodes.len() {
+            1 => nodes.remove(0),
+            _ => Node::Any(nodes),
+        })
+    }
+
+    fn conjunction(&mut self) -> Result<Node, ExprError> {
+        let start = self.offset();
+        let mut nodes = vec![self.unary()?];
+        while self.keyword("and") {
+            nodes.push(self.unary()?);
+        }
+        if self.keyword("within") {
+            let offset = self.offset();
+            let chars = match self.next() {
+                Some(Token::Number(n)) if n.fract() == 0.0 => n as usize,
+                _ => return Err(syntax(offset, "expected a whole number of chars")),
+            };
+            if !(self.keyword("chars") || self.keyword("char")) {
+                return Err(syntax(self.offset(), "expected `chars`"));
+            }
+            let terms = nodes
+                .into_iter()
+                .map(|node| match node {
+                    Node::Text(regex) => Some(regex),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()
+                .filter(|terms| terms.len() > 1)
+                .ok_or_else(|| {
+                    syntax(
+                        start,
+                        "`within` needs two or more contains or matches conditions joined with `and`",
+                    )
+                })?;
+            return Ok(Node::Near { terms, chars });
+        }
+        Ok(match nodes.len() {
+            1 => nodes.remove(0),
+            _ => Node::All(nodes),
+        })
+    }
+
+    fn unary(&mut self) -> Result<Node, ExprError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(syntax(self.offset(), "expression is nested too deeply"));
+        }
+        self.depth += 1;
+        let node = if self.keyword("not") {
+            Node::Not(Box::new(self.unary()?))
+        } else if self.peek() == Some(&Token::Open) {
+            self.position += 1;
+            let node = self.disjunction()?;
+            self.expect(Token::Close, "expected `)`")?;
+            node
+        } else {
+            self.condition()?
+        };
+        self.depth -= 1;
+        Ok(node)
+    }
+
+    fn condition(&mut self) -> Result<Node, ExprError> {
+        let offset = self.offset();
+        let name = match self.next() {
+            Some(Token::Word(name)) => name,
+            _ => return Err(syntax(offset, "expected a condition")),
+        };
+        match name.as_str() {
+            "contains" | "matches" => {
+                self.expect(Token::Open, "expected `(`")?;
+                let offset = self.offset();
+                let text = match self.next() {
+                    Some(Token::Text(text)) => text,
+                    _ => return Err(syntax(offset, "expected a quoted string")),
+                };
+                self.expect(Token::Close, "expected `)`")?;
+                let pattern = match name.as_str() {
+                    "contains" => format!("(?i){}", engine::escape(&text)),
+                    _ => text,
+                };
+                Ok(Node::Text(compile_pattern(&pattern, self.limits)?))
+            }
+            "length" | "entropy" => {
+                let offset = self.offset();
+                let op = match self.next() {
+                    Some(Token::Compare(op)) => op,
+                    _ => return Err(syntax(offset, "expected a comparison such as `>`")),
+                };
+                let offset = self.offset();
+                let value = match self.next() {
+                    Some(Token::Number(value)) => value,
+                    _ => return Err(syntax(offset, "expected a number")),
+                };
+                let metric = match name.as_str() {
+                    "length" => Metric::Length,
+                    _ => Metric::Entropy,
+                };
+                Ok(Node::Compare { metric, op, value })
+            }
+            _ => Err(syntax(
+                offset,
+                "expected contains, matches, length or entropy",
+            )),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use super::validation::Validator;
+
+/// Location of the raw value inside a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// Named path parameter
+    Path(&'static str),
+    /// Named query string parameter
+    Query(&'static str),
+    /// Field of a URL-encoded form body
+    Form(&'static str),
+    /// Top-level field of a JSON body
+    Json(&'static str),
+}
+
+/// Validators that know where their input lives in a request
+///
+/// Framework extractors construct the validator with [`Default`] and read
+/// the raw value from [`RequestField::SOURCE`].
+pub trait RequestField<T>: Validator<T> + Default {
+    /// Where the extractor reads the raw value from
+    const SOURCE: Source;
+}
+
+/// Reasons a field could not be read from a request
+#[cfg(any(feature = "axum", feature = "actix"))]
+#[derive(Debug)]
+pub(crate) enum FieldError {
+    Missing(&'static str),
+    Malformed(String),
+}
+
+/// Reads a top-level scalar field from a JSON body as a string
+#[cfg(any(feature = "axum", feature = "actix"))]
+pub(crate) fn json_field(body: &[u8], name: &'static str) -> Result<String, FieldError> {
+    use serde_json::Value;
+
+    let json: Value =
+        serde_json::from_slice(body).map_err(|err| FieldError::Malformed(err.to_string()))?;
+    match json.get(name) {
+        Some(Value::String(value)) => Ok(value.clone()),
+        Some(value @ (Value::Number(_) | Value::Bool(_))) => Ok(value.to_string()),
+        Some(_) => Err(FieldError::Malformed(format!(
+            "field `{}` is not a scalar value",
+            name
+        ))),
+        None => Err(FieldError::Missing(name)),
+    }
+}
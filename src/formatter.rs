@@ -0,0 +1,101 @@
+use super::error::ValidationError;
+use std::collections::HashMap;
+
+/// Renders validation errors into user-facing messages
+pub trait MessageFormatter: Send + Sync {
+    /// Formats an error for display
+    fn format(&self, error: &ValidationError) -> String;
+}
+
+/// Formatter using the built-in `Display` messages
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultFormatter;
+
+impl MessageFormatter for DefaultFormatter {
+    fn format(&self, error: &ValidationError) -> String {
+        error.to_string()
+    }
+}
+
+/// Formatter rendering per-code message templates
+///
+/// Templates may contain the placeholders `{code}`, `{count}`, `{symbols}`,
+/// `{type}`, `{patterns}`, `{message}` and `{hint}`; placeholders that do not
+/// apply to an error render as empty strings. Errors without a template fall
+/// back to their `Display` message.
+///
+/// ```rust
+/// use huginn::formatter::{MessageFormatter, TemplateFormatter};
+/// use huginn::ValidationError;
+///
+/// let formatter = TemplateFormatter::new()
+///     .with_template("HUG-020", "Please enter a valid {type}");
+/// let message = formatter.format(&ValidationError::invalid_format("email"));
+/// assert_eq!(message, "Please enter a valid email");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TemplateFormatter {
+    templates: HashMap<&'static str, String>,
+}
+
+impl TemplateFormatter {
+    /// Creates formatter without templates
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the template for an error code (see [`ValidationError::code`])
+    pub fn with_template<S: Into<String>>(mut self, code: &'static str, template: S) -> Self {
+        self.templates.insert(code, template.into());
+        self
+    }
+}
+
+impl MessageFormatter for TemplateFormatter {
+    fn format(&self, error: &ValidationError) -> String {
+        let Some(template) = self.templates.get(error.code()) else {
+            return error.to_string();
+        };
+
+        let (count, symbols, target_type, patterns, message) = match error {
+            ValidationError::DangerousCharacters { symbols, count, .. } => {
+                (count.to_string(), symbols.clone(), "", String::new(), "")
+            }
+            ValidationError::InvalidFormat { target_type, .. } => (
+                String::new(),
+                String::new(),
+                *target_type,
+                String::new(),
+                "",
+            ),
+            ValidationError::BlockedPattern { matches, .. } => (
+                matches.len().to_string(),
+                String::new(),
+                "",
+                matches
+                    .iter()
+                    .map(|m| m.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                "",
+            ),
+            ValidationError::Custom { message, .. } | ValidationError::Transient { message } => (
+                String::new(),
+                String::new(),
+                "",
+                String::new(),
+                message.as_str(),
+            ),
+            _ => (String::new(), String::new(), "", String::new(), ""),
+        };
+
+        template
+            .replace("{code}", error.code())
+            .replace("{count}", &count)
+            .replace("{symbols}", &symbols)
+            .replace("{type}", target_type)
+            .replace("{patterns}", &patterns)
+            .replace("{message}", message)
+            .replace("{hint}", error.hint().unwrap_or(""))
+    }
+}
@@ -0,0 +1,469 @@
+use super::{
+    config::SecurityConfig,
+    error::{ValidationError, ValidationErrors},
+    json::{sanitize_json, JsonSchema},
+    validation::prepare_input,
+};
+use serde_json::Value;
+use std::{borrow::Cow, collections::HashMap, mem, str::Chars};
+
+/// Limits applied to GraphQL documents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphqlLimits {
+    /// Maximum document length in bytes
+    pub max_query_len: usize,
+    /// Maximum nesting depth of selection sets
+    pub max_depth: usize,
+    /// Maximum number of field selections, a proxy for query cost
+    pub max_fields: usize,
+    /// Maximum number of aliased fields
+    pub max_aliases: usize,
+}
+
+impl Default for GraphqlLimits {
+    fn default() -> Self {
+        Self {
+            max_query_len: 16 << 10,
+            max_depth: 10,
+            max_fields: 200,
+            max_aliases: 15,
+        }
+    }
+}
+
+/// Shape of a screened GraphQL document
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryStats {
+    /// Deepest selection set nesting
+    pub depth: usize,
+    /// Number of field selections
+    pub fields: usize,
+    /// Number of aliased fields
+    pub aliases: usize,
+}
+
+/// Screens a GraphQL document and its variables
+///
+/// The document is tokenized without building a syntax tree: string
+/// literals are unescaped and run through the pipeline stages that precede
+/// the validator, and selection sets are measured against the limits.
+/// Fragment spreads are expanded: a fragment's depth, fields and aliases
+/// count at every spread site, and undefined or cyclic fragments are
+/// rejected. Variables are screened like [`sanitize_json`], with errors keyed
+/// under `variables`.
+///
+/// ```rust
+/// use huginn::graphql::{screen_graphql, GraphqlLimits};
+/// use huginn::SecurityConfig;
+/// use serde_json::json;
+///
+/// let config = SecurityConfig::default();
+/// let limits = GraphqlLimits { max_aliases: 2, ..GraphqlLimits::default() };
+///
+/// let query = "query($id: ID!) { user(id: $id) { name friends { name } } }";
+/// let stats = screen_graphql(query, Some(&json!({ "id": "42" })), &config, &limits).unwrap();
+/// assert_eq!((stats.depth, stats.fields), (3, 4));
+///
+/// let batched = "{ a: user(id: 1) { name } b: user(id: 2) { name } c: user(id: 3) { name } }";
+/// assert!(screen_graphql(batched, None, &config, &limits).is_err());
+///
+/// let escaped = r#"{ user(name: "\u003Cscript\u003E") { id } }"#;
+/// assert!(screen_graphql(escaped, None, &config, &limits).is_err());
+///
+/// let shallow = GraphqlLimits { max_depth: 4, ..GraphqlLimits::default() };
+/// let split = "{ user { friends { ...Deep } } }
+///     fragment Deep on User { friends { friends { name } } }";
+/// assert!(screen_graphql(split, None, &config, &shallow).is_err());
+///
+/// let cyclic = "{ user { ...A } } fragment A on User { ...B } fragment B on User { ...A }";
+/// assert!(screen_graphql(cyclic, None, &config, &limits).is_err());
+///
+/// let variables = json!({ "id": "1 UNION SELECT password FROM users" });
+/// let errors = screen_graphql(query, Some(&variables), &config, &limits).unwrap_err();
+/// assert_eq!(errors.for_field("variables.id").count(), 1);
+/// ```
+pub fn screen_graphql(
+    query: &str,
+    variables: Option<&Value>,
+    config: &SecurityConfig,
+    limits: &GraphqlLimits,
+) -> Result<QueryStats, ValidationErrors> {
+    let mut errors = ValidationErrors::new();
+
+    if query.len() > limits.max_query_len {
+        errors.push_field(
+            "query",
+            limit_error("Query is too long", "bytes", limits.max_query_len),
+        );
+        return Err(errors);
+    }
+
+    let stats = match measure(query, config) {
+        Ok(stats) => stats,
+        Err(error) => {
+            errors.push_field("query", error);
+            return Err(errors);
+        }
+    };
+
+    if stats.depth > limits.max_depth {
+        errors.push_field(
+            "query",
+            limit_error("Query is nested too deeply", "levels", limits.max_depth),
+        );
+    }
+    if stats.fields > limits.max_fields {
+        errors.push_field(
+            "query",
+            limit_error("Query selects too many fields", "fields", limits.max_fields),
+        );
+    }
+    if stats.aliases > limits.max_aliases {
+        errors.push_field(
+            "query",
+            limit_error("Query uses too many aliases", "aliases", limits.max_aliases),
+        );
+    }
+
+    if let Some(variables) = variables {
+        if let Err(variable_errors) = sanitize_json(variables, config, &JsonSchema::new()) {
+            for (field, error) in variable_errors {
+                let field = match field {
+                    Some(field) if !field.is_empty() => format!("variables.{}", field),
+                    _ => "variables".to_string(),
+                };
+                errors.push_field(field, error);
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(stats)
+    } else {
+        Err(errors)
+    }
+}
+
+fn limit_error(message: &str, unit: &str, limit: usize) -> ValidationError {
+    ValidationError::custom(message).with_hint(format!("use at most {} {}", limit, unit))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Name(&'a str),
+    Spread,
+    Colon,
+    Other,
+}
+
+/// Top-level operation or fragment definition, measured without expanding spreads
+#[derive(Debug, Default)]
+struct Definition<'a> {
+    /// Fragment name, or `None` for operations
+    fragment: Option<&'a str>,
+    stats: QueryStats,
+    /// Spread fragment names with the selection depth they appear at
+    spreads: Vec<(&'a str, usize)>,
+    tokens: usize,
+}
+
+/// Walks the document, screening string literals and measuring selection sets
+fn measure(query: &str, config: &SecurityConfig) -> Result<QueryStats, ValidationError> {
+    let bytes = query.as_bytes();
+    let mut definitions = Vec::new();
+    let mut current = Definition::default();
+    let mut depth = 0usize;
+    let mut parens = 0usize;
+    let mut pending: Option<Token> = None;
+    let mut prev = [Token::Other, Token::Other];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        let mut closed = false;
+        let token = match b {
+            b' ' | b'\t' | b'\n' | b'\r' | b',' => {
+                i += 1;
+                continue;
+            }
+            b'#' => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            b'"' => {
+                let (literal, end) = string_literal(query, i)?;
+                prepare_input(&literal, config, &[])?;
+                i = end;
+                Token::Other
+            }
+            b'{' if parens == 0 => {
+                depth += 1;
+                current.stats.depth = current.stats.depth.max(depth);
+                i += 1;
+                Token::Other
+            }
+            b'}' if parens == 0 => {
+                depth = depth.saturating_sub(1);
+                closed = depth == 0;
+                i += 1;
+                Token::Other
+            }
+            b'(' => {
+                parens += 1;
+                i += 1;
+                Token::Other
+            }
+            b')' => {
+                parens = parens.saturating_sub(1);
+                i += 1;
+                Token::Other
+            }
+            b':' => {
+                i += 1;
+                Token::Colon
+            }
+            b'.' if query[i..].starts_with("...") => {
+                i += 3;
+                Token::Spread
+            }
+            b if b == b'_' || b.is_ascii_alphabetic() => {
+                let start = i;
+                while i < bytes.len() && (bytes[i] == b'_' || bytes[i].is_ascii_alphanumeric()) {
+                    i += 1;
+                }
+                Token::Name(&query[start..i])
+            }
+            _ => {
+                i += 1;
+                Token::Other
+            }
+        };
+
+        settle(&mut current.stats, &mut pending, token);
+
+        if let Token::Name(name) = token {
+            let spread_target = prev[1] == Token::Spread
+                || (prev[1] == Token::Name("on") && prev[0] == Token::Spread);
+            let directive = i > name.len() && bytes[i - name.len() - 1] == b'@';
+            if depth == 0 && current.tokens == 1 && prev[1] == Token::Name("fragment") {
+                current.fragment = Some(name);
+            }
+            if depth > 0 && prev[1] == Token::Spread && name != "on" && !directive {
+                current.spreads.push((name, depth));
+            }
+            if depth > 0 && parens == 0 && !spread_target && !directive {
+                pending = Some(token);
+            }
+        }
+        prev = [prev[1], token];
+
+        if closed {
+            definitions.push(mem::take(&mut current));
+        } else {
+            current.tokens += 1;
+        }
+    }
+    settle(&mut current.stats, &mut pending, Token::Other);
+    definitions.push(current);
+
+    expand(&definitions)
+}
+
+/// Totals the operations of a document, counting each fragment at every spread site
+///
+/// Undefined and cyclic fragments are rejected.
+fn expand(definitions: &[Definition<'_>]) -> Result<QueryStats, ValidationError> {
+    let mut fragments = HashMap::new();
+    for definition in definitions {
+        if let Some(name) = definition.fragment {
+            if fragments.insert(name, definition).is_some() {
+                return Err(invalid_document("name each fragment once"));
+            }
+        }
+    }
+
+    let mut expanded = HashMap::new();
+    let mut stats = QueryStats::default();
+    for definition in definitions.iter().filter(|d| d.fragment.is_none()) {
+        let operation = expand_definition(definition, &fragments, &mut expanded, &mut Vec::new())?;
+        stats.depth = stats.depth.max(operation.depth);
+        stats.fields = stats.fields.saturating_add(operation.fields);
+        stats.aliases = stats.aliases.saturating_add(operation.aliases);
+    }
+    Ok(stats)
+}
+
+/// Adds the expanded stats of every fragment spread in a definition to its own
+///
+/// `path` holds the fragments being expanded, so a spread of one of them is a cycle.
+fn expand_definition<'a>(
+    definition: &Definition<'a>,
+    fragments: &HashMap<&'a str, &Definition<'a>>,
+    expanded: &mut HashMap<&'a str, QueryStats>,
+    path: &mut Vec<&'a str>,
+) -> Result<QueryStats, ValidationError> {
+    let mut stats = definition.stats;
+    for &(name, depth) in &definition.spreads {
+        let fragment = match expanded.get(name) {
+            Some(fragment) => *fragment,
+            None => {
+                if path.contains(&name) {
+                    return Err(invalid_document("break the cycle between fragments"));
+                }
+                let definition = fragments
+                    .get(name)
+                    .ok_or_else(|| invalid_document("define every spread fragment"))?;
+                path.push(name);
+                let fragment = expand_definition(definition, fragments, expanded, path)?;
+                path.pop();
+                expanded.insert(name, fragment);
+                fragment
+            }
+        };
+        // The fragment's outer selection set is the one holding the spread
+        stats.depth = stats.depth.max((depth + fragment.depth).saturating_sub(1));
+        stats.fields = stats.fields.saturating_add(fragment.fields);
+        stats.aliases = stats.aliases.saturating_add(fragment.aliases);
+    }
+    Ok(stats)
+}
+
+fn invalid_document(hint: &str) -> ValidationError {
+    ValidationError::invalid_format("GraphQL document").with_hint(hint)
+}
+
+/// Counts a held selection name as an alias or a field once the next token is known
+///
+/// A name followed by `:` is an alias; the field name after it is counted separately.
+fn settle(stats: &mut QueryStats, pending: &mut Option<Token>, next: Token) {
+    if pending.take().is_some() {
+        if next == Token::Colon {
+            stats.aliases += 1;
+        } else {
+            stats.fields += 1;
+        }
+    }
+}
+
+/// Returns the value of the string literal starting at `start` and the offset after it
+///
+/// Escape sequences are resolved, so the pipeline screens the text the
+/// server will see.
+fn string_literal(query: &str, start: usize) -> Result<(Cow<'_, str>, usize), ValidationError> {
+    let unterminated = || invalid_document("close the string literal");
+    let bytes = query.as_bytes();
+
+    if bytes[start..].starts_with(b"\"\"\"") {
+        let body = start + 3;
+        let mut i = body;
+        let mut escaped = false;
+        while i < bytes.len() {
+            if bytes[i..].starts_with(b"\\\"\"\"") {
+                escaped = true;
+                i += 4;
+            } else if bytes[i..].starts_with(b"\"\"\"") {
+                let value = &query[body..i];
+                let value = match escaped {
+                    true => Cow::Owned(value.replace("\\\"\"\"", "\"\"\"")),
+                    false => Cow::Borrowed(value),
+                };
+                return Ok((value, i + 3));
+            } else {
+                i += 1;
+            }
+        }
+        return Err(unterminated());
+    }
+
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => {
+                let raw = &query[start + 1..i];
+                let value = match raw.contains('\\') {
+                    true => Cow::Owned(unescape(raw)?),
+                    false => Cow::Borrowed(raw),
+                };
+                return Ok((value, i + 1));
+            }
+            b'\n' => break,
+            _ => i += 1,
+        }
+    }
+    Err(unterminated())
+}
+
+/// Resolves the escape sequences of a quoted string
+fn unescape(raw: &str) -> Result<String, ValidationError> {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        value.push(match chars.next() {
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some('/') => '/',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('u') => unicode_escape(&mut chars)?,
+            _ => return Err(invalid_escape()),
+        });
+    }
+    Ok(value)
+}
+
+/// Reads the code point of a `\u` escape, joining surrogate pairs
+fn unicode_escape(chars: &mut Chars<'_>) -> Result<char, ValidationError> {
+    let high = code_unit(chars)?;
+    let code = if (0xD800..0xDC00).contains(&high) {
+        if chars.next() != Some('\\') || chars.next() != Some('u') {
+            return Err(invalid_escape());
+        }
+        let low = code_unit(chars)?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(invalid_escape());
+        }
+        0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+    } else {
+        high
+    };
+    char::from_u32(code).ok_or_else(invalid_escape)
+}
+
+/// Reads the hex digits of a `\u` escape, either four of them or up to six in braces
+fn code_unit(chars: &mut Chars<'_>) -> Result<u32, ValidationError> {
+    let braced = chars.as_str().starts_with('{');
+    let digits = match braced {
+        true => {
+            chars.next();
+            let rest = chars.as_str();
+            let end = rest.find('}').filter(|end| (1..=6).contains(end));
+            let digits = &rest[..end.ok_or_else(invalid_escape)?];
+            chars.nth(digits.len());
+            digits
+        }
+        false => {
+            let rest = chars.as_str();
+            let digits = rest.get(..4).ok_or_else(invalid_escape)?;
+            chars.nth(3);
+            digits
+        }
+    };
+    match digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        true => u32::from_str_radix(digits, 16).map_err(|_| invalid_escape()),
+        false => Err(invalid_escape()),
+    }
+}
+
+fn invalid_escape() -> ValidationError {
+    invalid_document("use a valid escape sequence")
+}
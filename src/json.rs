@@ -0,0 +1,336 @@
+use super::{
+    config::SecurityConfig,
+    error::{ValidationError, ValidationErrors},
+    path::{self, Segment},
+    query::{denied_key_error, denied_segment, PROTOTYPE_KEYS},
+    validation::{boxed_check, decode_input, prepare_input, BoxedCheck, Validator},
+};
+use serde_json::{Map, Value};
+use std::{fmt, fmt::Debug};
+
+struct PathRule {
+    pattern: Vec<Segment>,
+    source: String,
+    check: BoxedCheck,
+}
+
+/// Rules applied while walking a JSON document
+///
+/// Paths use the same notation as struct validation errors: `order.items[2].sku`.
+/// In rule paths, `[*]` matches any array index.
+///
+/// ```rust
+/// use huginn::json::{sanitize_json, JsonSchema};
+/// use huginn::{SecurityConfig, ValidationError, Validator};
+/// use serde_json::json;
+///
+/// struct Sku;
+///
+/// impl Validator<String> for Sku {
+///     fn validate(&self, input: &str) -> Result<String, ValidationError> {
+///         if input.chars().all(|c| c.is_ascii_alphanumeric()) {
+///             Ok(input.to_string())
+///         } else {
+///             Err(ValidationError::invalid_format(self.target_type()))
+///         }
+///     }
+///
+///     fn target_type(&self) -> &'static str {
+///         "sku"
+///     }
+/// }
+///
+/// let schema = JsonSchema::new().with_path("items[*].sku", Sku);
+/// let config = SecurityConfig::default();
+///
+/// let payload = json!({
+///     "note": "<script>alert(1)</script>",
+///     "items": [{ "sku": "A1" }, { "sku": "B-2" }],
+/// });
+/// let errors = sanitize_json(&payload, &config, &schema).unwrap_err();
+/// assert_eq!(errors.for_field("note").count(), 1);
+/// assert_eq!(errors.for_field("items[1].sku").count(), 1);
+/// ```
+pub struct JsonSchema {
+    rules: Vec<PathRule>,
+    key_check: Option<BoxedCheck>,
+    max_key_len: usize,
+    max_depth: usize,
+    deny_operator_keys: bool,
+    denied_keys: Vec<String>,
+}
+
+impl Default for JsonSchema {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            key_check: None,
+            max_key_len: 256,
+            max_depth: 64,
+            deny_operator_keys: false,
+            denied_keys: PROTOTYPE_KEYS.iter().map(|key| key.to_string()).collect(),
+        }
+    }
+}
+
+impl Debug for JsonSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonSchema")
+            .field(
+                "paths",
+                &self.rules.iter().map(|r| &r.source).collect::<Vec<_>>(),
+            )
+            .field("max_key_len", &self.max_key_len)
+            .field("max_depth", &self.max_depth)
+            .field("deny_operator_keys", &self.deny_operator_keys)
+            .field("denied_keys", &self.denied_keys)
+            .finish()
+    }
+}
+
+impl JsonSchema {
+    /// Creates new schema that only screens string leaves and keys
+    ///
+    /// Keys named `__proto__`, `constructor` or `prototype`, or containing
+    /// them as a dotted segment, are rejected so the document is safe to hand
+    /// to JavaScript code that merges it into existing objects.
+    ///
+    /// ```rust
+    /// use huginn::json::{sanitize_json, JsonSchema};
+    /// use huginn::SecurityConfig;
+    /// use serde_json::json;
+    ///
+    /// let config = SecurityConfig::default();
+    /// let payload = json!({ "settings": { "__proto__": { "isAdmin": true } } });
+    /// let errors = sanitize_json(&payload, &config, &JsonSchema::new()).unwrap_err();
+    /// assert_eq!(errors.for_field("settings.__proto__").count(), 1);
+    ///
+    /// // Keys are checked as decoded, which is how they appear in the output
+    /// let encoded = json!({ "%5F_proto__": { "isAdmin": true } });
+    /// assert!(sanitize_json(&encoded, &config, &JsonSchema::new()).is_err());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validates the scalar at a path with the given validator
+    ///
+    /// Numbers and booleans are validated in their JSON text form.
+    pub fn with_path<T, V>(mut self, path: &str, validator: V) -> Self
+    where
+        T: Debug + Send + Sync,
+        V: Validator<T> + 'static,
+    {
+        self.rules.push(PathRule {
+            pattern: path::parse(path),
+            source: path.to_string(),
+            check: boxed_check(validator),
+        });
+        self
+    }
+
+    /// Validates every object key with the given validator
+    pub fn with_key_validator<T, V>(mut self, validator: V) -> Self
+    where
+        T: Debug + Send + Sync,
+        V: Validator<T> + 'static,
+    {
+        self.key_check = Some(boxed_check(validator));
+        self
+    }
+
+    /// Sets the maximum object key length in bytes (256 by default)
+    pub fn with_max_key_len(mut self, max_key_len: usize) -> Self {
+        self.max_key_len = max_key_len;
+        self
+    }
+
+    /// Sets the maximum nesting depth of objects and arrays (64 by default)
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Rejects object keys equal to or containing the given key as a dotted segment
+    pub fn deny_key(mut self, key: &str) -> Self {
+        if !self.denied_keys.iter().any(|denied| denied == key) {
+            self.denied_keys.push(key.to_string());
+        }
+        self
+    }
+
+    /// Stops rejecting the given key, including the built-in prototype keys
+    pub fn allow_key(mut self, key: &str) -> Self {
+        self.denied_keys.retain(|denied| denied != key);
+        self
+    }
+
+    /// Rejects object keys that MongoDB would treat as operators
+    ///
+    /// Keys starting with `$` (`$where`, `$ne`, `$gt`) or containing `.`
+    /// let a client turn a value comparison into a query of its own when the
+    /// document is passed to a query or update unchanged.
+    ///
+    /// ```rust
+    /// use huginn::json::{sanitize_json, JsonSchema};
+    /// use huginn::SecurityConfig;
+    /// use serde_json::json;
+    ///
+    /// let schema = JsonSchema::new().deny_operator_keys();
+    /// let config = SecurityConfig::default();
+    ///
+    /// let login = json!({ "user": "admin", "password": { "$ne": null } });
+    /// let errors = sanitize_json(&login, &config, &schema).unwrap_err();
+    /// assert_eq!(errors.for_field("password.$ne").count(), 1);
+    ///
+    /// let encoded = json!({ "user": { "%24ne": 1 } });
+    /// assert!(sanitize_json(&encoded, &config, &schema).is_err());
+    /// ```
+    pub fn deny_operator_keys(mut self) -> Self {
+        self.deny_operator_keys = true;
+        self
+    }
+}
+
+/// Screens a parsed JSON document, returning a copy with sanitized strings
+///
+/// Every key and string leaf runs through the pipeline stages that precede
+/// the validator, and scalars at paths registered in the schema are validated.
+/// All failures are collected, keyed by the path of the offending value.
+pub fn sanitize_json(
+    value: &Value,
+    config: &SecurityConfig,
+    schema: &JsonSchema,
+) -> Result<Value, ValidationErrors> {
+    let mut walker = Walker {
+        config,
+        schema,
+        path: Vec::new(),
+        errors: ValidationErrors::new(),
+    };
+    let sanitized = walker.walk(value, 0);
+
+    if walker.errors.is_empty() {
+        Ok(sanitized)
+    } else {
+        Err(walker.errors)
+    }
+}
+
+struct Walker<'a> {
+    config: &'a SecurityConfig,
+    schema: &'a JsonSchema,
+    path: Vec<Segment>,
+    errors: ValidationErrors,
+}
+
+impl Walker<'_> {
+    fn walk(&mut self, value: &Value, depth: usize) -> Value {
+        match value {
+            Value::Object(_) | Value::Array(_) if depth >= self.schema.max_depth => {
+                self.error(
+                    ValidationError::custom(format!(
+                        "JSON nesting exceeds {} levels",
+                        self.schema.max_depth
+                    ))
+                    .with_hint("flatten the document"),
+                );
+                Value::Null
+            }
+            Value::Object(object) => {
+                let mut sanitized = Map::new();
+                for (key, value) in object {
+                    self.path.push(Segment::Key(key.clone()));
+                    let key = self.check_key(key);
+                    let value = self.walk(value, depth + 1);
+                    self.path.pop();
+                    sanitized.insert(key, value);
+                }
+                Value::Object(sanitized)
+            }
+            Value::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(index, item)| {
+                        self.path.push(Segment::Index(index));
+                        let item = self.walk(item, depth + 1);
+                        self.path.pop();
+                        item
+                    })
+                    .collect(),
+            ),
+            Value::String(text) => {
+                let validated = self.check_rules(text);
+                Value::String(self.screen(text, !validated))
+            }
+            Value::Number(_) | Value::Bool(_) => {
+                self.check_rules(&value.to_string());
+                value.clone()
+            }
+            Value::Null => Value::Null,
+        }
+    }
+
+    fn check_key(&mut self, key: &str) -> String {
+        if key.len() > self.schema.max_key_len {
+            self.error(
+                ValidationError::invalid_format("object key").with_hint(format!(
+                    "shorten the key to at most {} bytes",
+                    self.schema.max_key_len
+                )),
+            );
+        }
+        let schema = self.schema;
+        let decoded = decode_input(key);
+        if let Some(segment) = denied_segment(&decoded, &schema.denied_keys) {
+            self.error(denied_key_error(segment));
+        }
+        if schema.deny_operator_keys && (decoded.starts_with('$') || decoded.contains('.')) {
+            self.error(
+                ValidationError::invalid_format("object key")
+                    .with_hint("remove query operators such as '$where' and '.' from keys"),
+            );
+        }
+        // The key validator runs the pipeline, which decodes the key itself
+        if let Some(check) = &schema.key_check {
+            if let Err(error) = check(key, self.config) {
+                self.error(error);
+            }
+        }
+        self.screen(key, schema.key_check.is_none())
+    }
+
+    /// Runs the validators registered for the current path, returning whether any applied
+    fn check_rules(&mut self, text: &str) -> bool {
+        let schema = self.schema;
+        let mut applied = false;
+        for rule in schema.rules.iter() {
+            if path::matches(&rule.pattern, &self.path) {
+                applied = true;
+                if let Err(error) = (rule.check)(text, self.config) {
+                    self.error(error);
+                }
+            }
+        }
+        applied
+    }
+
+    /// Screens text, reporting failures unless a path validator already did
+    fn screen(&mut self, text: &str, report: bool) -> String {
+        match prepare_input(text, self.config, &[]) {
+            Ok(cleaned) => cleaned.into_owned(),
+            Err(error) => {
+                if report {
+                    self.error(error);
+                }
+                text.to_string()
+            }
+        }
+    }
+
+    fn error(&mut self, error: ValidationError) {
+        let path = path::render(&self.path);
+        self.errors.push_field(path, error);
+    }
+}
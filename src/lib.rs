@@ -1,3 +1,4 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 #![forbid(unsafe_code)]
 
@@ -10,89 +11,352 @@
 //! - Customizable security rules
 //! - Concurrent processing capabilities
 //! - Asynchronous validation support
+//! - `no_std` support with `alloc` for the core
+//! - Choice of `regex` or the smaller `regex-lite` pattern backend
 //!
 //! ## Example: Synchronous String Validation
 //!
 //! This example shows how to validate a string with a maximum length synchronously:
 //!
 //! ```rust
-//! use huginn::{SecurityConfig, sanitize_and_validate, Validator, ValidationError};
-//!
-//! struct LengthValidator {
-//!     max: usize,
-//! }
-//!
-//! impl Validator<String> for LengthValidator {
-//!     fn validate(&self, input: &str) -> Result<String, ValidationError> {
-//!         if input.len() <= self.max {
-//!             Ok(input.to_string())
-//!         } else {
-//!             Err(ValidationError::custom("Input too long"))
-//!         }
-//!     }
-//!
-//!     fn target_type(&self) -> &'static str {
-//!         "string"
-//!     }
-//! }
+//! # #[cfg(feature = "std")]
+//! # {
+//! use huginn::validators::LengthValidator;
+//! use huginn::{SecurityConfig, sanitize_and_validate};
 //!
 //! fn main() -> Result<(), Box<dyn std::error::Error>> {
 //!     let config = SecurityConfig::default();
-//!     let validator = LengthValidator { max: 10 };
+//!     let validator = LengthValidator::new().with_max(10);
 //!     let result = sanitize_and_validate("hello", &validator, &config)?;
 //!     println!("Sanitized input: {}", result.cleaned);
 //!     Ok(())
 //! }
+//! # main().unwrap();
+//! # }
 //! ```
 //!
-//! ## Example: Asynchronous Number Validation
+//! ## Example: Pipeline Hooks
 //!
-//! This example demonstrates custom configuration and asynchronous number validation:
+//! Hooks can observe, rewrite, or veto input at each stage of the pipeline:
 //!
 //! ```rust
-//! use huginn::{SecurityConfig, sanitize_and_validate_async, Validator, ValidationError};
-//! use async_trait::async_trait;
+//! # #[cfg(feature = "std")]
+//! # {
+//! use huginn::{Pipeline, PipelineHook, SecurityConfig, Validator, ValidationError};
 //!
-//! struct NumberValidator;
+//! struct Trim;
 //!
-//! #[async_trait]
-//! impl Validator<i32> for NumberValidator {
-//!     fn validate(&self, input: &str) -> Result<i32, ValidationError> {
-//!         input.parse().map_err(|_| ValidationError::InvalidFormat { target_type: self.target_type() })
+//! impl PipelineHook for Trim {
+//!     fn before_sanitize(&self, input: &mut String) -> Result<(), ValidationError> {
+//!         *input = input.trim().to_string();
+//!         Ok(())
 //!     }
+//! }
+//!
+//! struct Echo;
 //!
-//!     async fn validate_async(&self, input: &str) -> Result<i32, ValidationError> {
-//!         Ok(self.validate(input)?)
+//! impl Validator<String> for Echo {
+//!     fn validate(&self, input: &str) -> Result<String, ValidationError> {
+//!         Ok(input.to_string())
 //!     }
 //!
 //!     fn target_type(&self) -> &'static str {
-//!         "i32"
+//!         "string"
 //!     }
 //! }
 //!
-//! #[tokio::main]
-//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
-//!     let config = SecurityConfig::builder()
-//!         .add_forbidden_char('#')
-//!         .add_blocked_pattern(r"\d{5}")?
-//!         .build();
-//!     let validator = NumberValidator;
-//!     let result = sanitize_and_validate_async("42", &validator, &config).await?;
-//!     println!("Sanitized number: {}", result.cleaned);
-//!     Ok(())
-//! }
+//! let pipeline = Pipeline::builder(SecurityConfig::default())
+//!     .add_hook(Trim)
+//!     .build();
+//! let result = pipeline.process("  hello  ", &Echo).unwrap();
+//! assert_eq!(result.cleaned, "hello");
+//! # }
 //! ```
+#![cfg_attr(
+    feature = "async",
+    doc = r#"
+## Example: Asynchronous Number Validation
+
+This example demonstrates custom configuration and asynchronous number validation:
+
+```rust
+use huginn::{SecurityConfig, sanitize_and_validate_async, Validator, ValidationError};
+use async_trait::async_trait;
+
+struct NumberValidator;
+
+#[async_trait]
+impl Validator<i32> for NumberValidator {
+    fn validate(&self, input: &str) -> Result<i32, ValidationError> {
+        input.parse().map_err(|_| ValidationError::invalid_format(self.target_type()))
+    }
+
+    async fn validate_async(&self, input: &str) -> Result<i32, ValidationError> {
+        Ok(self.validate(input)?)
+    }
+
+    fn target_type(&self) -> &'static str {
+        "i32"
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let config = SecurityConfig::builder()
+        .add_forbidden_char('#')
+        .add_blocked_pattern(r"\d{5}")?
+        .build();
+    let validator = NumberValidator;
+    let result = sanitize_and_validate_async("42", &validator, &config).await?;
+    println!("Sanitized number: {}", result.cleaned);
+    Ok(())
+}
+```
+"#
+)]
+
+extern crate alloc;
+
+#[cfg(all(feature = "std", not(any(feature = "regex", feature = "regex-lite"))))]
+compile_error!("the `std` feature needs a regex backend: enable `regex` or `regex-lite`");
+
+/// Module with actix-web extractors
+#[cfg(feature = "actix")]
+pub mod actix;
+
+/// Module with axum extractors
+#[cfg(feature = "axum")]
+pub mod axum;
+
+/// Module with clap value parsers
+#[cfg(feature = "clap")]
+pub mod clap;
+
+/// Module for recording rejected inputs
+#[cfg(feature = "std")]
+pub mod audit;
+
+/// Module for validating many inputs at once
+#[cfg(any(feature = "rayon", feature = "async"))]
+pub mod batch;
+
+/// Module for sanitizing into fixed-capacity buffers without allocating
+#[cfg(feature = "heapless")]
+pub mod bounded;
+
+/// Module with a validator backed by a blocklist shared through Redis
+#[cfg(feature = "redis")]
+pub mod blocklist;
 
 /// Module for configuring security parameters
 pub mod config;
 
+/// Module for request details passed along with inputs
+#[cfg(feature = "std")]
+pub mod context;
+
+/// Module selecting the regular expression backend
+#[cfg(feature = "std")]
+mod engine;
+
 /// Module for handling validation errors
 pub mod error;
 
+/// Module for screening CSV documents
+#[cfg(feature = "csv")]
+pub mod csv;
+
+/// Module with validators that look domains up in DNS
+#[cfg(feature = "dns")]
+pub mod dns;
+
+/// Module for dry runs reporting every finding without rejecting
+#[cfg(feature = "std")]
+pub mod explain;
+
+/// Module for rule expressions combining text, length and entropy conditions
+#[cfg(feature = "std")]
+pub mod expr;
+
+/// Module describing where framework extractors read their input
+#[cfg(feature = "std")]
+pub mod extract;
+
+/// Module for customizing error messages
+#[cfg(feature = "std")]
+pub mod formatter;
+
+/// Module for screening GraphQL documents and variables
+#[cfg(feature = "graphql")]
+pub mod graphql;
+
+/// Module for screening whole JSON documents
+#[cfg(feature = "json")]
+pub mod json;
+
+/// Module for writing untrusted text to logs
+#[cfg(feature = "std")]
+pub mod log;
+
+/// Module for collecting validation metrics
+#[cfg(feature = "std")]
+pub mod metrics;
+
+/// Module for validating multipart/form-data uploads
+#[cfg(feature = "async")]
+pub mod multipart;
+
+/// Module for addressing values inside nested documents
+#[cfg(any(feature = "json", feature = "prost", feature = "toml"))]
+#[cfg_attr(not(any(feature = "json", feature = "prost")), allow(dead_code))]
+mod path;
+
+/// Module for compiling user-supplied patterns within resource limits
+#[cfg(feature = "std")]
+pub mod pattern;
+
+/// Module for composing pipelines with middleware hooks
+#[cfg(feature = "std")]
+pub mod pipeline;
+
+/// Module exporting validation metrics to Prometheus
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
+
+/// Module for validating decoded protobuf messages
+#[cfg(feature = "prost")]
+pub mod prost;
+
+/// Module with a validator rejecting passwords found in known breaches
+#[cfg(feature = "pwned")]
+pub mod pwned;
+
+/// Module for per-key rate limiting
+#[cfg(feature = "std")]
+pub mod ratelimit;
+
+/// Module with a validator backed by an HTTP endpoint
+#[cfg(feature = "reqwest")]
+pub mod remote;
+
+/// Module for scoring the risk of inputs
+#[cfg(feature = "std")]
+pub mod risk;
+
+/// Module with built-in pattern categories
+pub mod rules;
+
+/// Module for exporting and importing rulesets shared between services
+#[cfg(feature = "serde")]
+pub mod ruleset;
+
+/// Module for parsing and validating query strings
+#[cfg(feature = "std")]
+pub mod query;
+
+/// Module with Rocket parameter and form guards
+#[cfg(feature = "rocket")]
+pub mod rocket;
+
+/// Module for sampling rejected inputs with personal data masked
+#[cfg(feature = "std")]
+pub mod sampler;
+
+/// Module with wrappers that sanitize values during deserialization
+#[cfg(feature = "serde")]
+pub mod sanitized;
+
+/// Module for scanning very large inputs in parallel chunks
+#[cfg(feature = "rayon")]
+pub mod scan;
+
+/// Module for SQL parameter and identifier validation
+#[cfg(feature = "std")]
+pub mod sql;
+
+/// Module for in-process validation statistics
+#[cfg(feature = "std")]
+pub mod stats;
+
+/// Module for validating structs with nested fields
+#[cfg(feature = "std")]
+pub mod structs;
+
 /// Core module for validation and sanitization
 pub mod validation;
 
+/// Module emitting tracing spans and events for pipeline runs
+#[cfg(feature = "tracing")]
+mod trace;
+
+/// Module for screening TOML documents
+#[cfg(feature = "toml")]
+pub mod toml;
+
+/// Module with newtypes that only successful validation constructs
+#[cfg(feature = "std")]
+pub mod typed;
+
+/// Module bridging the `validator` crate's `Validate` trait and huginn validators
+#[cfg(feature = "validator-compat")]
+pub mod validator_compat;
+
+/// Module with built-in validators for common input types
+#[cfg(feature = "std")]
+pub mod validators;
+
+/// Module with a wasm-bindgen API for running checks in the browser
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// Module with warp filters
+#[cfg(feature = "warp")]
+pub mod warp;
+
+/// Module for screening YAML documents
+#[cfg(feature = "yaml")]
+pub mod yaml;
+
 // Re-exporting core types
+#[cfg(feature = "rayon")]
+pub use batch::sanitize_and_validate_batch;
+#[cfg(feature = "async")]
+pub use batch::{sanitize_and_validate_batch_async, validate_stream, validate_stream_buffered};
+#[cfg(feature = "std")]
+pub use config::PatternRule;
 pub use config::SecurityConfig;
-pub use error::ValidationError;
-pub use validation::{sanitize_and_validate, sanitize_and_validate_async, SanitizedInput, Validator};
\ No newline at end of file
+#[cfg(feature = "std")]
+pub use context::ValidationContext;
+#[cfg(feature = "std")]
+pub use error::PatternError;
+pub use error::{CharPosition, ExternalError, PatternMatch, ValidationError, ValidationErrors};
+#[cfg(feature = "std")]
+pub use explain::{explain, ValidationReport};
+#[cfg(feature = "std")]
+pub use formatter::{MessageFormatter, TemplateFormatter};
+#[cfg(feature = "std")]
+pub use pattern::RegexLimits;
+#[cfg(feature = "async")]
+pub use pipeline::AsyncSanitizer;
+#[cfg(feature = "std")]
+pub use pipeline::{Pipeline, RejectionEvent};
+#[cfg(feature = "std")]
+pub use query::QuerySchema;
+pub use rules::{PatternCategory, ThreatClass};
+#[cfg(feature = "serde")]
+pub use sanitized::{Sanitized, SanitizedString};
+#[cfg(feature = "rayon")]
+pub use scan::ChunkedScan;
+#[cfg(feature = "std")]
+pub use structs::{validate_struct, Validate};
+#[cfg(feature = "async")]
+pub use tokio_util::sync::CancellationToken;
+pub use validation::{
+    sanitize_and_validate, sanitize_and_validate_collect, sanitize_and_validate_detailed,
+    PipelineHook, SanitizationReport, SanitizedInput, SanitizedInputDetailed, Validator,
+};
+#[cfg(feature = "async")]
+pub use validation::{
+    sanitize_and_validate_async, sanitize_and_validate_async_cancellable,
+    sanitize_and_validate_async_with_timeout,
+};
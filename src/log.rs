@@ -0,0 +1,89 @@
+use std::{borrow::Cow, fmt, iter::Peekable, str::Chars};
+
+/// Makes untrusted text safe to write into a single log line
+///
+/// CR and LF become `\r` and `\n`, so a value cannot end the current entry
+/// and forge a new one. ANSI escape sequences (CSI, OSC and two-byte
+/// escapes) are removed, so viewing the log in a terminal cannot recolor,
+/// retitle or rewrite it. Other control characters, NEL and the Unicode line
+/// and paragraph separators are shown as `\x..` or `\u....` escapes; tab is
+/// kept.
+///
+/// Input without such characters is returned borrowed.
+///
+/// ```rust
+/// use huginn::log::for_logging;
+///
+/// let forged = "bob\n2024-01-01 INFO admin logged in";
+/// assert_eq!(for_logging(forged), "bob\\n2024-01-01 INFO admin logged in");
+/// assert_eq!(for_logging("\x1b[2J\x1b[31mred\x1b[0m"), "red");
+/// assert_eq!(for_logging("plain text"), "plain text");
+/// ```
+pub fn for_logging(input: &str) -> Cow<'_, str> {
+    if !input.chars().any(needs_escape) {
+        return Cow::Borrowed(input);
+    }
+
+    let mut output = String::with_capacity(input.len() + 8);
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\u{1b}' => skip_escape(&mut chars),
+            '\u{9b}' => skip_csi(&mut chars),
+            c if needs_escape(c) && (c as u32) < 0x100 => {
+                output.push_str(&format!("\\x{:02x}", c as u32));
+            }
+            c if needs_escape(c) => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    Cow::Owned(output)
+}
+
+/// Displays a value through [`for_logging`], for use in log macros
+///
+/// ```rust
+/// use huginn::log::LogSafe;
+///
+/// let user = "eve\r\nINFO root login";
+/// assert_eq!(format!("login failed for {}", LogSafe(user)), "login failed for eve\\r\\nINFO root login");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct LogSafe<'a>(pub &'a str);
+
+impl fmt::Display for LogSafe<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&for_logging(self.0))
+    }
+}
+
+fn needs_escape(c: char) -> bool {
+    (c.is_control() && c != '\t') || c == '\u{2028}' || c == '\u{2029}'
+}
+
+/// Skips the rest of an escape sequence whose ESC was already consumed
+fn skip_escape(chars: &mut Peekable<Chars<'_>>) {
+    match chars.next() {
+        Some('[') => skip_csi(chars),
+        Some(']') => {
+            // Operating system command, terminated by BEL or ESC \
+            while let Some(c) = chars.next() {
+                if c == '\u{7}' || (c == '\u{1b}' && chars.next_if_eq(&'\\').is_some()) {
+                    break;
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Skips parameter, intermediate and final bytes of a control sequence
+fn skip_csi(chars: &mut Peekable<Chars<'_>>) {
+    while chars
+        .next_if(|c| ('\u{20}'..='\u{3f}').contains(c))
+        .is_some()
+    {}
+    chars.next_if(|c| ('\u{40}'..='\u{7e}').contains(c));
+}
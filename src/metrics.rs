@@ -0,0 +1,87 @@
+use super::error::{PatternMatch, ValidationError};
+use std::{sync::Arc, time::Duration};
+
+/// Measurements of one validation run, passed to a [`MetricsSink`]
+#[derive(Debug, Clone, Copy)]
+pub struct ValidationMetrics<'a> {
+    /// Target type reported by the validator
+    pub validator: &'static str,
+    /// Label of the pipeline, if set
+    pub context: Option<&'a str>,
+    /// Length of the raw input in bytes
+    pub input_len: usize,
+    /// Time spent in the pipeline, including the validator
+    pub duration: Duration,
+    /// Validation outcome
+    pub outcome: Result<(), &'a ValidationError>,
+}
+
+impl ValidationMetrics<'_> {
+    /// Returns true if the input was accepted
+    pub fn is_accepted(&self) -> bool {
+        self.outcome.is_ok()
+    }
+
+    /// Returns the blocked patterns that caused a rejection
+    pub fn patterns(&self) -> &[PatternMatch] {
+        match self.outcome {
+            Err(ValidationError::BlockedPattern { matches, .. }) => matches,
+            _ => &[],
+        }
+    }
+}
+
+/// Receiver of measurements for every input processed by a [`Pipeline`](crate::Pipeline)
+///
+/// Implementations are called on the request path, so they should only
+/// update counters and hand heavier work elsewhere.
+///
+/// ```rust
+/// use huginn::metrics::{MetricsSink, ValidationMetrics};
+/// use huginn::{Pipeline, SecurityConfig, Validator, ValidationError};
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use std::sync::Arc;
+///
+/// #[derive(Default)]
+/// struct Rejections(AtomicU64);
+///
+/// impl MetricsSink for Rejections {
+///     fn record(&self, metrics: &ValidationMetrics<'_>) {
+///         if !metrics.is_accepted() {
+///             self.0.fetch_add(1, Ordering::Relaxed);
+///         }
+///     }
+/// }
+///
+/// struct Text;
+///
+/// impl Validator<String> for Text {
+///     fn validate(&self, input: &str) -> Result<String, ValidationError> {
+///         Ok(input.to_string())
+///     }
+///
+///     fn target_type(&self) -> &'static str {
+///         "text"
+///     }
+/// }
+///
+/// let rejections = Arc::new(Rejections::default());
+/// let pipeline = Pipeline::builder(SecurityConfig::default())
+///     .with_metrics(rejections.clone())
+///     .build();
+///
+/// let _ = pipeline.process("hello", &Text);
+/// let _ = pipeline.process("<script>", &Text);
+/// assert_eq!(rejections.0.load(Ordering::Relaxed), 1);
+/// ```
+pub trait MetricsSink: Send + Sync {
+    /// Records the measurements of one validation run
+    fn record(&self, metrics: &ValidationMetrics<'_>);
+}
+
+/// Lets the caller keep a handle to a sink shared with a pipeline
+impl<S: MetricsSink + ?Sized> MetricsSink for Arc<S> {
+    fn record(&self, metrics: &ValidationMetrics<'_>) {
+        (**self).record(metrics)
+    }
+}
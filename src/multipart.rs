@@ -0,0 +1,297 @@
+use super::{
+    config::SecurityConfig,
+    error::{ValidationError, ValidationErrors},
+    validation::{boxed_check, prepare_input, sanitize_and_validate, BoxedCheck, Validator},
+    validators::FilenameValidator,
+};
+use futures::{Stream, StreamExt};
+use std::{error::Error as StdError, fmt, fmt::Debug};
+
+/// Limits and rules applied to the parts of a multipart/form-data request
+///
+/// ```rust
+/// use futures::stream;
+/// use huginn::multipart::{validate_part, MultipartPolicy, PartContent};
+/// use huginn::SecurityConfig;
+/// use std::convert::Infallible;
+///
+/// # futures::executor::block_on(async {
+/// let policy = MultipartPolicy::new()
+///     .with_max_file_size(1024)
+///     .allow_content_type("image/png");
+/// let config = SecurityConfig::default();
+///
+/// let body = stream::iter([Ok::<_, Infallible>(&b"\x89PNG..."[..])]);
+/// let part = validate_part(&policy, &config, "avatar", Some("me.png"), Some("image/png"), body).await;
+/// assert!(part.is_valid());
+/// assert!(matches!(part.content, PartContent::File(_)));
+///
+/// let body = stream::iter([Ok::<_, Infallible>(&b"#!/bin/sh"[..])]);
+/// let part = validate_part(&policy, &config, "avatar", Some("../x.sh"), Some("text/x-sh"), body).await;
+/// assert_eq!(part.errors.for_field("filename").count(), 1);
+/// assert_eq!(part.errors.for_field("content_type").count(), 1);
+/// # });
+/// ```
+pub struct MultipartPolicy {
+    max_file_size: u64,
+    max_text_size: usize,
+    content_types: Vec<String>,
+    filenames: FilenameValidator,
+    fields: Vec<(String, BoxedCheck)>,
+}
+
+impl Default for MultipartPolicy {
+    fn default() -> Self {
+        Self {
+            max_file_size: 10 << 20,
+            max_text_size: 64 << 10,
+            content_types: Vec::new(),
+            filenames: FilenameValidator::new(),
+            fields: Vec::new(),
+        }
+    }
+}
+
+impl Debug for MultipartPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultipartPolicy")
+            .field("max_file_size", &self.max_file_size)
+            .field("max_text_size", &self.max_text_size)
+            .field("content_types", &self.content_types)
+            .field(
+                "fields",
+                &self.fields.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl MultipartPolicy {
+    /// Creates policy with 10 MiB files, 64 KiB text fields and any content type
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum size of file parts in bytes
+    pub fn with_max_file_size(mut self, max: u64) -> Self {
+        self.max_file_size = max;
+        self
+    }
+
+    /// Sets the maximum size of text parts in bytes
+    pub fn with_max_text_size(mut self, max: usize) -> Self {
+        self.max_text_size = max;
+        self
+    }
+
+    /// Allows a content type for file parts; once any is added, others are rejected
+    ///
+    /// Parameters such as `charset` are ignored when comparing. A type of the
+    /// form `image/*` allows every subtype.
+    pub fn allow_content_type(mut self, content_type: &str) -> Self {
+        self.content_types.push(content_type.to_ascii_lowercase());
+        self
+    }
+
+    /// Sets the validator applied to file names
+    pub fn with_filename_validator(mut self, validator: FilenameValidator) -> Self {
+        self.filenames = validator;
+        self
+    }
+
+    /// Validates the text part with the given field name
+    pub fn with_field<T, V>(mut self, name: &str, validator: V) -> Self
+    where
+        T: Debug + Send + Sync,
+        V: Validator<T> + 'static,
+    {
+        self.fields.push((name.to_string(), boxed_check(validator)));
+        self
+    }
+
+    fn allows_content_type(&self, content_type: &str) -> bool {
+        if self.content_types.is_empty() {
+            return true;
+        }
+        let essence = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        self.content_types
+            .iter()
+            .any(|allowed| match allowed.strip_suffix("/*") {
+                Some(top) => essence.split('/').next() == Some(top),
+                None => *allowed == essence,
+            })
+    }
+}
+
+/// Body of a validated part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartContent {
+    /// Text field contents after screening
+    Text(String),
+    /// File contents as received
+    File(Vec<u8>),
+    /// Body was not kept because the part failed validation
+    Discarded,
+}
+
+/// Outcome of validating one part
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    /// Form field name
+    pub name: String,
+    /// File name, for file parts
+    pub filename: Option<String>,
+    /// Declared content type
+    pub content_type: Option<String>,
+    /// Number of body bytes read
+    pub size: u64,
+    /// Part body
+    pub content: PartContent,
+    /// Failures keyed by `filename`, `content_type` or `body`
+    pub errors: ValidationErrors,
+}
+
+impl PartResult {
+    /// Returns true if the part passed every check
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Validates one part, reading its streamed body up to the configured limit
+///
+/// Parts with a file name are treated as files: the name and content type are
+/// validated and the body is kept as bytes. Other parts are text fields whose
+/// body must be UTF-8 and runs through the pipeline, using the validator
+/// registered for the field name if any. Reading stops at the first chunk
+/// beyond the size limit.
+pub async fn validate_part<S, B, E>(
+    policy: &MultipartPolicy,
+    config: &SecurityConfig,
+    name: &str,
+    filename: Option<&str>,
+    content_type: Option<&str>,
+    body: S,
+) -> PartResult
+where
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+    E: StdError + Send + Sync + 'static,
+{
+    let mut errors = ValidationErrors::new();
+    let is_file = filename.is_some();
+
+    if let Some(filename) = filename {
+        if let Err(error) = sanitize_and_validate(filename, &policy.filenames, config) {
+            errors.push_field("filename", error);
+        }
+        match content_type {
+            Some(content_type) if !policy.allows_content_type(content_type) => {
+                errors.push_field(
+                    "content_type",
+                    ValidationError::invalid_format("content type").with_hint(format!(
+                        "upload one of: {}",
+                        policy.content_types.join(", ")
+                    )),
+                );
+            }
+            None if !policy.content_types.is_empty() => {
+                errors.push_field(
+                    "content_type",
+                    ValidationError::invalid_format("content type")
+                        .with_hint("declare the content type of the file"),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    let limit = if is_file {
+        policy.max_file_size
+    } else {
+        policy.max_text_size as u64
+    };
+    let (data, size) = read_limited(body, limit, &mut errors).await;
+
+    let content = match data {
+        Some(_) if !errors.is_empty() => PartContent::Discarded,
+        Some(data) if is_file => PartContent::File(data),
+        Some(data) => match text_field(policy, config, name, data) {
+            Ok(text) => PartContent::Text(text),
+            Err(error) => {
+                errors.push_field("body", error);
+                PartContent::Discarded
+            }
+        },
+        None => PartContent::Discarded,
+    };
+
+    PartResult {
+        name: name.to_string(),
+        filename: filename.map(str::to_string),
+        content_type: content_type.map(str::to_string),
+        size,
+        content,
+        errors,
+    }
+}
+
+/// Collects the body, returning `None` when it is too large or the stream failed
+async fn read_limited<S, B, E>(
+    body: S,
+    limit: u64,
+    errors: &mut ValidationErrors,
+) -> (Option<Vec<u8>>, u64)
+where
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+    E: StdError + Send + Sync + 'static,
+{
+    let mut body = std::pin::pin!(body);
+    let mut data = Vec::new();
+    let mut size = 0u64;
+
+    while let Some(chunk) = body.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(err) => {
+                errors.push_field("body", ValidationError::external(err));
+                return (None, size);
+            }
+        };
+        let chunk = chunk.as_ref();
+        size += chunk.len() as u64;
+        if size > limit {
+            errors.push_field(
+                "body",
+                ValidationError::custom("Part exceeds the size limit")
+                    .with_hint(format!("send at most {} bytes", limit)),
+            );
+            return (None, size);
+        }
+        data.extend_from_slice(chunk);
+    }
+
+    (Some(data), size)
+}
+
+fn text_field(
+    policy: &MultipartPolicy,
+    config: &SecurityConfig,
+    name: &str,
+    data: Vec<u8>,
+) -> Result<String, ValidationError> {
+    let text = String::from_utf8(data).map_err(|_| {
+        ValidationError::invalid_format("text field").with_hint("send the field as UTF-8")
+    })?;
+
+    match policy.fields.iter().find(|(field, _)| field == name) {
+        Some((_, check)) => check(&text, config),
+        None => prepare_input(&text, config, &[]).map(|cleaned| cleaned.into_owned()),
+    }
+}
@@ -0,0 +1,54 @@
+/// Step in a path through a nested document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Segment {
+    Key(String),
+    Index(usize),
+    AnyIndex,
+}
+
+/// Parses a dotted path such as `items[*].sku`
+pub(crate) fn parse(path: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    for part in path.split('.').filter(|part| !part.is_empty()) {
+        let (key, mut indices) = part.split_at(part.find('[').unwrap_or(part.len()));
+        if !key.is_empty() {
+            segments.push(Segment::Key(key.to_string()));
+        }
+        while let Some(rest) = indices.strip_prefix('[') {
+            let (index, tail) = rest.split_once(']').unwrap_or((rest, ""));
+            segments.push(match index.parse() {
+                Ok(index) => Segment::Index(index),
+                Err(_) => Segment::AnyIndex,
+            });
+            indices = tail;
+        }
+    }
+    segments
+}
+
+/// Checks a concrete path against a pattern, where `[*]` matches any index
+pub(crate) fn matches(pattern: &[Segment], path: &[Segment]) -> bool {
+    pattern.len() == path.len()
+        && pattern.iter().zip(path).all(|(p, s)| match (p, s) {
+            (Segment::AnyIndex, Segment::Index(_)) => true,
+            (p, s) => p == s,
+        })
+}
+
+/// Renders a path in the notation used for error fields
+pub(crate) fn render(path: &[Segment]) -> String {
+    let mut rendered = String::new();
+    for segment in path {
+        match segment {
+            Segment::Key(key) => {
+                if !rendered.is_empty() {
+                    rendered.push('.');
+                }
+                rendered.push_str(key);
+            }
+            Segment::Index(index) => rendered.push_str(&format!("[{}]", index)),
+            Segment::AnyIndex => rendered.push_str("[*]"),
+        }
+    }
+    rendered
+}
@@ -0,0 +1,288 @@
+use super::{
+    config::PatternRule,
+    engine::{self, RegexSet},
+    error::{PatternError, PatternMatch},
+    rules::ThreatClass,
+};
+use lazy_static::lazy_static;
+#[cfg(feature = "regex")]
+use regex_syntax::ast::{parse::Parser, Ast, RepetitionKind, RepetitionRange};
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
+/// Compiled regular expression of the selected backend
+///
+/// This is `regex::Regex` with the `regex` feature and `regex_lite::Regex`
+/// with only `regex-lite`; both offer `is_match`, `find`, `as_str` and
+/// `replace_all`, so code using those works on either backend.
+pub use super::engine::Regex;
+
+/// Pattern syntax or compilation error of the selected backend
+pub use super::engine::RegexError;
+
+/// Maximum number of compiled patterns kept in the process-wide cache
+const PATTERN_CACHE_CAPACITY: usize = 1024;
+
+lazy_static! {
+    /// Compiled patterns shared by every configuration in the process
+    static ref PATTERN_CACHE: Mutex<HashMap<(String, RegexLimits), Regex>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Resource limits applied when compiling user-supplied patterns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RegexLimits {
+    /// Maximum length of the pattern source in bytes
+    pub max_pattern_len: usize,
+    /// Maximum size of the compiled program in bytes
+    pub size_limit: usize,
+    /// Maximum size of the lazy DFA cache in bytes, unused by `regex-lite`
+    pub dfa_size_limit: usize,
+    /// Maximum nesting depth of the pattern syntax
+    pub nest_limit: u32,
+    /// Rejects unbounded quantifiers nested inside other unbounded quantifiers
+    ///
+    /// Only enforced with the `regex` feature, since the check needs its
+    /// syntax tree. `regex-lite` matches in linear time as well, so nested
+    /// quantifiers cannot cause catastrophic backtracking on either backend.
+    pub reject_nested_quantifiers: bool,
+}
+
+impl Default for RegexLimits {
+    /// Creates limits suitable for patterns supplied at runtime
+    fn default() -> Self {
+        Self {
+            max_pattern_len: 1024,
+            size_limit: 1 << 20,
+            dfa_size_limit: 2 << 20,
+            nest_limit: 64,
+            reject_nested_quantifiers: true,
+        }
+    }
+}
+
+/// Compiles a pattern after checking it against the limits
+///
+/// Successfully compiled patterns are cached process-wide, so building many
+/// configurations from the same rules compiles each pattern only once.
+///
+/// ```rust
+/// use huginn::{PatternError, SecurityConfig};
+///
+/// let result = SecurityConfig::builder().add_blocked_pattern(r"^(\w+\s?)*$");
+/// # #[cfg(feature = "regex")]
+/// assert!(matches!(result, Err(PatternError::TooComplex { .. })));
+/// ```
+pub fn compile_pattern(pattern: &str, limits: &RegexLimits) -> Result<Regex, PatternError> {
+    let key = (pattern.to_string(), *limits);
+    if let Some(regex) = lock_cache().get(&key) {
+        return Ok(regex.clone());
+    }
+
+    let regex = compile_uncached(pattern, limits)?;
+    let mut cache = lock_cache();
+    if cache.len() < PATTERN_CACHE_CAPACITY {
+        cache.insert(key, regex.clone());
+    }
+    Ok(regex)
+}
+
+/// Locks the pattern cache, recovering from poisoning since entries are immutable
+fn lock_cache() -> std::sync::MutexGuard<'static, HashMap<(String, RegexLimits), Regex>> {
+    PATTERN_CACHE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Checks length, syntax and complexity without compiling the pattern
+pub(crate) fn check_pattern(pattern: &str, limits: &RegexLimits) -> Result<(), PatternError> {
+    if pattern.len() > limits.max_pattern_len {
+        return Err(PatternError::TooComplex {
+            pattern: pattern.to_string(),
+            reason: format!(
+                "pattern is {} bytes long, limit is {}",
+                pattern.len(),
+                limits.max_pattern_len
+            ),
+        });
+    }
+
+    engine::check_syntax(pattern, limits)?;
+
+    #[cfg(feature = "regex")]
+    if limits.reject_nested_quantifiers {
+        if let Ok(ast) = Parser::new().parse(pattern) {
+            if has_nested_unbounded(&ast, false) {
+                return Err(PatternError::TooComplex {
+                    pattern: pattern.to_string(),
+                    reason: "unbounded quantifier nested inside another unbounded quantifier"
+                        .to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn compile_uncached(pattern: &str, limits: &RegexLimits) -> Result<Regex, PatternError> {
+    check_pattern(pattern, limits)?;
+    engine::build(pattern, limits)
+}
+
+/// Detects constructs like `(a+)+` or `(\w*\s?)*` that describe exponentially many paths
+#[cfg(feature = "regex")]
+fn has_nested_unbounded(ast: &Ast, inside_unbounded: bool) -> bool {
+    match ast {
+        Ast::Repetition(rep) => {
+            let unbounded = match &rep.op.kind {
+                RepetitionKind::ZeroOrMore | RepetitionKind::OneOrMore => true,
+                RepetitionKind::Range(RepetitionRange::AtLeast(_)) => true,
+                RepetitionKind::ZeroOrOne | RepetitionKind::Range(_) => false,
+            };
+            if unbounded && inside_unbounded {
+                return true;
+            }
+            has_nested_unbounded(&rep.ast, inside_unbounded || unbounded)
+        }
+        Ast::Group(group) => has_nested_unbounded(&group.ast, inside_unbounded),
+        Ast::Alternation(alt) => alt
+            .asts
+            .iter()
+            .any(|ast| has_nested_unbounded(ast, inside_unbounded)),
+        Ast::Concat(concat) => concat
+            .asts
+            .iter()
+            .any(|ast| has_nested_unbounded(ast, inside_unbounded)),
+        _ => false,
+    }
+}
+
+/// Pattern source waiting for lazy compilation
+#[derive(Debug, Clone)]
+pub(crate) struct LazySource {
+    pub(crate) name: String,
+    pub(crate) pattern: String,
+    pub(crate) limits: RegexLimits,
+}
+
+/// Blocked patterns kept as source text until first use
+#[derive(Debug, Default)]
+pub(crate) struct LazyPatterns {
+    sources: Vec<LazySource>,
+    compiled: OnceLock<CompiledLazy>,
+}
+
+#[derive(Debug)]
+struct CompiledLazy {
+    /// Successfully compiled rules with their position among the lazy sources
+    rules: Vec<(usize, PatternRule)>,
+    set: Option<RegexSet>,
+    /// Positions of the sources that failed to compile, with the reason
+    failures: Vec<(usize, String)>,
+}
+
+impl LazyPatterns {
+    pub(crate) fn new(sources: Vec<LazySource>) -> Self {
+        Self {
+            sources,
+            compiled: OnceLock::new(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn sources(&self) -> &[LazySource] {
+        &self.sources
+    }
+
+    /// Compiles all sources, reporting the first one that failed
+    ///
+    /// Failed patterns match every input, so the blocklist fails closed.
+    pub(crate) fn warm_up(&self) -> Result<(), PatternError> {
+        match self.compiled().failures.first() {
+            Some((position, reason)) => Err(PatternError::TooComplex {
+                pattern: self.sources[*position].pattern.clone(),
+                reason: reason.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn is_match(&self, input: &str) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        let compiled = self.compiled();
+        if !compiled.failures.is_empty() {
+            return true;
+        }
+        match &compiled.set {
+            Some(set) => set.is_match(input),
+            None => compiled
+                .rules
+                .iter()
+                .any(|(_, rule)| rule.regex.is_match(input)),
+        }
+    }
+
+    /// Finds matches, numbering rules from `first_index`
+    pub(crate) fn find(&self, input: &str, first_index: usize) -> Vec<PatternMatch> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        let compiled = self.compiled();
+        let to_match = |(position, rule): &(usize, PatternRule)| {
+            rule.regex.find(input).map(|m| PatternMatch {
+                name: rule.name.clone(),
+                index: first_index + position,
+                span: m.range(),
+                threat: rule.threat(),
+            })
+        };
+        let mut matches: Vec<PatternMatch> = match &compiled.set {
+            Some(set) => set
+                .matches(input)
+                .into_iter()
+                .filter_map(|i| to_match(&compiled.rules[i]))
+                .collect(),
+            None => compiled.rules.iter().filter_map(to_match).collect(),
+        };
+        matches.extend(compiled.failures.iter().map(|(position, _)| PatternMatch {
+            name: self.sources[*position].name.clone(),
+            index: first_index + position,
+            span: 0..input.len(),
+            threat: ThreatClass::Custom,
+        }));
+        matches.sort_by_key(|m| m.index);
+        matches
+    }
+
+    fn compiled(&self) -> &CompiledLazy {
+        self.compiled.get_or_init(|| {
+            let mut rules = Vec::with_capacity(self.sources.len());
+            let mut failures = Vec::new();
+
+            for (position, source) in self.sources.iter().enumerate() {
+                match compile_pattern(&source.pattern, &source.limits) {
+                    Ok(regex) => rules.push((position, PatternRule::new(&source.name, regex))),
+                    Err(err) => failures.push((position, err.to_string())),
+                }
+            }
+
+            let set = engine::regex_set(rules.iter().map(|(_, rule)| rule.regex.as_str()));
+            CompiledLazy {
+                rules,
+                set,
+                failures,
+            }
+        })
+    }
+}
@@ -0,0 +1,575 @@
+pub use super::validation::PipelineHook;
+use super::{
+    audit::{AuditRecord, AuditSink},
+    config::SecurityConfig,
+    context::ValidationContext,
+    error::ValidationError,
+    formatter::{DefaultFormatter, MessageFormatter},
+    metrics::{MetricsSink, ValidationMetrics},
+    ratelimit::RateLimiter,
+    validation::{
+        run_pipeline, SanitizationReport, SanitizedInput, SanitizedInputDetailed, Validator,
+    },
+};
+use std::{fmt, fmt::Debug, sync::Arc, time::Instant};
+
+#[cfg(feature = "async")]
+use super::validation::{run_pipeline_async, AsyncOptions};
+#[cfg(feature = "async")]
+use std::time::Duration;
+
+/// Sanitization stage needing I/O, such as a lookup in a denylist kept in another service
+///
+/// Asynchronous sanitizers run in registration order on the screened input,
+/// after the `before_validate` hooks and right before the validator. They
+/// only run in asynchronous processing; synchronous processing of a
+/// pipeline with asynchronous sanitizers fails rather than skip them.
+///
+/// ```rust
+/// use huginn::{AsyncSanitizer, Pipeline, SecurityConfig, ValidationError, Validator};
+///
+/// struct Denylist;
+///
+/// #[async_trait::async_trait]
+/// impl AsyncSanitizer for Denylist {
+///     async fn sanitize(&self, input: &mut String) -> Result<(), ValidationError> {
+///         // A real implementation would query a database or cache here
+///         if input.eq_ignore_ascii_case("spam") {
+///             return Err(ValidationError::custom("input is denylisted"));
+///         }
+///         *input = input.trim().to_string();
+///         Ok(())
+///     }
+/// }
+///
+/// struct Echo;
+///
+/// impl Validator<String> for Echo {
+///     fn validate(&self, input: &str) -> Result<String, ValidationError> {
+///         Ok(input.to_string())
+///     }
+///
+///     fn target_type(&self) -> &'static str {
+///         "text"
+///     }
+/// }
+///
+/// # #[tokio::main] async fn main() {
+/// let pipeline = Pipeline::builder(SecurityConfig::default())
+///     .add_async_sanitizer(Denylist)
+///     .build();
+/// assert_eq!(pipeline.process_async(" hello ", &Echo).await.unwrap().cleaned, "hello");
+/// assert!(pipeline.process_async("SPAM", &Echo).await.is_err());
+/// assert!(pipeline.process("hello", &Echo).is_err());
+/// # }
+/// ```
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+pub trait AsyncSanitizer: Send + Sync {
+    /// Rewrites the screened input in place, or vetoes it by returning an error
+    async fn sanitize(&self, input: &mut String) -> Result<(), ValidationError>;
+}
+
+/// Details of an input rejected by a [`Pipeline`], passed to rejection callbacks
+#[derive(Debug, Clone, Copy)]
+pub struct RejectionEvent<'a> {
+    /// Raw input as received
+    pub input: &'a str,
+    /// Target type reported by the validator
+    pub validator: &'static str,
+    /// Label of the pipeline, if set
+    pub pipeline: Option<&'a str>,
+    /// Request details passed with the input
+    pub context: &'a ValidationContext,
+    /// Rejection error
+    pub error: &'a ValidationError,
+}
+
+impl RejectionEvent<'_> {
+    /// Returns the ids of the rules that rejected the input
+    pub fn rule_ids(&self) -> Vec<&str> {
+        self.error.rule_ids()
+    }
+}
+
+/// Callback invoked with every rejection
+type RejectionCallback = dyn Fn(&RejectionEvent<'_>) + Send + Sync;
+
+/// Validation pipeline bundling a security configuration with middleware hooks
+#[derive(Clone)]
+pub struct Pipeline {
+    config: SecurityConfig,
+    hooks: Arc<Vec<Arc<dyn PipelineHook>>>,
+    formatter: Arc<dyn MessageFormatter>,
+    metrics: Option<Arc<dyn MetricsSink>>,
+    audit: Option<Arc<dyn AuditSink>>,
+    context: Option<Arc<str>>,
+    on_rejection: Arc<Vec<Arc<RejectionCallback>>>,
+    warnings: Option<SecurityConfig>,
+    #[cfg(feature = "async")]
+    timeout: Option<Duration>,
+    rate_limit: Option<(Arc<RateLimiter>, Arc<str>)>,
+    #[cfg(feature = "async")]
+    async_sanitizers: Arc<Vec<Arc<dyn AsyncSanitizer>>>,
+}
+
+impl Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Pipeline");
+        debug
+            .field("config", &self.config)
+            .field("hooks", &self.hooks.len())
+            .field("metrics", &self.metrics.is_some())
+            .field("audit", &self.audit.is_some())
+            .field("context", &self.context)
+            .field("on_rejection", &self.on_rejection.len())
+            .field("warnings", &self.warnings.is_some())
+            .field("rate_limit", &self.rate_limit.as_ref().map(|(_, key)| key));
+        #[cfg(feature = "async")]
+        debug
+            .field("timeout", &self.timeout)
+            .field("async_sanitizers", &self.async_sanitizers.len());
+        debug.finish()
+    }
+}
+
+impl Pipeline {
+    /// Creates a new pipeline builder for the given configuration
+    pub fn builder(config: SecurityConfig) -> PipelineBuilder {
+        PipelineBuilder::new(config)
+    }
+
+    /// Returns the security configuration used by the pipeline
+    pub fn config(&self) -> &SecurityConfig {
+        &self.config
+    }
+
+    /// Returns the label identifying the pipeline in audit records and rejection events
+    pub fn context(&self) -> Option<&str> {
+        self.context.as_deref()
+    }
+
+    /// Renders an error using the configured message formatter
+    pub fn format_error(&self, error: &ValidationError) -> String {
+        self.formatter.format(error)
+    }
+
+    /// Processes input with synchronous validation
+    pub fn process<'a, T>(
+        &self,
+        input: &'a str,
+        validator: &impl Validator<T>,
+    ) -> Result<SanitizedInput<'a, T>, ValidationError>
+    where
+        T: Debug + Send + Sync,
+    {
+        self.process_with_context(input, validator, &ValidationContext::new())
+    }
+
+    /// Processes input with synchronous validation, passing request details to rejection callbacks
+    pub fn process_with_context<'a, T>(
+        &self,
+        input: &'a str,
+        validator: &impl Validator<T>,
+        context: &ValidationContext,
+    ) -> Result<SanitizedInput<'a, T>, ValidationError>
+    where
+        T: Debug + Send + Sync,
+    {
+        let started = Instant::now();
+        let result = self
+            .check_sync()
+            .and_then(|()| self.check_rate_limit(context))
+            .and_then(|()| run_pipeline(input, validator, &self.config, &self.hooks, None));
+        self.observe(input, validator.target_type(), context, started, &result);
+        result
+    }
+
+    /// Processes input with asynchronous validation
+    #[cfg(feature = "async")]
+    pub async fn process_async<'a, T>(
+        &self,
+        input: &'a str,
+        validator: &impl Validator<T>,
+    ) -> Result<SanitizedInput<'a, T>, ValidationError>
+    where
+        T: Debug + Send + Sync,
+    {
+        self.process_async_with_context(input, validator, &ValidationContext::new())
+            .await
+    }
+
+    /// Processes input with asynchronous validation, passing request details to rejection callbacks
+    ///
+    /// A cancellation token set on the context aborts the validator call
+    /// with [`ValidationError::Cancelled`].
+    #[cfg(feature = "async")]
+    pub async fn process_async_with_context<'a, T>(
+        &self,
+        input: &'a str,
+        validator: &impl Validator<T>,
+        context: &ValidationContext,
+    ) -> Result<SanitizedInput<'a, T>, ValidationError>
+    where
+        T: Debug + Send + Sync,
+    {
+        let started = Instant::now();
+        let result = match self.check_rate_limit(context) {
+            Ok(()) => {
+                let limits = AsyncOptions {
+                    timeout: self.timeout,
+                    cancel: context.cancellation(),
+                    sanitizers: &self.async_sanitizers,
+                };
+                run_pipeline_async(input, validator, &self.config, &self.hooks, None, limits).await
+            }
+            Err(error) => Err(error),
+        };
+        self.observe(input, validator.target_type(), context, started, &result);
+        result
+    }
+
+    /// Processes input with synchronous validation, reporting how the input was processed
+    ///
+    /// ```rust
+    /// use huginn::validators::FilenameValidator;
+    /// use huginn::{PatternCategory, Pipeline, SecurityConfig};
+    ///
+    /// let warnings = SecurityConfig::builder()
+    ///     .with_category(PatternCategory::TemplateInjection)
+    ///     .build();
+    /// let pipeline = Pipeline::builder(SecurityConfig::default())
+    ///     .with_warning_patterns(warnings)
+    ///     .build();
+    ///
+    /// let result = pipeline.process_detailed("{{name}}.txt", &FilenameValidator::new()).unwrap();
+    /// assert_eq!(result.input.cleaned, "{{name}}.txt");
+    /// assert_eq!(result.report.warnings[0].name, "ssti_double_brace");
+    /// ```
+    pub fn process_detailed<'a, T>(
+        &self,
+        input: &'a str,
+        validator: &impl Validator<T>,
+    ) -> Result<SanitizedInputDetailed<'a, T>, ValidationError>
+    where
+        T: Debug + Send + Sync,
+    {
+        self.process_detailed_with_context(input, validator, &ValidationContext::new())
+    }
+
+    /// Processes input with synchronous validation, reporting how the input was processed
+    /// and passing request details to the rate limiter and rejection callbacks
+    pub fn process_detailed_with_context<'a, T>(
+        &self,
+        input: &'a str,
+        validator: &impl Validator<T>,
+        context: &ValidationContext,
+    ) -> Result<SanitizedInputDetailed<'a, T>, ValidationError>
+    where
+        T: Debug + Send + Sync,
+    {
+        let started = Instant::now();
+        let mut report = SanitizationReport::default();
+        let detail = Some((&mut report, self.warnings.as_ref()));
+        let result = self
+            .check_sync()
+            .and_then(|()| self.check_rate_limit(context))
+            .and_then(|()| run_pipeline(input, validator, &self.config, &self.hooks, detail));
+        self.observe(input, validator.target_type(), context, started, &result);
+        report.duration = started.elapsed();
+        result.map(|input| SanitizedInputDetailed { input, report })
+    }
+
+    /// Processes input with asynchronous validation, reporting how the input was processed
+    #[cfg(feature = "async")]
+    pub async fn process_async_detailed<'a, T>(
+        &self,
+        input: &'a str,
+        validator: &impl Validator<T>,
+    ) -> Result<SanitizedInputDetailed<'a, T>, ValidationError>
+    where
+        T: Debug + Send + Sync,
+    {
+        self.process_async_detailed_with_context(input, validator, &ValidationContext::new())
+            .await
+    }
+
+    /// Processes input with asynchronous validation, reporting how the input was processed
+    /// and passing request details to the rate limiter and rejection callbacks
+    ///
+    /// A cancellation token set on the context aborts the validator call
+    /// with [`ValidationError::Cancelled`].
+    #[cfg(feature = "async")]
+    pub async fn process_async_detailed_with_context<'a, T>(
+        &self,
+        input: &'a str,
+        validator: &impl Validator<T>,
+        context: &ValidationContext,
+    ) -> Result<SanitizedInputDetailed<'a, T>, ValidationError>
+    where
+        T: Debug + Send + Sync,
+    {
+        let started = Instant::now();
+        let mut report = SanitizationReport::default();
+        let result = match self.check_rate_limit(context) {
+            Ok(()) => {
+                let detail = Some((&mut report, self.warnings.as_ref()));
+                let limits = AsyncOptions {
+                    timeout: self.timeout,
+                    cancel: context.cancellation(),
+                    sanitizers: &self.async_sanitizers,
+                };
+                run_pipeline_async(input, validator, &self.config, &self.hooks, detail, limits)
+                    .await
+            }
+            Err(error) => Err(error),
+        };
+        self.observe(input, validator.target_type(), context, started, &result);
+        report.duration = started.elapsed();
+        result.map(|input| SanitizedInputDetailed { input, report })
+    }
+
+    /// Fails synchronous processing, which cannot run asynchronous sanitizers
+    fn check_sync(&self) -> Result<(), ValidationError> {
+        #[cfg(feature = "async")]
+        if !self.async_sanitizers.is_empty() {
+            return Err(ValidationError::custom(
+                "pipeline has asynchronous sanitizers; process the input asynchronously",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Takes a token for the context's rate limiting key, if the context has one
+    fn check_rate_limit(&self, context: &ValidationContext) -> Result<(), ValidationError> {
+        match &self.rate_limit {
+            Some((limiter, field)) => context.get(field).map_or(Ok(()), |key| limiter.check(key)),
+            None => Ok(()),
+        }
+    }
+
+    /// Reports a finished run to the metrics and audit sinks and rejection callbacks
+    fn observe<T>(
+        &self,
+        input: &str,
+        validator: &'static str,
+        context: &ValidationContext,
+        started: Instant,
+        result: &Result<T, ValidationError>,
+    ) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record(&ValidationMetrics {
+                validator,
+                context: self.context.as_deref(),
+                input_len: input.len(),
+                duration: started.elapsed(),
+                outcome: result.as_ref().map(|_| ()),
+            });
+        }
+        let Err(error) = result else {
+            return;
+        };
+        if let Some(audit) = &self.audit {
+            audit.record(&AuditRecord::new(
+                input,
+                validator,
+                self.context.as_deref(),
+                error,
+            ));
+        }
+        if !self.on_rejection.is_empty() {
+            let event = RejectionEvent {
+                input,
+                validator,
+                pipeline: self.context.as_deref(),
+                context,
+                error,
+            };
+            for callback in self.on_rejection.iter() {
+                callback(&event);
+            }
+        }
+    }
+}
+
+/// Builder pattern for Pipeline
+pub struct PipelineBuilder {
+    config: SecurityConfig,
+    hooks: Vec<Arc<dyn PipelineHook>>,
+    formatter: Arc<dyn MessageFormatter>,
+    metrics: Option<Arc<dyn MetricsSink>>,
+    audit: Option<Arc<dyn AuditSink>>,
+    context: Option<Arc<str>>,
+    on_rejection: Vec<Arc<RejectionCallback>>,
+    warnings: Option<SecurityConfig>,
+    #[cfg(feature = "async")]
+    timeout: Option<Duration>,
+    rate_limit: Option<(Arc<RateLimiter>, Arc<str>)>,
+    #[cfg(feature = "async")]
+    async_sanitizers: Vec<Arc<dyn AsyncSanitizer>>,
+}
+
+impl PipelineBuilder {
+    /// Creates new builder without hooks
+    pub fn new(config: SecurityConfig) -> Self {
+        Self {
+            config,
+            hooks: Vec::new(),
+            formatter: Arc::new(DefaultFormatter),
+            metrics: None,
+            audit: None,
+            context: None,
+            on_rejection: Vec::new(),
+            warnings: None,
+            #[cfg(feature = "async")]
+            timeout: None,
+            rate_limit: None,
+            #[cfg(feature = "async")]
+            async_sanitizers: Vec::new(),
+        }
+    }
+
+    /// Adds a hook; hooks run in registration order
+    pub fn add_hook(mut self, hook: impl PipelineHook + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Adds an asynchronous sanitizer; sanitizers run in registration order
+    #[cfg(feature = "async")]
+    pub fn add_async_sanitizer(mut self, sanitizer: impl AsyncSanitizer + 'static) -> Self {
+        self.async_sanitizers.push(Arc::new(sanitizer));
+        self
+    }
+
+    /// Sets the formatter used by [`Pipeline::format_error`]
+    pub fn with_formatter(mut self, formatter: impl MessageFormatter + 'static) -> Self {
+        self.formatter = Arc::new(formatter);
+        self
+    }
+
+    /// Sets the sink receiving measurements of every processed input
+    pub fn with_metrics(mut self, metrics: impl MetricsSink + 'static) -> Self {
+        self.metrics = Some(Arc::new(metrics));
+        self
+    }
+
+    /// Sets the sink receiving a record of every rejected input
+    pub fn with_audit(mut self, audit: impl AuditSink + 'static) -> Self {
+        self.audit = Some(Arc::new(audit));
+        self
+    }
+
+    /// Sets the label identifying the pipeline in audit records and rejection events, such as a route
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        self.context = Some(context.into().into());
+        self
+    }
+
+    /// Adds a callback invoked with every rejected input, in registration order
+    ///
+    /// Callbacks run on the request path after the audit sink, so they
+    /// should hand slow work such as notifications elsewhere.
+    ///
+    /// ```rust
+    /// use huginn::validators::FilenameValidator;
+    /// use huginn::{Pipeline, SecurityConfig, ValidationContext};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let banned = Arc::new(Mutex::new(Vec::new()));
+    /// let pipeline = Pipeline::builder(SecurityConfig::default())
+    ///     .on_rejection({
+    ///         let banned = Arc::clone(&banned);
+    ///         move |event| {
+    ///             if event.rule_ids().contains(&"path_traversal") {
+    ///                 if let Some(ip) = event.context.get("ip") {
+    ///                     banned.lock().unwrap().push(ip.to_string());
+    ///                 }
+    ///             }
+    ///         }
+    ///     })
+    ///     .build();
+    ///
+    /// let context = ValidationContext::new().with("ip", "203.0.113.7");
+    /// let validator = FilenameValidator::new();
+    /// assert!(pipeline.process_with_context("../../etc/passwd", &validator, &context).is_err());
+    /// assert_eq!(*banned.lock().unwrap(), ["203.0.113.7"]);
+    /// ```
+    pub fn on_rejection<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&RejectionEvent<'_>) + Send + Sync + 'static,
+    {
+        self.on_rejection.push(Arc::new(callback));
+        self
+    }
+
+    /// Sets patterns reported as warnings by [`Pipeline::process_detailed`] instead of rejecting
+    ///
+    /// Only the blocked patterns of the configuration are used; its forbidden
+    /// characters are ignored.
+    pub fn with_warning_patterns(mut self, config: SecurityConfig) -> Self {
+        self.warnings = Some(config);
+        self
+    }
+
+    /// Bounds asynchronous validation, turning slow validators into [`ValidationError::Timeout`]
+    ///
+    /// Only the validator's `validate_async` is timed; asynchronous
+    /// processing must then run within a Tokio runtime.
+    #[cfg(feature = "async")]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Rate limits inputs by the value of a [`ValidationContext`] field, such as a chat id
+    ///
+    /// Inputs processed with a context holding the field take a token from
+    /// the limiter before they are screened, and are rejected with
+    /// [`ValidationError::RateLimited`] once the key runs out. Inputs without
+    /// the field are not limited. Pass an `Arc` to share one limiter
+    /// between pipelines.
+    ///
+    /// ```rust
+    /// use huginn::ratelimit::RateLimiter;
+    /// use huginn::validators::FilenameValidator;
+    /// use huginn::{Pipeline, SecurityConfig, ValidationContext, ValidationError};
+    /// use std::time::Duration;
+    ///
+    /// let pipeline = Pipeline::builder(SecurityConfig::default())
+    ///     .with_rate_limit(RateLimiter::new(1, Duration::from_secs(10)), "chat")
+    ///     .build();
+    ///
+    /// let chat = ValidationContext::new().with("chat", "42");
+    /// let validator = FilenameValidator::new();
+    /// assert!(pipeline.process_with_context("a.txt", &validator, &chat).is_ok());
+    /// assert!(matches!(
+    ///     pipeline.process_with_context("b.txt", &validator, &chat),
+    ///     Err(ValidationError::RateLimited { .. })
+    /// ));
+    /// assert!(pipeline.process_detailed_with_context("c.txt", &validator, &chat).is_err());
+    /// ```
+    pub fn with_rate_limit(mut self, limiter: impl Into<Arc<RateLimiter>>, field: &str) -> Self {
+        self.rate_limit = Some((limiter.into(), field.into()));
+        self
+    }
+
+    /// Finalizes the pipeline
+    pub fn build(self) -> Pipeline {
+        Pipeline {
+            config: self.config,
+            hooks: Arc::new(self.hooks),
+            formatter: self.formatter,
+            metrics: self.metrics,
+            audit: self.audit,
+            context: self.context,
+            on_rejection: Arc::new(self.on_rejection),
+            warnings: self.warnings,
+            #[cfg(feature = "async")]
+            timeout: self.timeout,
+            rate_limit: self.rate_limit,
+            #[cfg(feature = "async")]
+            async_sanitizers: Arc::new(self.async_sanitizers),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use super::metrics::{MetricsSink, ValidationMetrics};
+use ::prometheus::{HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry};
+
+/// Metrics sink exporting Prometheus counters and a latency histogram
+///
+/// Registers three metrics:
+///
+/// - `huginn_validations_total`, labeled by `validator` and `outcome`
+///   (`accepted` or the rejection error code)
+/// - `huginn_pattern_hits_total`, labeled by `pattern`
+/// - `huginn_validation_duration_seconds`, labeled by `validator`
+///
+/// ```rust
+/// use huginn::prometheus::PrometheusMetrics;
+/// use huginn::validators::FilenameValidator;
+/// use huginn::{Pipeline, SecurityConfig};
+/// use prometheus::Registry;
+///
+/// let registry = Registry::new();
+/// let pipeline = Pipeline::builder(SecurityConfig::default())
+///     .with_metrics(PrometheusMetrics::new(&registry)?)
+///     .build();
+///
+/// let _ = pipeline.process("../../etc/passwd", &FilenameValidator::new());
+/// let families = registry.gather();
+/// assert!(families.iter().any(|family| family.get_name() == "huginn_pattern_hits_total"));
+/// # Ok::<(), prometheus::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrometheusMetrics {
+    validations: IntCounterVec,
+    pattern_hits: IntCounterVec,
+    duration: HistogramVec,
+}
+
+impl PrometheusMetrics {
+    /// Creates the metrics and registers them with the registry
+    pub fn new(registry: &Registry) -> Result<Self, ::prometheus::Error> {
+        let validations = IntCounterVec::new(
+            Opts::new("huginn_validations_total", "Inputs validated by outcome"),
+            &["validator", "outcome"],
+        )?;
+        let pattern_hits = IntCounterVec::new(
+            Opts::new(
+                "huginn_pattern_hits_total",
+                "Rejections by matching blocked pattern",
+            ),
+            &["pattern"],
+        )?;
+        let duration = HistogramVec::new(
+            HistogramOpts::new(
+                "huginn_validation_duration_seconds",
+                "Time spent validating one input",
+            )
+            .buckets(vec![
+                0.000_01, 0.000_05, 0.000_1, 0.000_5, 0.001, 0.005, 0.01, 0.05, 0.1,
+            ]),
+            &["validator"],
+        )?;
+
+        registry.register(Box::new(validations.clone()))?;
+        registry.register(Box::new(pattern_hits.clone()))?;
+        registry.register(Box::new(duration.clone()))?;
+
+        Ok(Self {
+            validations,
+            pattern_hits,
+            duration,
+        })
+    }
+}
+
+impl MetricsSink for PrometheusMetrics {
+    fn record(&self, metrics: &ValidationMetrics<'_>) {
+        let outcome = match metrics.outcome {
+            Ok(()) => "accepted",
+            Err(error) => error.code(),
+        };
+        self.validations
+            .with_label_values(&[metrics.validator, outcome])
+            .inc();
+        for pattern in metrics.patterns() {
+            self.pattern_hits
+                .with_label_values(&[pattern.name.as_str()])
+                .inc();
+        }
+        self.duration
+            .with_label_values(&[metrics.validator])
+            .observe(metrics.duration.as_secs_f64());
+    }
+}
@@ -0,0 +1,244 @@
+use super::{
+    config::SecurityConfig,
+    error::{ValidationError, ValidationErrors},
+    path::{self, Segment},
+    validation::{boxed_check, prepare_input, BoxedCheck, Validator},
+};
+use ::prost_reflect::{DynamicMessage, MapKey, ReflectMessage, Value};
+use std::{fmt, fmt::Debug};
+
+struct PathRule {
+    pattern: Vec<Segment>,
+    source: String,
+    check: BoxedCheck,
+}
+
+/// Validators applied to fields of decoded protobuf messages
+///
+/// Paths use protobuf field names joined with `.`, with `[n]` for repeated
+/// fields and the key for map entries. In rule paths, `[*]` matches any
+/// index.
+///
+/// ```rust
+/// use huginn::prost::{validate_message, ProtoSchema};
+/// use huginn::validators::FilenameValidator;
+/// use huginn::SecurityConfig;
+/// use prost_types::{value::Kind, Struct, Value};
+///
+/// let config = SecurityConfig::default();
+/// let schema = ProtoSchema::new().with_path("fields.file.string_value", FilenameValidator::new());
+///
+/// let string = |text: &str| Value { kind: Some(Kind::StringValue(text.to_string())) };
+/// let mut message = Struct::default();
+/// message.fields.insert("title".to_string(), string("Quarterly report"));
+/// message.fields.insert("file".to_string(), string("report.pdf"));
+/// assert!(validate_message(&message, &config, &schema).is_ok());
+///
+/// message.fields.insert("title".to_string(), string("<script>alert(1)</script>"));
+/// message.fields.insert("file".to_string(), string("../../etc/passwd"));
+/// let errors = validate_message(&message, &config, &schema).unwrap_err();
+/// assert_eq!(errors.for_field("fields.title.string_value").count(), 1);
+/// assert_eq!(errors.for_field("fields.file.string_value").count(), 1);
+/// ```
+pub struct ProtoSchema {
+    rules: Vec<PathRule>,
+    max_depth: usize,
+}
+
+impl Default for ProtoSchema {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            max_depth: 64,
+        }
+    }
+}
+
+impl Debug for ProtoSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProtoSchema")
+            .field(
+                "paths",
+                &self
+                    .rules
+                    .iter()
+                    .map(|rule| &rule.source)
+                    .collect::<Vec<_>>(),
+            )
+            .field("max_depth", &self.max_depth)
+            .finish()
+    }
+}
+
+impl ProtoSchema {
+    /// Creates new schema that only screens string fields and map keys
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validates the string or bytes field at a path with the given validator
+    ///
+    /// Bytes fields are only checked when a path targets them and must then
+    /// hold UTF-8 text.
+    pub fn with_path<T, V>(mut self, path: &str, validator: V) -> Self
+    where
+        T: Debug + Send + Sync,
+        V: Validator<T> + 'static,
+    {
+        self.rules.push(PathRule {
+            pattern: path::parse(path),
+            source: path.to_string(),
+            check: boxed_check(validator),
+        });
+        self
+    }
+
+    /// Sets the maximum nesting depth of messages (64 by default)
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+}
+
+/// Validates a decoded protobuf message through reflection
+///
+/// Works with any message implementing [`ReflectMessage`], such as types
+/// generated with `prost-reflect-build` or derived with its `ReflectMessage`
+/// macro. Every string field and string map key runs through the pipeline
+/// stages that precede the validator, and fields at paths registered in the
+/// schema are validated. Only fields that are set are visited. All failures
+/// are collected, keyed by the path of the offending field.
+pub fn validate_message<M: ReflectMessage>(
+    message: &M,
+    config: &SecurityConfig,
+    schema: &ProtoSchema,
+) -> Result<(), ValidationErrors> {
+    let mut walker = Walker {
+        config,
+        schema,
+        path: Vec::new(),
+        errors: ValidationErrors::new(),
+    };
+    walker.message(&message.transcode_to_dynamic(), 0);
+
+    if walker.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(walker.errors)
+    }
+}
+
+struct Walker<'a> {
+    config: &'a SecurityConfig,
+    schema: &'a ProtoSchema,
+    path: Vec<Segment>,
+    errors: ValidationErrors,
+}
+
+impl Walker<'_> {
+    fn message(&mut self, message: &DynamicMessage, depth: usize) {
+        if depth >= self.schema.max_depth {
+            self.error(
+                ValidationError::custom(format!(
+                    "Message nesting exceeds {} levels",
+                    self.schema.max_depth
+                ))
+                .with_hint("flatten the message"),
+            );
+            return;
+        }
+        for (field, value) in message.fields() {
+            self.path.push(Segment::Key(field.name().to_string()));
+            self.value(value, depth);
+            self.path.pop();
+        }
+    }
+
+    fn value(&mut self, value: &Value, depth: usize) {
+        match value {
+            Value::String(text) => {
+                let validated = self.check_rules(text);
+                self.screen(text, !validated);
+            }
+            Value::Bytes(bytes) => {
+                if !self.has_rule() {
+                    return;
+                }
+                match std::str::from_utf8(bytes) {
+                    Ok(text) => {
+                        self.check_rules(text);
+                    }
+                    Err(_) => self.error(
+                        ValidationError::invalid_format("bytes field")
+                            .with_hint("send the field as UTF-8"),
+                    ),
+                }
+            }
+            Value::Message(message) => self.message(message, depth + 1),
+            Value::List(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    self.path.push(Segment::Index(index));
+                    self.value(item, depth);
+                    self.path.pop();
+                }
+            }
+            Value::Map(entries) => {
+                let mut entries: Vec<_> = entries.iter().collect();
+                entries.sort_by_key(|(key, _)| *key);
+                for (key, item) in entries {
+                    let segment = match key {
+                        MapKey::String(key) => key.clone(),
+                        MapKey::Bool(key) => key.to_string(),
+                        MapKey::I32(key) => key.to_string(),
+                        MapKey::I64(key) => key.to_string(),
+                        MapKey::U32(key) => key.to_string(),
+                        MapKey::U64(key) => key.to_string(),
+                    };
+                    self.path.push(Segment::Key(segment));
+                    if let MapKey::String(key) = key {
+                        self.screen(key, true);
+                    }
+                    self.value(item, depth);
+                    self.path.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn has_rule(&self) -> bool {
+        self.schema
+            .rules
+            .iter()
+            .any(|rule| path::matches(&rule.pattern, &self.path))
+    }
+
+    /// Runs the validators registered for the current path, returning whether any applied
+    fn check_rules(&mut self, text: &str) -> bool {
+        let schema = self.schema;
+        let mut applied = false;
+        for rule in schema.rules.iter() {
+            if path::matches(&rule.pattern, &self.path) {
+                applied = true;
+                if let Err(error) = (rule.check)(text, self.config) {
+                    self.error(error);
+                }
+            }
+        }
+        applied
+    }
+
+    /// Screens text, reporting failures unless a path validator already did
+    fn screen(&mut self, text: &str, report: bool) {
+        if let Err(error) = prepare_input(text, self.config, &[]) {
+            if report {
+                self.error(error);
+            }
+        }
+    }
+
+    fn error(&mut self, error: ValidationError) {
+        let path = path::render(&self.path);
+        self.errors.push_field(path, error);
+    }
+}
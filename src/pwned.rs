@@ -0,0 +1,255 @@
+use super::{error::ValidationError, validation::Validator};
+use reqwest::{Client, StatusCode};
+use sha1::{Digest, Sha1};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Range endpoint of the Have I Been Pwned password API
+const DEFAULT_URL: &str = "https://api.pwnedpasswords.com/range/";
+
+/// Number of hex digits of the SHA-1 hash sent to the API
+const PREFIX_LEN: usize = 5;
+
+/// Breach counts by hash suffix for one prefix
+type Range = Arc<HashMap<String, u64>>;
+
+/// Cached ranges by hash prefix, with their expiry
+type Cache = HashMap<String, (Range, Instant)>;
+
+/// Validator rejecting passwords that appear in known data breaches
+///
+/// The password is hashed with SHA-1 and only the first five hex digits of
+/// the hash are sent to the [Have I Been Pwned] range API (k-anonymity);
+/// the response lists every breached hash with that prefix and the match
+/// happens locally. Requests ask for padded responses so their size does
+/// not reveal the prefix. Ranges are cached, and clones share the cache.
+///
+/// A local validator, such as a strength check, can run first with
+/// [`with_local`](Self::with_local); its output is what gets looked up,
+/// and no request is made for input it rejects. Requests that fail or time
+/// out end in [`ValidationError::Transient`], [`ValidationError::Timeout`]
+/// or [`ValidationError::External`], so callers decide whether to fail
+/// open. Only asynchronous validation queries the API; synchronous
+/// validation fails.
+///
+/// The default configuration forbids characters such as `<`, `&` and `'`;
+/// pipelines receiving passwords usually need a configuration allowing them.
+///
+/// [Have I Been Pwned]: https://haveibeenpwned.com/API/v3#PwnedPasswords
+///
+/// ```rust,no_run
+/// use huginn::pwned::PwnedPasswordValidator;
+/// use huginn::{sanitize_and_validate_async, SecurityConfig, ValidationError};
+/// use std::time::Duration;
+///
+/// # async fn check() -> Result<(), ValidationError> {
+/// let validator = PwnedPasswordValidator::new()
+///     .with_timeout(Duration::from_millis(800))
+///     .with_threshold(3);
+///
+/// let config = SecurityConfig::default();
+/// let result = sanitize_and_validate_async("password123", &validator, &config).await;
+/// assert!(result.is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct PwnedPasswordValidator {
+    client: Client,
+    url: String,
+    timeout: Duration,
+    threshold: u64,
+    ttl: Duration,
+    capacity: usize,
+    cache: Arc<Mutex<Cache>>,
+    local: Option<Arc<dyn Validator<String>>>,
+}
+
+impl fmt::Debug for PwnedPasswordValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PwnedPasswordValidator")
+            .field("url", &self.url)
+            .field("timeout", &self.timeout)
+            .field("threshold", &self.threshold)
+            .field("ttl", &self.ttl)
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for PwnedPasswordValidator {
+    fn default() -> Self {
+        Self {
+            client: Client::new(),
+            url: DEFAULT_URL.to_string(),
+            timeout: Duration::from_secs(2),
+            threshold: 1,
+            ttl: Duration::from_secs(3600),
+            capacity: 100,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            local: None,
+        }
+    }
+}
+
+impl PwnedPasswordValidator {
+    /// Creates validator with a 2 second timeout and ranges cached for an hour
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses the given client, for example one with a proxy
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Queries a mirror of the range API; the hash prefix is appended to the URL
+    pub fn with_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Sets the time allowed for each request
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets how many breaches a password must appear in to be rejected
+    pub fn with_threshold(mut self, threshold: u64) -> Self {
+        self.threshold = threshold.max(1);
+        self
+    }
+
+    /// Sets how long ranges are cached; zero disables caching
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets the number of cached ranges kept before expired ones are dropped
+    ///
+    /// A padded range holds about a thousand hashes, some 80 KiB in memory.
+    pub fn with_cache_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Runs a local validator, such as a strength check, before the lookup
+    pub fn with_local<V>(mut self, validator: V) -> Self
+    where
+        V: Validator<String> + 'static,
+    {
+        self.local = Some(Arc::new(validator));
+        self
+    }
+
+    /// Returns how many breaches the password appears in
+    pub async fn breach_count(&self, password: &str) -> Result<u64, ValidationError> {
+        let hash = Sha1::digest(password.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect::<String>();
+        let (prefix, suffix) = hash.split_at(PREFIX_LEN);
+        let range = self.range(prefix).await?;
+        Ok(range.get(suffix).copied().unwrap_or(0))
+    }
+
+    /// Returns the cached range for the prefix, fetching it if missing or expired
+    async fn range(&self, prefix: &str) -> Result<Range, ValidationError> {
+        let now = Instant::now();
+        if let Some((range, expires)) = self.lock().get(prefix) {
+            if *expires > now {
+                return Ok(Arc::clone(range));
+            }
+        }
+
+        let range = Arc::new(self.fetch(prefix).await?);
+        if !self.ttl.is_zero() {
+            let mut cache = self.lock();
+            if cache.len() >= self.capacity {
+                cache.retain(|_, (_, expires)| *expires > now);
+            }
+            if cache.len() < self.capacity {
+                cache.insert(prefix.to_string(), (Arc::clone(&range), now + self.ttl));
+            }
+        }
+        Ok(range)
+    }
+
+    async fn fetch(&self, prefix: &str) -> Result<HashMap<String, u64>, ValidationError> {
+        let response = self
+            .client
+            .get(format!("{}{}", self.url, prefix))
+            .header("Add-Padding", "true")
+            .timeout(self.timeout)
+            .send()
+            .await
+            .map_err(|err| self.map_error(err))?;
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+            return Err(ValidationError::transient(format!(
+                "breached password lookup answered with status {}",
+                status.as_u16()
+            )));
+        }
+        if !status.is_success() {
+            return Err(ValidationError::custom(format!(
+                "breached password lookup failed with status {}",
+                status.as_u16()
+            )));
+        }
+        let body = response.text().await.map_err(|err| self.map_error(err))?;
+        // Lines read `SUFFIX:COUNT`; padding entries have a count of zero
+        Ok(body
+            .lines()
+            .filter_map(|line| {
+                let (suffix, count) = line.trim().split_once(':')?;
+                let count = count.parse().ok().filter(|count| *count > 0)?;
+                Some((suffix.to_ascii_uppercase(), count))
+            })
+            .collect())
+    }
+
+    fn map_error(&self, err: reqwest::Error) -> ValidationError {
+        if err.is_timeout() {
+            ValidationError::timeout(self.timeout)
+        } else {
+            ValidationError::external(err)
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Cache> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[async_trait::async_trait]
+impl Validator<String> for PwnedPasswordValidator {
+    fn validate(&self, _input: &str) -> Result<String, ValidationError> {
+        Err(ValidationError::custom(
+            "breached password checks are only available asynchronously",
+        ))
+    }
+
+    async fn validate_async(&self, input: &str) -> Result<String, ValidationError> {
+        let password = match &self.local {
+            Some(local) => local.validate_async(input).await?,
+            None => input.to_string(),
+        };
+        if self.breach_count(&password).await? >= self.threshold {
+            return Err(ValidationError::invalid_format(self.target_type())
+                .with_hint("choose a password that has not appeared in a data breach"));
+        }
+        Ok(password)
+    }
+
+    fn target_type(&self) -> &'static str {
+        "password"
+    }
+}
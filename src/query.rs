@@ -0,0 +1,308 @@
+use super::{
+    config::SecurityConfig,
+    error::{ValidationError, ValidationErrors},
+    validation::{boxed_check, prepare_input, BoxedCheck, Validator},
+};
+use std::{borrow::Cow, fmt, fmt::Debug};
+use urlencoding::decode_binary;
+
+/// Splits a query string into decoded key/value pairs
+///
+/// Pairs keep their order and repeated keys are preserved. `+` is decoded as
+/// a space and invalid UTF-8 in escapes is replaced with U+FFFD. A leading
+/// `?` is ignored.
+///
+/// ```rust
+/// use huginn::query::parse_query;
+///
+/// let pairs = parse_query("?tag=a&tag=b&q=%3Cscript%3E+x");
+/// assert_eq!(pairs[0], ("tag".to_string(), "a".to_string()));
+/// assert_eq!(pairs[1], ("tag".to_string(), "b".to_string()));
+/// assert_eq!(pairs[2], ("q".to_string(), "<script> x".to_string()));
+/// ```
+pub fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .strip_prefix('?')
+        .unwrap_or(query)
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode_component(key), decode_component(value))
+        })
+        .collect()
+}
+
+/// Returns the first value of a key in a query string or form body, still percent-encoded
+///
+/// Keys are decoded for comparison; in the value only `+` becomes a space,
+/// so the pipeline's own decoding is the only one applied to it.
+#[cfg(any(feature = "axum", feature = "actix", feature = "warp"))]
+pub(crate) fn raw_value(query: &str, name: &str) -> Option<String> {
+    raw_pairs(query)
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value)
+}
+
+/// Splits a query string into decoded keys and still percent-encoded values
+fn raw_pairs(query: &str) -> impl Iterator<Item = (String, String)> + '_ {
+    query
+        .strip_prefix('?')
+        .unwrap_or(query)
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode_component(key), value.replace('+', " "))
+        })
+}
+
+fn decode_component(component: &str) -> String {
+    let component = if component.contains('+') {
+        Cow::Owned(component.replace('+', " "))
+    } else {
+        Cow::Borrowed(component)
+    };
+    String::from_utf8_lossy(&decode_binary(component.as_bytes())).into_owned()
+}
+
+/// Keys that reach `Object.prototype` when a JavaScript consumer merges the data
+pub(crate) const PROTOTYPE_KEYS: [&str; 3] = ["__proto__", "constructor", "prototype"];
+
+/// Returns the first denied segment of a possibly nested key
+///
+/// Keys are split on `.`, `[` and `]`, so `user[__proto__][admin]` and
+/// `user.__proto__.admin` are both caught.
+pub(crate) fn denied_segment<'a>(key: &'a str, denied: &[String]) -> Option<&'a str> {
+    key.split(['.', '[', ']'])
+        .find(|segment| denied.iter().any(|name| name == segment))
+}
+
+pub(crate) fn denied_key_error(segment: &str) -> ValidationError {
+    ValidationError::custom("Key is not allowed")
+        .with_hint(format!("remove '{}' from the key", segment))
+}
+
+struct FieldSpec {
+    name: String,
+    required: bool,
+    check: BoxedCheck,
+}
+
+/// Expected query parameters with the validator for each
+///
+/// ```rust
+/// use huginn::query::QuerySchema;
+/// use huginn::{SecurityConfig, ValidationError, Validator};
+///
+/// struct Page;
+///
+/// impl Validator<u32> for Page {
+///     fn validate(&self, input: &str) -> Result<u32, ValidationError> {
+///         input
+///             .parse()
+///             .map_err(|_| ValidationError::invalid_format(self.target_type()))
+///     }
+///
+///     fn target_type(&self) -> &'static str {
+///         "page"
+///     }
+/// }
+///
+/// let schema = QuerySchema::new().required("page", Page).deny_unknown();
+/// let config = SecurityConfig::default();
+///
+/// let params = schema.validate("page=2", &config).unwrap();
+/// assert_eq!(params.get("page"), Some("2"));
+///
+/// let errors = schema.validate("page=two&debug=1", &config).unwrap_err();
+/// assert_eq!(errors.for_field("page").count(), 1);
+/// assert_eq!(errors.for_field("debug").count(), 1);
+/// ```
+pub struct QuerySchema {
+    fields: Vec<FieldSpec>,
+    deny_unknown: bool,
+    denied_keys: Vec<String>,
+}
+
+impl Default for QuerySchema {
+    fn default() -> Self {
+        Self {
+            fields: Vec::new(),
+            deny_unknown: false,
+            denied_keys: PROTOTYPE_KEYS.iter().map(|key| key.to_string()).collect(),
+        }
+    }
+}
+
+impl Debug for QuerySchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QuerySchema")
+            .field(
+                "fields",
+                &self.fields.iter().map(|f| &f.name).collect::<Vec<_>>(),
+            )
+            .field("deny_unknown", &self.deny_unknown)
+            .field("denied_keys", &self.denied_keys)
+            .finish()
+    }
+}
+
+impl QuerySchema {
+    /// Creates new schema accepting any parameters except prototype pollution keys
+    ///
+    /// Parameter names containing `__proto__`, `constructor` or `prototype`
+    /// as a segment, such as `user[__proto__][admin]`, are rejected so the
+    /// data is safe to hand to JavaScript code that merges nested keys.
+    ///
+    /// ```rust
+    /// use huginn::query::QuerySchema;
+    /// use huginn::SecurityConfig;
+    ///
+    /// let config = SecurityConfig::default();
+    /// let errors = QuerySchema::new().validate("user[__proto__][admin]=1", &config).unwrap_err();
+    /// assert_eq!(errors.for_field("user[__proto__][admin]").count(), 1);
+    ///
+    /// let schema = QuerySchema::new().allow_key("constructor");
+    /// assert!(schema.validate("constructor=Ada", &config).is_ok());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a parameter that must be present at least once
+    pub fn required<T, V>(self, name: &str, validator: V) -> Self
+    where
+        T: Debug + Send + Sync,
+        V: Validator<T> + 'static,
+    {
+        self.add_field(name, true, validator)
+    }
+
+    /// Adds a parameter that may be absent
+    pub fn optional<T, V>(self, name: &str, validator: V) -> Self
+    where
+        T: Debug + Send + Sync,
+        V: Validator<T> + 'static,
+    {
+        self.add_field(name, false, validator)
+    }
+
+    /// Reports parameters not declared in the schema as errors
+    pub fn deny_unknown(mut self) -> Self {
+        self.deny_unknown = true;
+        self
+    }
+
+    /// Rejects parameter names containing the given key as a segment
+    pub fn deny_key(mut self, key: &str) -> Self {
+        if !self.denied_keys.iter().any(|denied| denied == key) {
+            self.denied_keys.push(key.to_string());
+        }
+        self
+    }
+
+    /// Stops rejecting the given key, including the built-in prototype keys
+    pub fn allow_key(mut self, key: &str) -> Self {
+        self.denied_keys.retain(|denied| denied != key);
+        self
+    }
+
+    fn add_field<T, V>(mut self, name: &str, required: bool, validator: V) -> Self
+    where
+        T: Debug + Send + Sync,
+        V: Validator<T> + 'static,
+    {
+        self.fields.push(FieldSpec {
+            name: name.to_string(),
+            required,
+            check: boxed_check(validator),
+        });
+        self
+    }
+
+    /// Parses a query string and validates every value, collecting all failures
+    ///
+    /// Repeated keys are validated value by value. Values are decoded once,
+    /// by the pipeline, and the returned parameters hold exactly the text
+    /// that was validated. Values of undeclared parameters are still
+    /// decoded and screened against the configuration. Errors are keyed by
+    /// parameter name.
+    pub fn validate(
+        &self,
+        query: &str,
+        config: &SecurityConfig,
+    ) -> Result<QueryParams, ValidationErrors> {
+        let raw: Vec<(String, String)> = raw_pairs(query).collect();
+        let mut pairs = Vec::with_capacity(raw.len());
+        let mut errors = ValidationErrors::new();
+
+        for (key, value) in &raw {
+            if let Some(segment) = denied_segment(key, &self.denied_keys) {
+                errors.push_field(key.as_str(), denied_key_error(segment));
+                continue;
+            }
+            let checked = match self.fields.iter().find(|field| field.name == *key) {
+                Some(field) => (field.check)(value, config),
+                None if self.deny_unknown => Err(ValidationError::custom("Unexpected parameter")
+                    .with_hint("remove the parameter from the query string")),
+                None => prepare_input(value, config, &[]).map(Cow::into_owned),
+            };
+            match checked {
+                Ok(cleaned) => pairs.push((key.clone(), cleaned)),
+                Err(error) => errors.push_field(key.as_str(), error),
+            }
+        }
+
+        for field in self.fields.iter().filter(|field| field.required) {
+            if !raw.iter().any(|(key, _)| *key == field.name) {
+                errors.push_field(
+                    field.name.as_str(),
+                    ValidationError::custom("Missing required parameter"),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(QueryParams { pairs })
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Decoded query parameters that passed validation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryParams {
+    pairs: Vec<(String, String)>,
+}
+
+impl QueryParams {
+    /// Returns the first value of a parameter
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns every value of a repeated parameter in order
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.pairs
+            .iter()
+            .filter(move |(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns all key/value pairs in order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Consumes the parameters, returning the key/value pairs
+    pub fn into_vec(self) -> Vec<(String, String)> {
+        self.pairs
+    }
+}
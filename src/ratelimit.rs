@@ -0,0 +1,148 @@
+use super::error::ValidationError;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    /// Position in the recency order, larger for more recently used buckets
+    stamp: u64,
+}
+
+/// Buckets with the order in which they were last used
+#[derive(Debug, Default)]
+struct Buckets {
+    by_key: HashMap<String, Bucket>,
+    by_stamp: BTreeMap<u64, String>,
+    next_stamp: u64,
+}
+
+/// Token bucket rate limiter keyed by user, chat, IP address or similar
+///
+/// Every key starts with `burst` tokens, spends one per input and regains
+/// them evenly over `period`. When the number of tracked keys reaches the
+/// limit, the least recently used key is forgotten to make room, so memory
+/// stays bounded even when a client rotates keys. The forgotten key is the
+/// one idle the longest, whose bucket has most likely refilled anyway.
+///
+/// Attach it to a pipeline with
+/// [`PipelineBuilder::with_rate_limit`](crate::pipeline::PipelineBuilder::with_rate_limit)
+/// to reject flooding clients before their input is screened or validated.
+///
+/// ```rust
+/// use huginn::ratelimit::RateLimiter;
+/// use std::time::Duration;
+///
+/// let limiter = RateLimiter::new(2, Duration::from_secs(60));
+/// assert!(limiter.check("chat:42").is_ok());
+/// assert!(limiter.check("chat:42").is_ok());
+/// assert!(limiter.check("chat:42").unwrap_err().is_retryable());
+/// assert!(limiter.check("chat:7").is_ok());
+///
+/// let small = RateLimiter::new(1, Duration::from_secs(60)).with_max_keys(2);
+/// for key in ["a", "b", "c", "d"] {
+///     small.check(key).unwrap();
+/// }
+/// assert_eq!(small.len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    burst: f64,
+    per_token: Duration,
+    max_keys: usize,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    /// Creates limiter allowing `burst` inputs per key, refilled over `period`
+    pub fn new(burst: u32, period: Duration) -> Self {
+        let burst = burst.max(1);
+        Self {
+            burst: f64::from(burst),
+            per_token: period / burst,
+            max_keys: 100_000,
+            buckets: Mutex::new(Buckets::default()),
+        }
+    }
+
+    /// Sets the number of keys tracked before the least recently used are forgotten
+    pub fn with_max_keys(mut self, max_keys: usize) -> Self {
+        self.max_keys = max_keys.max(1);
+        self
+    }
+
+    /// Takes a token for the key, failing with [`ValidationError::RateLimited`] if none is left
+    pub fn check(&self, key: &str) -> Result<(), ValidationError> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        let Buckets {
+            by_key,
+            by_stamp,
+            next_stamp,
+        } = &mut *buckets;
+
+        if !by_key.contains_key(key) {
+            while by_key.len() >= self.max_keys {
+                let Some((_, oldest)) = by_stamp.pop_first() else {
+                    break;
+                };
+                by_key.remove(&oldest);
+            }
+        }
+        let stamp = *next_stamp;
+        *next_stamp += 1;
+        let bucket = by_key.entry(key.to_string()).or_insert(Bucket {
+            tokens: self.burst,
+            updated: now,
+            stamp,
+        });
+        by_stamp.remove(&bucket.stamp);
+        by_stamp.insert(stamp, key.to_string());
+        *bucket = Bucket {
+            stamp,
+            ..self.refill(*bucket, now)
+        };
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let retry_after = self.per_token.mul_f64(1.0 - bucket.tokens);
+            Err(ValidationError::rate_limited(retry_after))
+        }
+    }
+
+    /// Returns the number of tracked keys
+    pub fn len(&self) -> usize {
+        self.buckets
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .by_key
+            .len()
+    }
+
+    /// Returns true if no keys are tracked
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds the tokens regained since the bucket was last updated
+    fn refill(&self, bucket: Bucket, now: Instant) -> Bucket {
+        let elapsed = now.duration_since(bucket.updated);
+        let regained = if self.per_token.is_zero() {
+            self.burst
+        } else {
+            elapsed.as_secs_f64() / self.per_token.as_secs_f64()
+        };
+        Bucket {
+            tokens: (bucket.tokens + regained).min(self.burst),
+            updated: now,
+            ..bucket
+        }
+    }
+}
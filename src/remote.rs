@@ -0,0 +1,224 @@
+use super::{error::ValidationError, validation::Validator};
+use reqwest::{Client, RequestBuilder, StatusCode};
+use std::{fmt, sync::Arc, time::Duration};
+
+type BuildRequest = dyn Fn(&Client, &str) -> RequestBuilder + Send + Sync;
+type MapResponse<T> = dyn Fn(&str, StatusCode, &str) -> Result<T, ValidationError> + Send + Sync;
+
+/// Validator delegating the decision to an HTTP endpoint, such as a token check in another service
+///
+/// By default the cleaned input is POSTed to the URL as a `text/plain`
+/// body. Any 2xx answer accepts the input unchanged, 429 and 5xx answers are
+/// treated as temporary and retried with exponential backoff, and other
+/// statuses reject the input. Requests that keep failing end in
+/// [`ValidationError::Transient`], [`ValidationError::Timeout`] or
+/// [`ValidationError::External`] for network errors, so callers can tell an
+/// unavailable service from rejected input.
+///
+/// Only asynchronous validation talks to the endpoint; synchronous
+/// validation fails, since it would block the runtime.
+///
+/// ```rust,no_run
+/// use huginn::remote::RemoteValidator;
+/// use huginn::{sanitize_and_validate_async, SecurityConfig, ValidationError};
+/// use std::time::Duration;
+///
+/// # async fn check() -> Result<(), ValidationError> {
+/// let validator = RemoteValidator::new("https://auth.internal.example/tokens/check")
+///     .with_timeout(Duration::from_millis(500))
+///     .with_retries(2)
+///     .with_request(|client, token| client.get("https://auth.internal.example/tokens").bearer_auth(token))
+///     .with_response(|_input, status, body| match status.as_u16() {
+///         200 => Ok(body.trim().to_string()),
+///         _ => Err(ValidationError::custom("unknown token")),
+///     });
+///
+/// let user_id = sanitize_and_validate_async("tok_123", &validator, &SecurityConfig::default()).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct RemoteValidator<T = String> {
+    client: Client,
+    url: String,
+    timeout: Duration,
+    retries: u32,
+    backoff: Duration,
+    request: Option<Arc<BuildRequest>>,
+    response: Arc<MapResponse<T>>,
+}
+
+impl<T> fmt::Debug for RemoteValidator<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteValidator")
+            .field("url", &self.url)
+            .field("timeout", &self.timeout)
+            .field("retries", &self.retries)
+            .field("backoff", &self.backoff)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Clone for RemoteValidator<T> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            url: self.url.clone(),
+            timeout: self.timeout,
+            retries: self.retries,
+            backoff: self.backoff,
+            request: self.request.clone(),
+            response: Arc::clone(&self.response),
+        }
+    }
+}
+
+impl RemoteValidator<String> {
+    /// Creates validator POSTing input to the URL, with a 2 second timeout and one retry
+    pub fn new<S: Into<String>>(url: S) -> Self {
+        Self {
+            client: Client::new(),
+            url: url.into(),
+            timeout: Duration::from_secs(2),
+            retries: 1,
+            backoff: Duration::from_millis(100),
+            request: None,
+            response: Arc::new(|input, status, _body| {
+                if status.is_success() {
+                    Ok(input.to_string())
+                } else {
+                    Err(ValidationError::custom(format!(
+                        "rejected by remote validator with status {}",
+                        status.as_u16()
+                    )))
+                }
+            }),
+        }
+    }
+}
+
+impl<T> RemoteValidator<T> {
+    /// Uses the given client, for example one with default headers or a proxy
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Sets the time allowed for each attempt
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets how many times a failed attempt is retried
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the delay before the first retry; it doubles for each further retry
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Replaces how the request is built from the cleaned input
+    pub fn with_request<F>(mut self, request: F) -> Self
+    where
+        F: Fn(&Client, &str) -> RequestBuilder + Send + Sync + 'static,
+    {
+        self.request = Some(Arc::new(request));
+        self
+    }
+
+    /// Replaces how a non-retryable answer is mapped to a result
+    ///
+    /// The mapper receives the cleaned input, the status and the body. 429
+    /// and 5xx answers never reach it; they are retried and end in
+    /// [`ValidationError::Transient`].
+    pub fn with_response<U, F>(self, response: F) -> RemoteValidator<U>
+    where
+        F: Fn(&str, StatusCode, &str) -> Result<U, ValidationError> + Send + Sync + 'static,
+    {
+        RemoteValidator {
+            client: self.client,
+            url: self.url,
+            timeout: self.timeout,
+            retries: self.retries,
+            backoff: self.backoff,
+            request: self.request,
+            response: Arc::new(response),
+        }
+    }
+
+    /// Sends one request, returning the status and body
+    async fn attempt(&self, input: &str) -> Result<(StatusCode, String), ValidationError> {
+        let request = match &self.request {
+            Some(build) => build(&self.client, input),
+            None => self
+                .client
+                .post(&self.url)
+                .header(reqwest::header::CONTENT_TYPE, "text/plain; charset=utf-8")
+                .body(input.to_string()),
+        };
+        let response = request
+            .timeout(self.timeout)
+            .send()
+            .await
+            .map_err(|err| self.map_error(err))?;
+        let status = response.status();
+        let body = response.text().await.map_err(|err| self.map_error(err))?;
+        Ok((status, body))
+    }
+
+    fn map_error(&self, err: reqwest::Error) -> ValidationError {
+        if err.is_timeout() {
+            ValidationError::timeout(self.timeout)
+        } else {
+            ValidationError::external(err)
+        }
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+#[async_trait::async_trait]
+impl<T: Send + Sync> Validator<T> for RemoteValidator<T> {
+    fn validate(&self, _input: &str) -> Result<T, ValidationError> {
+        Err(ValidationError::custom(
+            "remote validation is only available asynchronously",
+        ))
+    }
+
+    async fn validate_async(&self, input: &str) -> Result<T, ValidationError> {
+        let mut delay = self.backoff;
+        let mut attempt = 0;
+        loop {
+            let outcome = self.attempt(input).await;
+            let retry = match &outcome {
+                Ok((status, _)) => is_retryable_status(*status),
+                Err(_) => true,
+            };
+            if !retry || attempt >= self.retries {
+                return match outcome {
+                    Ok((status, _)) if is_retryable_status(status) => {
+                        Err(ValidationError::transient(format!(
+                            "remote validator answered with status {}",
+                            status.as_u16()
+                        )))
+                    }
+                    Ok((status, body)) => (self.response)(input, status, &body),
+                    Err(error) => Err(error),
+                };
+            }
+            attempt += 1;
+            tokio::time::sleep(delay).await;
+            delay = delay.saturating_mul(2);
+        }
+    }
+
+    fn target_type(&self) -> &'static str {
+        "remote check"
+    }
+}
@@ -0,0 +1,273 @@
+use super::{
+    config::SecurityConfig,
+    explain::{explain, ValidationReport},
+    rules::ThreatClass,
+    validation::Validator,
+};
+use std::{collections::HashMap, fmt::Debug};
+
+/// Response chosen for an input from its risk score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum RiskAction {
+    /// Accept the input
+    Allow,
+    /// Accept the input but flag it for review
+    Flag,
+    /// Reject the input
+    Block,
+}
+
+/// Thresholds mapping a risk score to an action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RiskPolicy {
+    flag_at: u8,
+    block_at: u8,
+}
+
+impl Default for RiskPolicy {
+    fn default() -> Self {
+        Self {
+            flag_at: 30,
+            block_at: 70,
+        }
+    }
+}
+
+impl RiskPolicy {
+    /// Creates policy flagging scores from 30 and blocking scores from 70
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the lowest score that is flagged
+    pub fn flag_at(mut self, score: u8) -> Self {
+        self.flag_at = score;
+        self
+    }
+
+    /// Sets the lowest score that is blocked
+    pub fn block_at(mut self, score: u8) -> Self {
+        self.block_at = score;
+        self
+    }
+
+    /// Returns the action for a score
+    pub fn action(&self, score: u8) -> RiskAction {
+        if score >= self.block_at {
+            RiskAction::Block
+        } else if score >= self.flag_at {
+            RiskAction::Flag
+        } else {
+            RiskAction::Allow
+        }
+    }
+}
+
+/// Contribution of one finding to a risk score
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RiskFactor {
+    /// Finding name: `dangerous_characters`, a threat class name, `validator`,
+    /// `entropy` or `length`
+    pub name: &'static str,
+    /// Points added to the score
+    pub points: u8,
+}
+
+/// Risk score of one input with the findings behind it
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RiskAssessment {
+    /// Score from 0 (benign) to 100
+    pub score: u8,
+    /// Action chosen by the policy
+    pub action: RiskAction,
+    /// Findings that contributed to the score
+    pub factors: Vec<RiskFactor>,
+}
+
+/// Combines pipeline findings into a 0–100 risk score
+///
+/// Each forbidden character, each distinct threat class among the matched
+/// patterns and a validator failure add points, as do high Shannon entropy
+/// (typical of encoded or obfuscated payloads) and input longer than
+/// expected. The sum is capped at 100 and mapped to an action by the
+/// [`RiskPolicy`], so callers can flag suspicious input for review instead
+/// of rejecting everything that trips a rule.
+///
+/// ```rust
+/// use huginn::risk::{RiskAction, RiskScorer};
+/// use huginn::validators::FilenameValidator;
+/// use huginn::SecurityConfig;
+///
+/// let scorer = RiskScorer::new();
+/// let config = SecurityConfig::default();
+/// let validator = FilenameValidator::new();
+///
+/// assert_eq!(scorer.assess("report.pdf", &validator, &config).action, RiskAction::Allow);
+/// assert_eq!(scorer.assess("<b>report</b>.pdf", &validator, &config).action, RiskAction::Flag);
+///
+/// let attack = scorer.assess("../../etc/passwd'; DROP TABLE users;--", &validator, &config);
+/// assert_eq!(attack.action, RiskAction::Block);
+/// assert!(attack.factors.iter().any(|f| f.name == "path_traversal"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RiskScorer {
+    char_points: u8,
+    max_char_points: u8,
+    threat_points: HashMap<ThreatClass, u8>,
+    validator_points: u8,
+    entropy_threshold: f64,
+    entropy_points: u8,
+    expected_len: usize,
+    length_points: u8,
+    policy: RiskPolicy,
+}
+
+impl Default for RiskScorer {
+    fn default() -> Self {
+        Self {
+            char_points: 10,
+            max_char_points: 40,
+            threat_points: HashMap::from([
+                (ThreatClass::SqlInjection, 60),
+                (ThreatClass::CommandInjection, 60),
+                (ThreatClass::Xss, 50),
+                (ThreatClass::PathTraversal, 50),
+                (ThreatClass::Injection, 40),
+                (ThreatClass::Custom, 40),
+                (ThreatClass::Encoding, 30),
+            ]),
+            validator_points: 20,
+            entropy_threshold: 4.5,
+            entropy_points: 15,
+            expected_len: 256,
+            length_points: 15,
+            policy: RiskPolicy::default(),
+        }
+    }
+}
+
+impl RiskScorer {
+    /// Creates scorer with default weights and policy
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the points per forbidden character and the cap on their total
+    pub fn with_char_points(mut self, points: u8, max: u8) -> Self {
+        self.char_points = points;
+        self.max_char_points = max;
+        self
+    }
+
+    /// Sets the points for patterns of a threat class
+    pub fn with_threat_points(mut self, threat: ThreatClass, points: u8) -> Self {
+        self.threat_points.insert(threat, points);
+        self
+    }
+
+    /// Sets the points for a validator failure
+    pub fn with_validator_points(mut self, points: u8) -> Self {
+        self.validator_points = points;
+        self
+    }
+
+    /// Sets the entropy in bits per character above which points are added
+    pub fn with_entropy(mut self, threshold: f64, points: u8) -> Self {
+        self.entropy_threshold = threshold;
+        self.entropy_points = points;
+        self
+    }
+
+    /// Sets the expected maximum input length in characters and the points for exceeding it
+    pub fn with_expected_len(mut self, len: usize, points: u8) -> Self {
+        self.expected_len = len;
+        self.length_points = points;
+        self
+    }
+
+    /// Sets the thresholds mapping scores to actions
+    pub fn with_policy(mut self, policy: RiskPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Runs the pipeline in dry-run mode and scores its findings
+    pub fn assess<T>(
+        &self,
+        input: &str,
+        validator: &impl Validator<T>,
+        config: &SecurityConfig,
+    ) -> RiskAssessment
+    where
+        T: Debug + Send + Sync,
+    {
+        self.assess_report(&explain(input, validator, config))
+    }
+
+    /// Scores the findings of a dry run
+    pub fn assess_report(&self, report: &ValidationReport) -> RiskAssessment {
+        let mut factors = Vec::new();
+        let mut add = |name: &'static str, points: u8| {
+            if points > 0 {
+                factors.push(RiskFactor { name, points });
+            }
+        };
+
+        let chars = report.removed.len().min(u8::MAX as usize) as u8;
+        add(
+            "dangerous_characters",
+            chars
+                .saturating_mul(self.char_points)
+                .min(self.max_char_points),
+        );
+        for threat in report.threats() {
+            add(
+                threat.name(),
+                self.threat_points.get(&threat).copied().unwrap_or(0),
+            );
+        }
+        if report.validator_error.is_some() {
+            add("validator", self.validator_points);
+        }
+
+        let len = report.cleaned.chars().count();
+        // Entropy of short inputs is too noisy to mean anything
+        if len >= 32 && shannon_entropy(&report.cleaned) > self.entropy_threshold {
+            add("entropy", self.entropy_points);
+        }
+        if len > self.expected_len {
+            add("length", self.length_points);
+        }
+
+        let score = factors
+            .iter()
+            .map(|f| f.points as u32)
+            .sum::<u32>()
+            .min(100) as u8;
+        RiskAssessment {
+            score,
+            action: self.policy.action(score),
+            factors,
+        }
+    }
+}
+
+/// Returns the Shannon entropy of the input in bits per character
+pub(crate) fn shannon_entropy(input: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    let mut total = 0;
+    for c in input.chars() {
+        *counts.entry(c).or_default() += 1;
+        total += 1;
+    }
+    counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
@@ -0,0 +1,58 @@
+//! Route parameters and form fields typed as [`SanitizedString`] or
+//! [`Sanitized<T>`] are screened with the default configuration before the
+//! handler runs. Rocket has already decoded them, so they are not decoded
+//! again.
+//!
+//! ```rust
+//! use huginn::{Sanitized, SanitizedString};
+//! use rocket::{get, routes};
+//!
+//! #[get("/users/<name>?<page>")]
+//! fn profile(name: SanitizedString, page: Option<Sanitized<u32>>) -> String {
+//!     format!("{} (page {})", name, page.map_or(1, |p| p.into_inner()))
+//! }
+//!
+//! let rocket = rocket::build().mount("/", routes![profile]);
+//! ```
+
+use super::{
+    error::ValidationError,
+    sanitized::{Sanitized, SanitizedString},
+};
+use ::rocket::{
+    form::{self, FromFormField, ValueField},
+    request::FromParam,
+};
+use std::str::FromStr;
+
+impl<'a> FromParam<'a> for SanitizedString {
+    type Error = ValidationError;
+
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        Self::from_decoded(param)
+    }
+}
+
+impl<'a, T: FromStr> FromParam<'a> for Sanitized<T> {
+    type Error = ValidationError;
+
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        Self::from_decoded(param)
+    }
+}
+
+impl<'v> FromFormField<'v> for SanitizedString {
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        Self::from_decoded(field.value).map_err(form_error)
+    }
+}
+
+impl<'v, T: FromStr + Send> FromFormField<'v> for Sanitized<T> {
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        Self::from_decoded(field.value).map_err(form_error)
+    }
+}
+
+fn form_error<'v>(error: ValidationError) -> form::Errors<'v> {
+    form::Error::validation(error.to_string()).into()
+}
@@ -0,0 +1,381 @@
+#[cfg(feature = "std")]
+use super::config::PatternRule;
+#[cfg(feature = "std")]
+use super::pattern::Regex;
+#[cfg(feature = "std")]
+use lazy_static::lazy_static;
+
+/// Category of built-in blocked patterns
+///
+/// Categories group related rules so they can be enabled together with
+/// [`SecurityConfigBuilder::with_category`](crate::config::SecurityConfigBuilder::with_category).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub enum PatternCategory {
+    /// SQL statements, tautologies and comment terminators
+    SqlInjection,
+    /// Script tags, event handlers and DOM access
+    Xss,
+    /// Directory traversal sequences
+    PathTraversal,
+    /// Multi-byte and double URL encoding
+    Encoding,
+    /// Shell command chaining and substitution
+    CommandInjection,
+    /// LDAP filter metacharacters used to rewrite search filters
+    ///
+    /// Not enabled by default since `*` and parentheses are common in prose;
+    /// enable it for values placed into LDAP filters.
+    LdapInjection,
+    /// MongoDB query operators and server-side JavaScript
+    ///
+    /// Not enabled by default; enable it for values that reach document
+    /// database queries. With the `json` feature, bodies can also be checked
+    /// structurally with `JsonSchema::deny_operator_keys`.
+    NoSqlInjection,
+    /// Template expression syntaxes and known template engine escapes
+    ///
+    /// Not enabled by default; enable it for values rendered through a
+    /// template engine such as Jinja2, Twig, Freemarker, ERB or Spring EL.
+    TemplateInjection,
+    /// Line breaks and terminal escape sequences that forge or hide log entries
+    ///
+    /// Not enabled by default; to keep such values instead of rejecting
+    /// them, escape them with [`for_logging`](crate::log::for_logging).
+    LogInjection,
+    /// Line breaks followed by header names in values destined for email headers
+    ///
+    /// Not enabled by default; see also
+    /// [`EmailHeaderValidator`](crate::validators::EmailHeaderValidator).
+    EmailHeaderInjection,
+    /// Quotes combined with boolean operators, predicates and axes in XPath queries
+    ///
+    /// Not enabled by default; see also
+    /// [`XPathLiteralValidator`](crate::validators::XPathLiteralValidator).
+    XPathInjection,
+}
+
+/// Attack type of a blocked pattern finding, used to route incidents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub enum ThreatClass {
+    /// SQL injection
+    SqlInjection,
+    /// Cross-site scripting
+    Xss,
+    /// Directory traversal
+    PathTraversal,
+    /// Shell command injection
+    CommandInjection,
+    /// Encoding tricks used to smuggle other payloads past filters
+    Encoding,
+    /// Injection into other interpreters such as LDAP, XPath, template engines or logs
+    Injection,
+    /// Finding of a user-supplied pattern
+    Custom,
+}
+
+impl ThreatClass {
+    /// Returns the class name used in reports
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SqlInjection => "sql_injection",
+            Self::Xss => "xss",
+            Self::PathTraversal => "path_traversal",
+            Self::CommandInjection => "command_injection",
+            Self::Encoding => "encoding",
+            Self::Injection => "injection",
+            Self::Custom => "custom",
+        }
+    }
+}
+
+impl PatternCategory {
+    /// Categories enabled by `with_default_blocked_patterns`
+    pub const DEFAULT: &'static [PatternCategory] = &[
+        Self::SqlInjection,
+        Self::Xss,
+        Self::PathTraversal,
+        Self::Encoding,
+        Self::CommandInjection,
+    ];
+
+    /// Every built-in category
+    pub const ALL: &'static [PatternCategory] = &[
+        Self::SqlInjection,
+        Self::Xss,
+        Self::PathTraversal,
+        Self::Encoding,
+        Self::CommandInjection,
+        Self::LdapInjection,
+        Self::NoSqlInjection,
+        Self::TemplateInjection,
+        Self::LogInjection,
+        Self::EmailHeaderInjection,
+        Self::XPathInjection,
+    ];
+
+    /// Returns the category with the given report name
+    ///
+    /// ```rust
+    /// use huginn::PatternCategory;
+    ///
+    /// assert_eq!(PatternCategory::from_name("xss"), Some(PatternCategory::Xss));
+    /// assert_eq!(PatternCategory::from_name("XSS"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|category| category.name() == name)
+    }
+
+    /// Returns the category name used in reports
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SqlInjection => "sql_injection",
+            Self::Xss => "xss",
+            Self::PathTraversal => "path_traversal",
+            Self::Encoding => "encoding",
+            Self::CommandInjection => "command_injection",
+            Self::LdapInjection => "ldap_injection",
+            Self::NoSqlInjection => "nosql_injection",
+            Self::TemplateInjection => "template_injection",
+            Self::LogInjection => "log_injection",
+            Self::EmailHeaderInjection => "email_header_injection",
+            Self::XPathInjection => "xpath_injection",
+        }
+    }
+
+    /// Returns the attack type findings of the category are classified as
+    pub fn threat(&self) -> ThreatClass {
+        match self {
+            Self::SqlInjection => ThreatClass::SqlInjection,
+            Self::Xss => ThreatClass::Xss,
+            Self::PathTraversal => ThreatClass::PathTraversal,
+            Self::Encoding => ThreatClass::Encoding,
+            Self::CommandInjection => ThreatClass::CommandInjection,
+            Self::LdapInjection
+            | Self::NoSqlInjection
+            | Self::TemplateInjection
+            | Self::LogInjection
+            | Self::EmailHeaderInjection
+            | Self::XPathInjection => ThreatClass::Injection,
+        }
+    }
+
+    /// Returns the built-in rules of the category
+    #[cfg(feature = "std")]
+    pub fn rules(&self) -> &'static [PatternRule] {
+        match self {
+            Self::SqlInjection => &SQL_INJECTION,
+            Self::Xss => &XSS,
+            Self::PathTraversal => &PATH_TRAVERSAL,
+            Self::Encoding => &ENCODING,
+            Self::CommandInjection => &COMMAND_INJECTION,
+            Self::LdapInjection => &LDAP_INJECTION,
+            Self::NoSqlInjection => &NOSQL_INJECTION,
+            Self::TemplateInjection => &TEMPLATE_INJECTION,
+            Self::LogInjection => &LOG_INJECTION,
+            Self::EmailHeaderInjection => &EMAIL_HEADER_INJECTION,
+            Self::XPathInjection => &XPATH_INJECTION,
+        }
+    }
+}
+
+/// Compiles a built-in rule table
+#[cfg(feature = "std")]
+fn compile(category: PatternCategory, rules: &[(&str, &str)]) -> Vec<PatternRule> {
+    rules
+        .iter()
+        .map(|(name, pattern)| {
+            PatternRule::new(*name, Regex::new(pattern).unwrap()).with_category(category)
+        })
+        .collect()
+}
+
+// Rules only fire on tokens in a context where they are meaningful (a `;`
+// followed by a command word, `--` right after a closing quote), so
+// ordinary prose containing punctuation passes.
+#[cfg(feature = "std")]
+lazy_static! {
+    static ref SQL_INJECTION: Vec<PatternRule> = compile(
+        PatternCategory::SqlInjection,
+        &[
+            ("sql_union_select", r"(?i)\bunion\s+(all\s+)?select\b"),
+            (
+                "sql_select_from",
+                r"(?i)\bselect\s+(\*|[\w.]+(\s*,\s*[\w.]+)*)\s+from\s+[\w.]+"
+            ),
+            (
+                "sql_insert_into",
+                r"(?i)\binsert\s+into\s+[\w.]+\s*(\(|values\b|select\b)"
+            ),
+            (
+                "sql_delete_from",
+                r"(?i)\bdelete\s+from\s+[\w.]+\s*(;|$|where\b)"
+            ),
+            (
+                "sql_drop",
+                r"(?i)\bdrop\s+(table|database|schema|view)\s+[\w.]+"
+            ),
+            (
+                "sql_update_set",
+                r"(?i)\bupdate\s+[\w.]+\s+set\s+[\w.]+\s*="
+            ),
+            (
+                "sql_stacked_query",
+                r"(?i);\s*(select|insert|update|delete|drop|alter|create|truncate|shutdown|exec)\b"
+            ),
+            (
+                "sql_tautology",
+                r#"(?i)(['"]\s*(or|and)\s+['"]?\w+['"]?\s*(=|like\b)|\bor\s+1\s*=\s*1\b)"#
+            ),
+            ("sql_comment", r#"['")]\s*(--|#|/\*)"#),
+            (
+                "sql_exec",
+                r"(?i)\b(exec(ute)?\s+(xp|sp)_\w+|xp_cmdshell\b)"
+            ),
+        ]
+    );
+    static ref XSS: Vec<PatternRule> = compile(
+        PatternCategory::Xss,
+        &[
+            ("xss_script_tag", r"(?i)<\s*/?\s*script\b"),
+            (
+                "xss_script_uri",
+                r"(?i)\b(javascript|vbscript)\s*:|\bdata\s*:\s*text/html"
+            ),
+            (
+                "xss_event_handler",
+                r"(?i)\bon(load|error|click|dblclick|mouse\w+|key\w+|focus|blur|submit|change|input|toggle|animation\w+|pointer\w+)\s*="
+            ),
+            ("xss_js_call", r"(?i)\b(alert|eval|prompt|confirm)\s*\("),
+            (
+                "xss_dom_access",
+                r"(?i)\b(document\.(cookie|write|location|domain)|window\.(location|open|name))\b"
+            ),
+        ]
+    );
+    static ref PATH_TRAVERSAL: Vec<PatternRule> = compile(
+        PatternCategory::PathTraversal,
+        &[("path_traversal", r"(?i)(\.\./|\.\.\\|%2e%2e%2f|%2e%2e%5c)"),]
+    );
+    static ref ENCODING: Vec<PatternRule> = compile(
+        PatternCategory::Encoding,
+        &[
+            ("encoded_sequence", r"(?:%[0-9a-fA-F]{2}){2,}"),
+            ("double_encoding", r"(?i)%25[0-9a-f]{2}"),
+        ]
+    );
+    static ref COMMAND_INJECTION: Vec<PatternRule> = compile(
+        PatternCategory::CommandInjection,
+        &[
+            (
+                "cmd_chained_command",
+                r"(?i)(;|\|\|?|&&)\s*(rm|cat|ls|wget|curl|nc|ncat|bash|sh|zsh|python\d?|perl|ruby|php|chmod|chown|kill|whoami|id|uname|ping|powershell|cmd)\b"
+            ),
+            ("cmd_substitution", r"`[^`]+`|\$\([^)]*\)"),
+            (
+                "cmd_shell_path",
+                r"(?i)(/bin/(ba|z)?sh\b|\bcmd\.exe\b|\bpowershell(\.exe)?\s+-)"
+            ),
+            ("cmd_destructive", r"(?i)\brm\s+-[a-z]*[rf]"),
+        ]
+    );
+    static ref LDAP_INJECTION: Vec<PatternRule> = compile(
+        PatternCategory::LdapInjection,
+        &[
+            ("ldap_filter_break", r"\)\s*\("),
+            ("ldap_wildcard_close", r"\*\s*\)"),
+            ("ldap_filter_open", r"\(\s*[|&!]?\s*[\w.;-]+\s*[~<>]?="),
+            ("ldap_nul", r"\x00|%00|\\00"),
+            (
+                "ldap_bad_escape",
+                r"\\([^0-9a-fA-F]|[0-9a-fA-F]([^0-9a-fA-F]|$)|$)"
+            ),
+        ]
+    );
+    static ref NOSQL_INJECTION: Vec<PatternRule> = compile(
+        PatternCategory::NoSqlInjection,
+        &[
+            ("nosql_operator_param", r"\[\s*\$[a-zA-Z]+\s*\]"),
+            (
+                "nosql_operator_key",
+                r#"["']?\$(where|ne|eq|gte?|lte?|in|nin|regex|exists|expr|or|and|nor|not|function|accumulator|elemMatch)["']?\s*:"#
+            ),
+            ("nosql_js_this", r"\bthis\.\w+\s*(===?|!==?|[<>]=?)"),
+            (
+                "nosql_js_call",
+                r"(?i)\b(sleep\s*\(\s*\d+|db\.\w+(\.\w+)*\s*\()"
+            ),
+        ]
+    );
+    static ref TEMPLATE_INJECTION: Vec<PatternRule> = compile(
+        PatternCategory::TemplateInjection,
+        &[
+            ("ssti_double_brace", r"\{\{[^{}]{0,200}\}\}"),
+            (
+                "ssti_block_tag",
+                r"\{%-?\s*(import|include|set|for|if|macro|extends|block|call|raw)\b"
+            ),
+            ("ssti_interpolation", r"[$#*@]\{[^{}]{0,200}\}"),
+            ("ssti_erb_tag", r"<%[=-]?[^%]{0,200}%>"),
+            (
+                "ssti_python_dunder",
+                r"__(class|mro|subclasses|globals|builtins|init|import|base)__"
+            ),
+            (
+                "ssti_freemarker_exec",
+                r"(?i)freemarker\.template\.utility\.(Execute|ObjectConstructor)|\?new\s*\(\s*\)"
+            ),
+            (
+                "ssti_java_runtime",
+                r"(?i)\bT\s*\(\s*java\.|\bjava\.lang\.(Runtime|ProcessBuilder)\b|\bgetRuntime\s*\(\s*\)"
+            ),
+        ]
+    );
+    static ref LOG_INJECTION: Vec<PatternRule> = compile(
+        PatternCategory::LogInjection,
+        &[
+            ("log_line_break", r"(?i)[\r\n\x{85}\x{2028}\x{2029}]|%0[ad]"),
+            (
+                "log_ansi_escape",
+                r"(?i)[\x1b\x{9b}]|\\(x1b|u001b|033|e\[)|%1b"
+            ),
+        ]
+    );
+    static ref EMAIL_HEADER_INJECTION: Vec<PatternRule> = compile(
+        PatternCategory::EmailHeaderInjection,
+        &[
+            (
+                "email_injected_header",
+                r"(?i)(\r|\n|%0[ad]|\\[rn])\s*(to|cc|bcc|from|sender|reply-to|subject|content-type|content-transfer-encoding|mime-version)\s*:"
+            ),
+            (
+                "email_header_name",
+                r"(?i)\b(bcc|cc|reply-to|content-type|content-transfer-encoding|mime-version)\s*:"
+            ),
+        ]
+    );
+    static ref XPATH_INJECTION: Vec<PatternRule> = compile(
+        PatternCategory::XPathInjection,
+        &[
+            (
+                "xpath_tautology",
+                r#"(?i)['"]\s*(or|and)\s+(['"]?\w*['"]?\s*(=|!=)|\w+\s*\()"#
+            ),
+            ("xpath_predicate_break", r#"['"]\s*\]|\]\s*\|"#),
+            (
+                "xpath_axis",
+                r"(?i)(^|[\s'\x22|\[(])//\*?\w|\b(ancestor|descendant|following|preceding|parent|child|self|attribute|namespace)(-or-self|-sibling)?::"
+            ),
+            (
+                "xpath_function",
+                r"(?i)\b(count|string-length|substring|starts-with|doc|document|position|last)\s*\("
+            ),
+        ]
+    );
+}
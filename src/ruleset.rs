@@ -0,0 +1,392 @@
+use super::{
+    config::{PatternRule, SecurityConfig},
+    error::PatternError,
+    expr::{ExprError, ExpressionRule, RuleExpr},
+    pattern::{compile_pattern, RegexLimits},
+    rules::PatternCategory,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Newest version of the ruleset format, written by [`SecurityConfig::export_rules`]
+///
+/// Version 2 added expression rules; rulesets without them are still
+/// written as version 1, so older releases keep reading them.
+pub const RULESET_VERSION: u32 = 2;
+
+/// How the `pattern` of a rule is interpreted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleKind {
+    /// A regular expression
+    #[default]
+    Pattern,
+    /// A [`RuleExpr`] such as `length > 1000 or entropy > 4.5`
+    Expression,
+}
+
+impl RuleKind {
+    fn is_pattern(&self) -> bool {
+        *self == Self::Pattern
+    }
+}
+
+/// What happens when a rule matches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// The input is rejected
+    #[default]
+    Block,
+    /// The match is reported as a warning, see
+    /// [`PipelineBuilder::with_warning_patterns`](crate::pipeline::PipelineBuilder::with_warning_patterns)
+    Warn,
+}
+
+/// Named blocked pattern of a ruleset
+///
+/// The struct is non-exhaustive, since fields such as `kind` are added as
+/// the format grows; build entries with [`RuleEntry::new`] or
+/// [`RuleEntry::expression`] rather than struct literals.
+///
+/// ```rust
+/// use huginn::ruleset::{RuleEntry, Ruleset, Severity, RULESET_VERSION};
+/// use huginn::SecurityConfig;
+///
+/// let ruleset = Ruleset {
+///     version: RULESET_VERSION,
+///     forbidden_chars: vec![],
+///     rules: vec![
+///         RuleEntry::new("tenant_secret", "(?i)acme-internal"),
+///         RuleEntry::expression("oversized", "length > 1000").with_severity(Severity::Warn),
+///     ],
+/// };
+/// let config = SecurityConfig::import_rules(&ruleset)?;
+/// assert!(config.has_blocked_pattern("ACME-internal wiki"));
+/// assert!(ruleset.warning_config()?.is_some());
+/// # Ok::<(), huginn::ruleset::RulesetError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct RuleEntry {
+    /// Rule name reported when the pattern matches
+    pub name: String,
+    /// Regular expression source, or expression source for expression rules
+    pub pattern: String,
+    /// How `pattern` is interpreted
+    #[serde(default, skip_serializing_if = "RuleKind::is_pattern")]
+    pub kind: RuleKind,
+    /// Name of the built-in category, such as `sql_injection`, for built-in rules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// What happens when the rule matches
+    #[serde(default)]
+    pub severity: Severity,
+}
+
+impl RuleEntry {
+    /// Creates a blocking rule from a regular expression source
+    pub fn new<N: Into<String>, P: Into<String>>(name: N, pattern: P) -> Self {
+        Self {
+            name: name.into(),
+            pattern: pattern.into(),
+            kind: RuleKind::Pattern,
+            category: None,
+            severity: Severity::Block,
+        }
+    }
+
+    /// Creates a blocking rule from a [`RuleExpr`] source
+    pub fn expression<N: Into<String>, S: Into<String>>(name: N, source: S) -> Self {
+        Self {
+            kind: RuleKind::Expression,
+            ..Self::new(name, source)
+        }
+    }
+
+    /// Sets what happens when the rule matches
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+}
+
+/// Versioned, serializable description of a configuration's rules
+///
+/// Rulesets are plain serde data, so any serde format works. Exported
+/// rulesets list forbidden characters in sorted order and rules in
+/// matching order, so two exports can be diffed line by line. In JSON and
+/// TOML they look like this:
+///
+/// ```json
+/// {
+///   "version": 2,
+///   "forbidden_chars": ["\"", "&", "<"],
+///   "rules": [
+///     { "name": "xss_script_tag", "pattern": "(?i)<\\s*script\\b", "category": "xss", "severity": "block" },
+///     { "name": "tenant_secret", "pattern": "(?i)acme-internal", "severity": "warn" },
+///     { "name": "oversized", "pattern": "length > 1000", "kind": "expression", "severity": "block" }
+///   ]
+/// }
+/// ```
+///
+/// ```toml
+/// version = 2
+/// forbidden_chars = ['"', "&", "<"]
+///
+/// [[rules]]
+/// name = "tenant_secret"
+/// pattern = "(?i)acme-internal"
+/// severity = "warn"
+///
+/// [[rules]]
+/// name = "union_select"
+/// pattern = 'contains("union") and contains("select") within 40 chars'
+/// kind = "expression"
+/// ```
+///
+/// ```rust
+/// use huginn::ruleset::Ruleset;
+/// use huginn::SecurityConfig;
+///
+/// let config = SecurityConfig::builder()
+///     .add_forbidden_char('<')
+///     .add_named_blocked_pattern("tenant_secret", r"(?i)acme-internal")?
+///     .build();
+///
+/// let json = serde_json::to_string(&config.export_rules()).unwrap();
+/// let ruleset: Ruleset = serde_json::from_str(&json).unwrap();
+/// let imported = SecurityConfig::import_rules(&ruleset)?;
+/// assert!(imported.is_char_forbidden(&'<'));
+/// assert!(imported.has_blocked_pattern("ACME-internal wiki"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ruleset {
+    /// Format version, at most [`RULESET_VERSION`]
+    pub version: u32,
+    /// Forbidden characters
+    #[serde(default)]
+    pub forbidden_chars: Vec<char>,
+    /// Blocked patterns
+    #[serde(default)]
+    pub rules: Vec<RuleEntry>,
+}
+
+impl Ruleset {
+    /// Appends the blocked patterns of a configuration as warning-level rules
+    ///
+    /// Forbidden characters of the warning configuration are not exported.
+    pub fn with_warnings(mut self, warnings: &SecurityConfig) -> Self {
+        self.rules
+            .extend(rule_entries(warnings).map(|rule| RuleEntry {
+                severity: Severity::Warn,
+                ..rule
+            }));
+        self.version = self.version.max(version_for(&self.rules));
+        self
+    }
+
+    /// Builds a configuration from the warning-level rules, `None` if there are none
+    ///
+    /// Pass it to
+    /// [`PipelineBuilder::with_warning_patterns`](crate::pipeline::PipelineBuilder::with_warning_patterns).
+    pub fn warning_config(&self) -> Result<Option<SecurityConfig>, RulesetError> {
+        check_version(self)?;
+        if self
+            .rules
+            .iter()
+            .all(|rule| rule.severity != Severity::Warn)
+        {
+            return Ok(None);
+        }
+        build(&[], self.rules_with(Severity::Warn)).map(Some)
+    }
+
+    fn rules_with(&self, severity: Severity) -> impl Iterator<Item = &RuleEntry> {
+        self.rules
+            .iter()
+            .filter(move |rule| rule.severity == severity)
+    }
+}
+
+/// Errors raised while importing a ruleset
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum RulesetError {
+    /// The ruleset was written in a newer format
+    #[error("Unsupported ruleset version {found}, supported up to {supported}")]
+    UnsupportedVersion {
+        /// Version of the ruleset
+        found: u32,
+        /// Newest version this crate reads
+        supported: u32,
+    },
+
+    /// A rule names a category that does not exist
+    #[error("Rule {rule:?} names unknown category {category:?}")]
+    UnknownCategory {
+        /// Rule name
+        rule: String,
+        /// Category name
+        category: String,
+    },
+
+    /// A rule's pattern was rejected
+    #[error("Rule {rule:?} has an invalid pattern: {source}")]
+    Pattern {
+        /// Rule name
+        rule: String,
+        /// Reason the pattern was rejected
+        #[source]
+        source: PatternError,
+    },
+
+    /// A rule's expression was rejected
+    #[error("Rule {rule:?} has an invalid expression: {source}")]
+    Expression {
+        /// Rule name
+        rule: String,
+        /// Reason the expression was rejected
+        #[source]
+        source: ExprError,
+    },
+}
+
+impl SecurityConfig {
+    /// Describes the forbidden characters and blocked patterns as a [`Ruleset`]
+    ///
+    /// Every rule is exported with [`Severity::Block`]; lazily added
+    /// patterns are included after the others, followed by expressions.
+    pub fn export_rules(&self) -> Ruleset {
+        let mut forbidden_chars: Vec<char> = self.forbidden_chars().iter().copied().collect();
+        forbidden_chars.sort_unstable();
+        let rules: Vec<RuleEntry> = rule_entries(self).collect();
+        Ruleset {
+            version: version_for(&rules),
+            forbidden_chars,
+            rules,
+        }
+    }
+
+    /// Builds a configuration from the forbidden characters and blocking rules of a ruleset
+    ///
+    /// Patterns other than the built-in rules, including those inside
+    /// expressions, are checked against the default [`RegexLimits`], since
+    /// rulesets usually come from outside the binary. Warning-level rules are
+    /// left out; see [`Ruleset::warning_config`].
+    pub fn import_rules(ruleset: &Ruleset) -> Result<SecurityConfig, RulesetError> {
+        check_version(ruleset)?;
+        build(
+            &ruleset.forbidden_chars,
+            ruleset.rules_with(Severity::Block),
+        )
+    }
+}
+
+fn check_version(ruleset: &Ruleset) -> Result<(), RulesetError> {
+    if ruleset.version > RULESET_VERSION {
+        return Err(RulesetError::UnsupportedVersion {
+            found: ruleset.version,
+            supported: RULESET_VERSION,
+        });
+    }
+    Ok(())
+}
+
+/// Oldest format version able to describe the rules
+fn version_for(rules: &[RuleEntry]) -> u32 {
+    match rules.iter().any(|rule| rule.kind == RuleKind::Expression) {
+        true => 2,
+        false => 1,
+    }
+}
+
+fn rule_entries(config: &SecurityConfig) -> impl Iterator<Item = RuleEntry> + '_ {
+    let eager = config.blocked_patterns().iter().map(|rule| RuleEntry {
+        name: rule.name.clone(),
+        pattern: rule.regex.as_str().to_string(),
+        kind: RuleKind::Pattern,
+        category: rule.category.map(|category| category.name().to_string()),
+        severity: Severity::Block,
+    });
+    let lazy = config.lazy_sources().iter().map(|source| RuleEntry {
+        name: source.name.clone(),
+        pattern: source.pattern.clone(),
+        kind: RuleKind::Pattern,
+        category: None,
+        severity: Severity::Block,
+    });
+    let expressions = config.expression_rules.iter().map(|rule| RuleEntry {
+        name: rule.name.clone(),
+        pattern: rule.expr.source().to_string(),
+        kind: RuleKind::Expression,
+        category: None,
+        severity: Severity::Block,
+    });
+    eager.chain(lazy).chain(expressions)
+}
+
+fn build<'r>(
+    forbidden_chars: &[char],
+    rules: impl Iterator<Item = &'r RuleEntry>,
+) -> Result<SecurityConfig, RulesetError> {
+    let mut builder = forbidden_chars
+        .iter()
+        .fold(SecurityConfig::builder(), |builder, c| {
+            builder.add_forbidden_char(*c)
+        });
+    for entry in rules {
+        match entry.kind {
+            RuleKind::Pattern => builder.push_rule(import_rule(entry)?),
+            RuleKind::Expression => builder.push_expression(import_expression(entry)?),
+        }
+    }
+    Ok(builder.build())
+}
+
+/// Compiles a rule, reusing the built-in rule when the entry names its category and source
+///
+/// Built-in rules are trusted, so only other patterns are held to the
+/// default [`RegexLimits`].
+fn import_rule(entry: &RuleEntry) -> Result<PatternRule, RulesetError> {
+    let category =
+        match &entry.category {
+            Some(name) => Some(PatternCategory::from_name(name).ok_or_else(|| {
+                RulesetError::UnknownCategory {
+                    rule: entry.name.clone(),
+                    category: name.clone(),
+                }
+            })?),
+            None => None,
+        };
+    let built_in = category.and_then(|category| {
+        category
+            .rules()
+            .iter()
+            .find(|rule| rule.regex.as_str() == entry.pattern)
+    });
+    let regex = match built_in {
+        Some(rule) => rule.regex.clone(),
+        None => compile_pattern(&entry.pattern, &RegexLimits::default()).map_err(|source| {
+            RulesetError::Pattern {
+                rule: entry.name.clone(),
+                source,
+            }
+        })?,
+    };
+    let rule = PatternRule::new(entry.name.as_str(), regex);
+    Ok(match category {
+        Some(category) => rule.with_category(category),
+        None => rule,
+    })
+}
+
+/// Parses an expression rule, holding its patterns to the default [`RegexLimits`]
+fn import_expression(entry: &RuleEntry) -> Result<ExpressionRule, RulesetError> {
+    let expr = RuleExpr::parse(&entry.pattern).map_err(|source| RulesetError::Expression {
+        rule: entry.name.clone(),
+        source,
+    })?;
+    Ok(ExpressionRule::new(entry.name.as_str(), expr))
+}
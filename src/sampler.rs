@@ -0,0 +1,193 @@
+use super::pattern::Regex;
+use super::{log::for_logging, pipeline::RejectionEvent, rules::ThreatClass};
+use lazy_static::lazy_static;
+use std::{
+    collections::VecDeque,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime},
+};
+
+lazy_static! {
+    static ref EMAIL: Regex =
+        Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}").unwrap();
+}
+
+/// Masks personal data in text: email addresses become `<email>` and digits `#`
+///
+/// ```rust
+/// use huginn::sampler::redact;
+///
+/// assert_eq!(redact("mail bob@example.com or call 555-0142"), "mail <email> or call ###-####");
+/// assert_eq!(redact("' OR 1=1 --"), "' OR #=# --");
+/// ```
+pub fn redact(input: &str) -> String {
+    EMAIL
+        .replace_all(input, "<email>")
+        .chars()
+        .map(|c| if c.is_ascii_digit() { '#' } else { c })
+        .collect()
+}
+
+/// Rejected input kept by a [`RejectionSampler`], with personal data masked
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Sample {
+    /// Time the input was rejected
+    pub timestamp: SystemTime,
+    /// Target type reported by the validator
+    pub validator: &'static str,
+    /// Label of the pipeline, if set
+    pub pipeline: Option<String>,
+    /// Error code of the rejection
+    pub code: &'static str,
+    /// Ids of the rules that rejected the input
+    pub rules: Vec<String>,
+    /// Attack types of the matched patterns
+    pub threats: Vec<ThreatClass>,
+    /// Redacted input, truncated to the sampler's maximum length
+    pub input: String,
+}
+
+#[derive(Debug)]
+struct State {
+    samples: VecDeque<Sample>,
+    seen: u64,
+    window_started: Instant,
+    taken_in_window: u32,
+}
+
+/// Keeps a bounded, rate-limited sample of rejected inputs for rule authors
+///
+/// Inputs are redacted with [`redact`] and have control characters escaped
+/// before they are stored, so the sample can be studied without retaining
+/// raw user data. Only every `n`th rejection is considered, at most
+/// `per_minute` are kept each minute, and once `capacity` samples are held
+/// the oldest is dropped. Feed it from
+/// [`PipelineBuilder::on_rejection`](crate::pipeline::PipelineBuilder::on_rejection).
+///
+/// ```rust
+/// use huginn::sampler::RejectionSampler;
+/// use huginn::validators::FilenameValidator;
+/// use huginn::{Pipeline, SecurityConfig};
+/// use std::sync::Arc;
+///
+/// let sampler = Arc::new(RejectionSampler::new(100).with_rate_limit(10));
+/// let pipeline = Pipeline::builder(SecurityConfig::default())
+///     .on_rejection({
+///         let sampler = Arc::clone(&sampler);
+///         move |event| sampler.record(event)
+///     })
+///     .build();
+///
+/// let _ = pipeline.process("../../home/bob@example.com/2024.txt", &FilenameValidator::new());
+///
+/// let samples = sampler.samples();
+/// assert_eq!(samples[0].input, "../../home/<email>/####.txt");
+/// assert_eq!(samples[0].rules, ["path_traversal"]);
+/// ```
+#[derive(Debug)]
+pub struct RejectionSampler {
+    capacity: usize,
+    per_minute: u32,
+    every: u64,
+    max_len: usize,
+    state: Mutex<State>,
+}
+
+impl RejectionSampler {
+    /// Creates sampler holding up to `capacity` samples, taking at most 60 per minute
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            per_minute: 60,
+            every: 1,
+            max_len: 512,
+            state: Mutex::new(State {
+                samples: VecDeque::new(),
+                seen: 0,
+                window_started: Instant::now(),
+                taken_in_window: 0,
+            }),
+        }
+    }
+
+    /// Sets the maximum number of samples taken per minute
+    pub fn with_rate_limit(mut self, per_minute: u32) -> Self {
+        self.per_minute = per_minute;
+        self
+    }
+
+    /// Considers only every `n`th rejection
+    pub fn every_nth(mut self, n: u64) -> Self {
+        self.every = n.max(1);
+        self
+    }
+
+    /// Sets the maximum length of stored inputs in characters
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Offers a rejection to the sampler, storing it if the sampling limits allow
+    pub fn record(&self, event: &RejectionEvent<'_>) {
+        let now = Instant::now();
+        {
+            let mut state = self.lock();
+            state.seen += 1;
+            if !(state.seen - 1).is_multiple_of(self.every) || self.capacity == 0 {
+                return;
+            }
+            if now.duration_since(state.window_started) >= Duration::from_secs(60) {
+                state.window_started = now;
+                state.taken_in_window = 0;
+            }
+            if state.taken_in_window >= self.per_minute {
+                return;
+            }
+            state.taken_in_window += 1;
+        }
+
+        // Redact outside the lock; the slot was reserved above
+        let truncated: String = event.input.chars().take(self.max_len).collect();
+        let sample = Sample {
+            timestamp: SystemTime::now(),
+            validator: event.validator,
+            pipeline: event.pipeline.map(str::to_string),
+            code: event.error.code(),
+            rules: event.rule_ids().into_iter().map(str::to_string).collect(),
+            threats: event.error.threats(),
+            input: for_logging(&redact(&truncated)).into_owned(),
+        };
+
+        let mut state = self.lock();
+        if state.samples.len() >= self.capacity {
+            state.samples.pop_front();
+        }
+        state.samples.push_back(sample);
+    }
+
+    /// Returns a copy of the stored samples, oldest first
+    pub fn samples(&self) -> Vec<Sample> {
+        self.lock().samples.iter().cloned().collect()
+    }
+
+    /// Removes and returns the stored samples, oldest first
+    pub fn drain(&self) -> Vec<Sample> {
+        self.lock().samples.drain(..).collect()
+    }
+
+    /// Returns the number of stored samples
+    pub fn len(&self) -> usize {
+        self.lock().samples.len()
+    }
+
+    /// Returns true if no samples are stored
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
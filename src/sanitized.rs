@@ -0,0 +1,191 @@
+use super::{
+    config::DEFAULT_CONFIG,
+    error::ValidationError,
+    validation::{prepare_decoded, prepare_input},
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{any::type_name, fmt, ops::Deref, str::FromStr};
+
+/// String that was sanitized and screened while being deserialized
+///
+/// Deserialization fails when the value contains forbidden characters or
+/// matches a blocked pattern of the default [`SecurityConfig`](crate::SecurityConfig),
+/// so request types can be screened without calling huginn per field.
+///
+/// ```rust
+/// use huginn::SanitizedString;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Comment {
+///     author: SanitizedString,
+///     body: SanitizedString,
+/// }
+///
+/// let comment: Comment =
+///     serde_json::from_str(r#"{"author": "alice", "body": "Nice post"}"#).unwrap();
+/// assert_eq!(comment.author.as_str(), "alice");
+///
+/// let result = serde_json::from_str::<Comment>(r#"{"author": "a;b", "body": "hi"}"#);
+/// assert!(result.is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SanitizedString(String);
+
+impl SanitizedString {
+    /// Sanitizes and screens a string with the default configuration
+    pub fn new(input: &str) -> Result<Self, ValidationError> {
+        Ok(Self(screen(input)?))
+    }
+
+    /// Sanitizes and screens a string that was already URL-decoded
+    ///
+    /// For values a framework has decoded, such as path segments and form
+    /// fields, so escapes left in them are not decoded a second time.
+    ///
+    /// ```rust
+    /// use huginn::SanitizedString;
+    ///
+    /// let name = SanitizedString::from_decoded("O Brien").unwrap();
+    /// assert_eq!(name.as_str(), "O Brien");
+    /// assert!(SanitizedString::from_decoded("a;b").is_err());
+    /// ```
+    pub fn from_decoded(input: &str) -> Result<Self, ValidationError> {
+        Ok(Self(screen_decoded(input)?))
+    }
+
+    /// Returns the sanitized value
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consumes the wrapper, returning the sanitized value
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl Deref for SanitizedString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for SanitizedString {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for SanitizedString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for SanitizedString {
+    type Err = ValidationError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::new(input)
+    }
+}
+
+impl Serialize for SanitizedString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for SanitizedString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Self::new(&raw).map_err(de::Error::custom)
+    }
+}
+
+/// Value parsed with [`FromStr`] after its string form was sanitized and screened
+///
+/// The value must be given as a string; it is screened like
+/// [`SanitizedString`] and then parsed, reporting parse failures as
+/// [`ValidationError::InvalidFormat`].
+///
+/// ```rust
+/// use huginn::Sanitized;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Query {
+///     page: Sanitized<u32>,
+/// }
+///
+/// let query: Query = serde_json::from_str(r#"{"page": "3"}"#).unwrap();
+/// assert_eq!(*query.page, 3);
+/// assert!(serde_json::from_str::<Query>(r#"{"page": "three"}"#).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Sanitized<T>(T);
+
+impl<T: FromStr> Sanitized<T> {
+    /// Sanitizes, screens and parses a string with the default configuration
+    pub fn new(input: &str) -> Result<Self, ValidationError> {
+        screen(input)?
+            .parse()
+            .map(Self)
+            .map_err(|_| ValidationError::invalid_format(type_name::<T>()))
+    }
+
+    /// Sanitizes, screens and parses a string that was already URL-decoded
+    pub fn from_decoded(input: &str) -> Result<Self, ValidationError> {
+        screen_decoded(input)?
+            .parse()
+            .map(Self)
+            .map_err(|_| ValidationError::invalid_format(type_name::<T>()))
+    }
+}
+
+impl<T> Sanitized<T> {
+    /// Consumes the wrapper, returning the parsed value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Sanitized<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Sanitized<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: Serialize> Serialize for Sanitized<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T: FromStr> Deserialize<'de> for Sanitized<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Self::new(&raw).map_err(de::Error::custom)
+    }
+}
+
+/// Runs the pipeline stages that precede the validator
+fn screen(input: &str) -> Result<String, ValidationError> {
+    prepare_input(input, &DEFAULT_CONFIG, &[]).map(|cleaned| cleaned.into_owned())
+}
+
+/// Runs the pipeline stages that precede the validator, except URL decoding
+fn screen_decoded(input: &str) -> Result<String, ValidationError> {
+    prepare_decoded(input, &DEFAULT_CONFIG).map(|cleaned| cleaned.into_owned())
+}
@@ -0,0 +1,117 @@
+use super::{config::SecurityConfig, error::PatternMatch};
+use rayon::prelude::*;
+use std::{collections::BTreeMap, ops::Range};
+
+/// Settings for scanning very large inputs in parallel, overlapping chunks
+///
+/// Each chunk is extended by `overlap` bytes into its successor, so matches up
+/// to that length are found even when they straddle a chunk boundary. Longer
+/// matches and anchored patterns (`^`, `$`) may behave differently than on a
+/// whole-input scan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkedScan {
+    /// Minimum input length in bytes that triggers chunked scanning
+    pub threshold: usize,
+    /// Length of each chunk in bytes, excluding overlap
+    pub chunk_size: usize,
+    /// Number of bytes each chunk extends into the next
+    pub overlap: usize,
+}
+
+impl Default for ChunkedScan {
+    /// Creates settings splitting inputs over 1 MiB into 256 KiB chunks
+    fn default() -> Self {
+        Self {
+            threshold: 1 << 20,
+            chunk_size: 256 << 10,
+            overlap: 4 << 10,
+        }
+    }
+}
+
+impl ChunkedScan {
+    /// Returns true if the input is large enough to be scanned in chunks
+    pub(crate) fn applies_to(&self, input: &str) -> bool {
+        input.len() >= self.threshold
+    }
+
+    /// Splits input into overlapping ranges aligned to character boundaries
+    fn chunk_ranges(&self, input: &str) -> Vec<Range<usize>> {
+        let chunk_size = self.chunk_size.max(4);
+        let mut ranges = Vec::with_capacity(input.len() / chunk_size + 1);
+        let mut start = 0;
+
+        while start < input.len() {
+            let mut end = floor_char_boundary(input, start + chunk_size);
+            if end <= start {
+                end = ceil_char_boundary(input, start + 1);
+            }
+            let scan_end = floor_char_boundary(input, end + self.overlap).max(end);
+            ranges.push(start..scan_end);
+            start = end;
+        }
+
+        ranges
+    }
+}
+
+/// Checks chunks in parallel, stopping as soon as any chunk matches
+pub(crate) fn has_blocked_pattern(
+    config: &SecurityConfig,
+    input: &str,
+    scan: &ChunkedScan,
+) -> bool {
+    scan.chunk_ranges(input)
+        .into_par_iter()
+        .any(|range| config.has_blocked_pattern_in(&input[range]))
+}
+
+/// Finds matches in parallel, keeping the earliest span of each pattern
+pub(crate) fn find_blocked_patterns(
+    config: &SecurityConfig,
+    input: &str,
+    scan: &ChunkedScan,
+) -> Vec<PatternMatch> {
+    let found: Vec<PatternMatch> = scan
+        .chunk_ranges(input)
+        .into_par_iter()
+        .flat_map_iter(|range| {
+            let offset = range.start;
+            config
+                .find_blocked_patterns_in(&input[range])
+                .into_iter()
+                .map(move |mut m| {
+                    m.span = m.span.start + offset..m.span.end + offset;
+                    m
+                })
+        })
+        .collect();
+
+    let mut earliest: BTreeMap<usize, PatternMatch> = BTreeMap::new();
+    for m in found {
+        match earliest.get(&m.index) {
+            Some(existing) if existing.span.start <= m.span.start => {}
+            _ => {
+                earliest.insert(m.index, m);
+            }
+        }
+    }
+    earliest.into_values().collect()
+}
+
+fn floor_char_boundary(input: &str, mut index: usize) -> usize {
+    if index >= input.len() {
+        return input.len();
+    }
+    while !input.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn ceil_char_boundary(input: &str, mut index: usize) -> usize {
+    while index < input.len() && !input.is_char_boundary(index) {
+        index += 1;
+    }
+    index.min(input.len())
+}
@@ -0,0 +1,276 @@
+use super::{error::ValidationError, validation::Validator};
+use std::borrow::Cow;
+
+/// Keywords rejected as identifiers unless explicitly allowed
+const RESERVED_WORDS: [&str; 32] = [
+    "ALL", "ALTER", "AND", "AS", "BY", "CREATE", "DELETE", "DROP", "EXEC", "EXECUTE", "FROM",
+    "GRANT", "GROUP", "HAVING", "INSERT", "INTO", "JOIN", "LIMIT", "NOT", "NULL", "OR", "ORDER",
+    "REVOKE", "SELECT", "SET", "TABLE", "TRUNCATE", "UNION", "UPDATE", "VALUES", "WHERE", "WITH",
+];
+
+/// Validates values that will be bound as query parameters
+///
+/// Binding already keeps values out of the SQL text, so quotes and
+/// semicolons are allowed here; use the validator with
+/// [`SecurityConfig::for_sql_params`](crate::SecurityConfig::for_sql_params)
+/// to keep screening for injection payloads that could resurface in
+/// dynamically built queries. NUL characters are rejected because most
+/// databases refuse them in text columns.
+///
+/// ```rust
+/// use huginn::sql::SqlParamValidator;
+/// use huginn::{sanitize_and_validate, SecurityConfig};
+///
+/// let config = SecurityConfig::for_sql_params();
+/// let validator = SqlParamValidator::new();
+///
+/// assert!(sanitize_and_validate("O'Brien; Sons", &validator, &config).is_ok());
+/// assert!(sanitize_and_validate("x' OR '1'='1", &validator, &config).is_err());
+/// assert!(sanitize_and_validate("a\0b", &validator, &config).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SqlParamValidator {
+    max_len: usize,
+    allow_wildcards: bool,
+}
+
+impl Default for SqlParamValidator {
+    fn default() -> Self {
+        Self {
+            max_len: 4096,
+            allow_wildcards: true,
+        }
+    }
+}
+
+impl SqlParamValidator {
+    /// Creates validator allowing values up to 4096 bytes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum value length in bytes
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Allows the `LIKE` wildcards `%` and `_`
+    ///
+    /// Wildcards are allowed by default; reject them for values bound into
+    /// `LIKE` patterns that are not escaped with [`escape_like`].
+    pub fn allow_wildcards(mut self, allow: bool) -> Self {
+        self.allow_wildcards = allow;
+        self
+    }
+}
+
+impl Validator<String> for SqlParamValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        let invalid =
+            |hint: &str| ValidationError::invalid_format(self.target_type()).with_hint(hint);
+
+        if input.len() > self.max_len {
+            return Err(invalid(&format!(
+                "shorten the value to at most {} bytes",
+                self.max_len
+            )));
+        }
+        if input.contains('\0') {
+            return Err(invalid("remove NUL characters"));
+        }
+        if !self.allow_wildcards && input.contains(['%', '_']) {
+            return Err(invalid("remove the wildcards '%' and '_'"));
+        }
+
+        Ok(input.to_string())
+    }
+
+    fn target_type(&self) -> &'static str {
+        "SQL parameter"
+    }
+}
+
+/// Validates table, column and other identifiers interpolated into SQL text
+///
+/// Identifiers cannot be bound as parameters, so they must be restricted to
+/// a safe shape: ASCII letters, digits and `_`, not starting with a digit,
+/// at most 63 bytes (the PostgreSQL limit) and not a reserved word. When an
+/// allowlist is set, only its entries are accepted, compared ignoring ASCII
+/// case.
+///
+/// ```rust
+/// use huginn::sql::SqlIdentifierValidator;
+/// use huginn::Validator;
+///
+/// let validator = SqlIdentifierValidator::new().allow_qualified(true);
+/// assert!(validator.validate("public.orders").is_ok());
+/// assert!(validator.validate("orders; DROP TABLE users").is_err());
+/// assert!(validator.validate("select").is_err());
+///
+/// let columns = SqlIdentifierValidator::new().with_allowed(["name", "created_at"]);
+/// assert!(columns.validate("created_at").is_ok());
+/// assert!(columns.validate("password_hash").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlIdentifierValidator {
+    max_len: usize,
+    allow_qualified: bool,
+    allow_reserved: bool,
+    allowed: Vec<String>,
+}
+
+impl Default for SqlIdentifierValidator {
+    fn default() -> Self {
+        Self {
+            max_len: 63,
+            allow_qualified: false,
+            allow_reserved: false,
+            allowed: Vec::new(),
+        }
+    }
+}
+
+impl SqlIdentifierValidator {
+    /// Creates validator for unqualified identifiers up to 63 bytes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum length of each identifier part in bytes
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Allows qualified names such as `schema.table`
+    pub fn allow_qualified(mut self, allow: bool) -> Self {
+        self.allow_qualified = allow;
+        self
+    }
+
+    /// Allows reserved words such as `order` or `group`
+    pub fn allow_reserved(mut self, allow: bool) -> Self {
+        self.allow_reserved = allow;
+        self
+    }
+
+    /// Restricts identifiers to the given names
+    pub fn with_allowed<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    fn check_part(&self, part: &str) -> Result<(), String> {
+        if part.is_empty() {
+            return Err("provide a name for every part of the identifier".into());
+        }
+        if part.len() > self.max_len {
+            return Err(format!(
+                "shorten the identifier to at most {} bytes",
+                self.max_len
+            ));
+        }
+        if part.starts_with(|c: char| c.is_ascii_digit())
+            || !part.bytes().all(|b| b == b'_' || b.is_ascii_alphanumeric())
+        {
+            return Err("use only ASCII letters, digits and '_', not starting with a digit".into());
+        }
+        if !self.allow_reserved
+            && RESERVED_WORDS
+                .iter()
+                .any(|word| word.eq_ignore_ascii_case(part))
+        {
+            return Err("choose a name that is not a reserved word".into());
+        }
+        Ok(())
+    }
+}
+
+impl Validator<String> for SqlIdentifierValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        let invalid =
+            |hint: String| ValidationError::invalid_format(self.target_type()).with_hint(hint);
+
+        if !self.allowed.is_empty() {
+            return match self
+                .allowed
+                .iter()
+                .find(|name| name.eq_ignore_ascii_case(input))
+            {
+                Some(name) => Ok(name.clone()),
+                None => Err(invalid(format!("use one of: {}", self.allowed.join(", ")))),
+            };
+        }
+
+        let parts: Vec<&str> = input.split('.').collect();
+        if parts.len() > 1 && !self.allow_qualified {
+            return Err(invalid("use an unqualified name".into()));
+        }
+        if parts.len() > 3 {
+            return Err(invalid("qualify the name with at most two parts".into()));
+        }
+        for part in parts {
+            self.check_part(part).map_err(invalid)?;
+        }
+
+        Ok(input.to_string())
+    }
+
+    fn target_type(&self) -> &'static str {
+        "SQL identifier"
+    }
+}
+
+/// Picks the allowlisted identifier matching the input, ignoring ASCII case
+///
+/// Returns the allowlist entry rather than the input, so only strings written
+/// by the developer reach the SQL text. Use this for `ORDER BY` columns and
+/// similar user-selected identifiers.
+///
+/// ```rust
+/// use huginn::sql::select_identifier;
+///
+/// const SORTABLE: &[&str] = &["name", "created_at"];
+/// assert_eq!(select_identifier("CREATED_AT", SORTABLE).unwrap(), "created_at");
+/// assert!(select_identifier("name; --", SORTABLE).is_err());
+/// ```
+pub fn select_identifier<'a>(input: &str, allowed: &[&'a str]) -> Result<&'a str, ValidationError> {
+    allowed
+        .iter()
+        .find(|name| name.eq_ignore_ascii_case(input))
+        .copied()
+        .ok_or_else(|| {
+            ValidationError::invalid_format("SQL identifier")
+                .with_hint(format!("use one of: {}", allowed.join(", ")))
+        })
+}
+
+/// Escapes `%`, `_` and `\` so a value matches literally inside a `LIKE` pattern
+///
+/// The result uses `\` as the escape character; add `ESCAPE '\'` to the
+/// query on databases where it is not the default.
+///
+/// ```rust
+/// use huginn::sql::escape_like;
+///
+/// assert_eq!(escape_like("100%_off"), r"100\%\_off");
+/// assert_eq!(escape_like("plain"), "plain");
+/// ```
+pub fn escape_like(input: &str) -> Cow<'_, str> {
+    if !input.contains(['%', '_', '\\']) {
+        return Cow::Borrowed(input);
+    }
+    let mut escaped = String::with_capacity(input.len() + 8);
+    for c in input.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    Cow::Owned(escaped)
+}
@@ -0,0 +1,235 @@
+use super::metrics::{MetricsSink, ValidationMetrics};
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Number of processed and rejected inputs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Counts {
+    /// Inputs processed
+    pub total: u64,
+    /// Inputs rejected
+    pub rejected: u64,
+}
+
+impl Counts {
+    fn add(&mut self, other: Counts) {
+        self.total += other.total;
+        self.rejected += other.rejected;
+    }
+}
+
+/// Counters of one time slice of the window
+#[derive(Debug)]
+struct Bucket {
+    started: Instant,
+    totals: Counts,
+    patterns: HashMap<String, u64>,
+    validators: HashMap<&'static str, Counts>,
+    fields: HashMap<String, Counts>,
+}
+
+impl Bucket {
+    fn new(started: Instant) -> Self {
+        Self {
+            started,
+            totals: Counts::default(),
+            patterns: HashMap::new(),
+            validators: HashMap::new(),
+            fields: HashMap::new(),
+        }
+    }
+}
+
+/// In-process statistics over a rolling time window, fed by a [`Pipeline`](crate::Pipeline)
+///
+/// Counts processed and rejected inputs per validator and per field, and
+/// hits per blocked pattern. The field is the pipeline label set with
+/// [`PipelineBuilder::with_context`](crate::pipeline::PipelineBuilder::with_context),
+/// so share one `Stats` between the pipelines of several fields to compare
+/// them. Counters are kept in slices of one minute and dropped once they
+/// fall out of the window, so memory stays bounded.
+///
+/// ```rust
+/// use huginn::stats::Stats;
+/// use huginn::validators::FilenameValidator;
+/// use huginn::{Pipeline, SecurityConfig};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// let stats = Arc::new(Stats::new(Duration::from_secs(3600)));
+/// let pipeline = Pipeline::builder(SecurityConfig::default())
+///     .with_context("avatar")
+///     .with_metrics(stats.clone())
+///     .build();
+///
+/// let validator = FilenameValidator::new();
+/// let _ = pipeline.process("me.png", &validator);
+/// let _ = pipeline.process("../../etc/passwd", &validator);
+/// let _ = pipeline.process("..%2F..%2Fboot.ini", &validator);
+///
+/// let hour = Duration::from_secs(3600);
+/// assert_eq!(stats.top_patterns(10, hour), [("path_traversal".to_string(), 2)]);
+/// assert_eq!(stats.field("avatar", hour).rejected, 2);
+/// assert_eq!(stats.totals(hour).total, 3);
+/// ```
+#[derive(Debug)]
+pub struct Stats {
+    window: Duration,
+    slice: Duration,
+    buckets: Mutex<VecDeque<Bucket>>,
+}
+
+impl Stats {
+    /// Creates collector keeping the given window of history in one-minute slices
+    pub fn new(window: Duration) -> Self {
+        Self::with_slice(window, Duration::from_secs(60))
+    }
+
+    /// Creates collector keeping the given window of history in slices of the given length
+    ///
+    /// Queries are rounded to whole slices, so shorter slices give more
+    /// precise windows at the cost of more memory.
+    pub fn with_slice(window: Duration, slice: Duration) -> Self {
+        Self {
+            window,
+            slice: slice.max(Duration::from_millis(1)),
+            buckets: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Returns the number of processed and rejected inputs in the last `period`
+    pub fn totals(&self, period: Duration) -> Counts {
+        let mut counts = Counts::default();
+        self.for_each_bucket(period, |bucket| counts.add(bucket.totals));
+        counts
+    }
+
+    /// Returns the `n` patterns with the most hits in the last `period`, most hits first
+    pub fn top_patterns(&self, n: usize, period: Duration) -> Vec<(String, u64)> {
+        let mut hits: HashMap<String, u64> = HashMap::new();
+        self.for_each_bucket(period, |bucket| {
+            for (name, count) in &bucket.patterns {
+                *hits.entry(name.clone()).or_default() += count;
+            }
+        });
+        top(hits, n, |hits| *hits)
+    }
+
+    /// Returns counts per validator in the last `period`, most rejections first
+    pub fn validators(&self, period: Duration) -> Vec<(&'static str, Counts)> {
+        let mut counts: HashMap<&'static str, Counts> = HashMap::new();
+        self.for_each_bucket(period, |bucket| {
+            for (validator, c) in &bucket.validators {
+                counts.entry(validator).or_default().add(*c);
+            }
+        });
+        top(counts, usize::MAX, |c| c.rejected)
+    }
+
+    /// Returns counts per field in the last `period`, most rejections first
+    pub fn fields(&self, period: Duration) -> Vec<(String, Counts)> {
+        let mut counts: HashMap<String, Counts> = HashMap::new();
+        self.for_each_bucket(period, |bucket| {
+            for (field, c) in &bucket.fields {
+                counts.entry(field.clone()).or_default().add(*c);
+            }
+        });
+        top(counts, usize::MAX, |c| c.rejected)
+    }
+
+    /// Returns counts of a single field in the last `period`
+    pub fn field(&self, field: &str, period: Duration) -> Counts {
+        let mut counts = Counts::default();
+        self.for_each_bucket(period, |bucket| {
+            if let Some(c) = bucket.fields.get(field) {
+                counts.add(*c);
+            }
+        });
+        counts
+    }
+
+    /// Drops all counters
+    pub fn reset(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<Bucket>> {
+        self.buckets.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Calls `f` with every slice that started within the last `period`
+    fn for_each_bucket(&self, period: Duration, mut f: impl FnMut(&Bucket)) {
+        let now = Instant::now();
+        let mut buckets = self.lock();
+        self.expire(&mut buckets, now);
+        buckets
+            .iter()
+            .filter(|bucket| now.duration_since(bucket.started) < period.max(self.slice))
+            .for_each(&mut f);
+    }
+
+    /// Drops slices that fell out of the window
+    fn expire(&self, buckets: &mut VecDeque<Bucket>, now: Instant) {
+        while buckets
+            .front()
+            .is_some_and(|bucket| now.duration_since(bucket.started) >= self.window)
+        {
+            buckets.pop_front();
+        }
+    }
+}
+
+impl MetricsSink for Stats {
+    fn record(&self, metrics: &ValidationMetrics<'_>) {
+        let now = Instant::now();
+        let mut buckets = self.lock();
+        self.expire(&mut buckets, now);
+        if buckets
+            .back()
+            .is_none_or(|bucket| now.duration_since(bucket.started) >= self.slice)
+        {
+            buckets.push_back(Bucket::new(now));
+        }
+        let bucket = buckets.back_mut().expect("bucket was just ensured");
+
+        let counts = Counts {
+            total: 1,
+            rejected: u64::from(!metrics.is_accepted()),
+        };
+        bucket.totals.add(counts);
+        bucket
+            .validators
+            .entry(metrics.validator)
+            .or_default()
+            .add(counts);
+        if let Some(field) = metrics.context {
+            match bucket.fields.get_mut(field) {
+                Some(c) => c.add(counts),
+                None => {
+                    bucket.fields.insert(field.to_string(), counts);
+                }
+            }
+        }
+        for pattern in metrics.patterns() {
+            match bucket.patterns.get_mut(&pattern.name) {
+                Some(hits) => *hits += 1,
+                None => {
+                    bucket.patterns.insert(pattern.name.clone(), 1);
+                }
+            }
+        }
+    }
+}
+
+/// Sorts entries by descending rank, then key, keeping the first `n`
+fn top<K: Ord + Hash, V>(map: HashMap<K, V>, n: usize, rank: impl Fn(&V) -> u64) -> Vec<(K, V)> {
+    let mut entries: Vec<(K, V)> = map.into_iter().collect();
+    entries.sort_by(|(ka, va), (kb, vb)| rank(vb).cmp(&rank(va)).then_with(|| ka.cmp(kb)));
+    entries.truncate(n);
+    entries
+}
@@ -0,0 +1,197 @@
+use super::{
+    config::SecurityConfig,
+    error::{ValidationError, ValidationErrors},
+    validation::{sanitize_and_validate, Validator},
+};
+use std::fmt::Debug;
+
+/// Types whose fields can be validated recursively as a unit
+///
+/// ```rust
+/// use huginn::{SecurityConfig, Validator, ValidationError};
+/// use huginn::structs::{validate_struct, FieldReport, Validate};
+///
+/// struct Sku;
+///
+/// impl Validator<String> for Sku {
+///     fn validate(&self, input: &str) -> Result<String, ValidationError> {
+///         if input.chars().all(|c| c.is_ascii_alphanumeric()) {
+///             Ok(input.to_string())
+///         } else {
+///             Err(ValidationError::invalid_format(self.target_type()))
+///         }
+///     }
+///
+///     fn target_type(&self) -> &'static str {
+///         "sku"
+///     }
+/// }
+///
+/// struct LineItem {
+///     sku: String,
+/// }
+///
+/// impl Validate for LineItem {
+///     fn validate_fields(&self, config: &SecurityConfig, report: &mut FieldReport) {
+///         report.field("sku", &self.sku, &Sku, config);
+///     }
+/// }
+///
+/// struct Order {
+///     items: Vec<LineItem>,
+/// }
+///
+/// impl Validate for Order {
+///     fn validate_fields(&self, config: &SecurityConfig, report: &mut FieldReport) {
+///         report.nested("items", &self.items, config);
+///     }
+/// }
+///
+/// let order = Order {
+///     items: vec![LineItem { sku: "A1".into() }, LineItem { sku: "B-2".into() }],
+/// };
+/// let errors = validate_struct(&order, &SecurityConfig::default()).unwrap_err();
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors.for_field("items[1].sku").count(), 1);
+/// ```
+pub trait Validate {
+    /// Validates every field, recording failures in the report
+    fn validate_fields(&self, config: &SecurityConfig, report: &mut FieldReport);
+}
+
+impl<V: Validate + ?Sized> Validate for &V {
+    fn validate_fields(&self, config: &SecurityConfig, report: &mut FieldReport) {
+        (**self).validate_fields(config, report);
+    }
+}
+
+impl<V: Validate + ?Sized> Validate for Box<V> {
+    fn validate_fields(&self, config: &SecurityConfig, report: &mut FieldReport) {
+        (**self).validate_fields(config, report);
+    }
+}
+
+impl<V: Validate> Validate for Option<V> {
+    fn validate_fields(&self, config: &SecurityConfig, report: &mut FieldReport) {
+        if let Some(value) = self {
+            value.validate_fields(config, report);
+        }
+    }
+}
+
+impl<V: Validate> Validate for [V] {
+    fn validate_fields(&self, config: &SecurityConfig, report: &mut FieldReport) {
+        for (index, item) in self.iter().enumerate() {
+            report.path.push(Segment::Index(index));
+            item.validate_fields(config, report);
+            report.path.pop();
+        }
+    }
+}
+
+impl<V: Validate> Validate for Vec<V> {
+    fn validate_fields(&self, config: &SecurityConfig, report: &mut FieldReport) {
+        self.as_slice().validate_fields(config, report);
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Field(String),
+    Index(usize),
+}
+
+/// Collects field errors while walking a structure
+#[derive(Debug, Default)]
+pub struct FieldReport {
+    path: Vec<Segment>,
+    errors: ValidationErrors,
+}
+
+impl FieldReport {
+    /// Creates new empty report
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs a field through the pipeline, returning the validated value on success
+    pub fn field<T>(
+        &mut self,
+        name: &str,
+        value: &str,
+        validator: &impl Validator<T>,
+        config: &SecurityConfig,
+    ) -> Option<T>
+    where
+        T: Debug + Send + Sync,
+    {
+        match sanitize_and_validate(value, validator, config) {
+            Ok(result) => Some(result.cleaned),
+            Err(error) => {
+                self.add_error(name, error);
+                None
+            }
+        }
+    }
+
+    /// Validates a nested structure under the given field name
+    pub fn nested(&mut self, name: &str, value: &impl Validate, config: &SecurityConfig) {
+        self.path.push(Segment::Field(name.to_string()));
+        value.validate_fields(config, self);
+        self.path.pop();
+    }
+
+    /// Records an error for a field of the current structure
+    pub fn add_error(&mut self, name: &str, error: ValidationError) {
+        self.path.push(Segment::Field(name.to_string()));
+        let path = self.current_path();
+        self.path.pop();
+        self.errors.push_field(path, error);
+    }
+
+    /// Returns true if no errors were recorded
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns recorded errors keyed by dotted field path (e.g. `items[2].sku`)
+    pub fn errors(&self) -> &ValidationErrors {
+        &self.errors
+    }
+
+    /// Consumes the report, returning recorded errors
+    pub fn into_errors(self) -> ValidationErrors {
+        self.errors
+    }
+
+    fn current_path(&self) -> String {
+        let mut path = String::new();
+        for segment in &self.path {
+            match segment {
+                Segment::Field(name) => {
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(name);
+                }
+                Segment::Index(index) => path.push_str(&format!("[{}]", index)),
+            }
+        }
+        path
+    }
+}
+
+/// Validates a structure and all nested structures in one call
+pub fn validate_struct(
+    value: &impl Validate,
+    config: &SecurityConfig,
+) -> Result<(), ValidationErrors> {
+    let mut report = FieldReport::new();
+    value.validate_fields(config, &mut report);
+
+    if report.is_empty() {
+        Ok(())
+    } else {
+        Err(report.into_errors())
+    }
+}
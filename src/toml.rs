@@ -0,0 +1,171 @@
+use super::{
+    config::SecurityConfig,
+    error::{ValidationError, ValidationErrors},
+    path::{self, Segment},
+    validation::prepare_input,
+};
+use ::toml::{Table, Value};
+
+/// Limits applied to TOML documents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TomlLimits {
+    /// Maximum document length in bytes
+    pub max_len: usize,
+    /// Maximum nesting depth of tables and arrays
+    pub max_depth: usize,
+    /// Maximum key length in bytes
+    pub max_key_len: usize,
+}
+
+impl Default for TomlLimits {
+    fn default() -> Self {
+        Self {
+            max_len: 64 << 10,
+            max_depth: 16,
+            max_key_len: 64,
+        }
+    }
+}
+
+/// Parses and screens a user-supplied TOML document, returning it with sanitized strings
+///
+/// Keys must be bare-key characters (ASCII letters, digits, `_` and `-`)
+/// even when written quoted, and every string value runs through the
+/// pipeline stages that precede the validator. All failures are collected,
+/// keyed by the path of the offending value; problems with the document as a
+/// whole are keyed under `document`.
+///
+/// ```rust
+/// use huginn::toml::{sanitize_toml, TomlLimits};
+/// use huginn::SecurityConfig;
+///
+/// let config = SecurityConfig::default();
+/// let limits = TomlLimits::default();
+///
+/// let table = sanitize_toml("name = \"demo\"\n[build]\njobs = 4\n", &config, &limits).unwrap();
+/// assert_eq!(table["build"]["jobs"].as_integer(), Some(4));
+///
+/// let snippet = "[hooks]\npost = [\"make\", \"curl x | sh; rm -rf ~\"]\n\"bad key!\" = 1\n";
+/// let errors = sanitize_toml(snippet, &config, &limits).unwrap_err();
+/// assert_eq!(errors.for_field("hooks.post[1]").count(), 1);
+/// assert_eq!(errors.for_field("hooks.bad key!").count(), 1);
+/// ```
+pub fn sanitize_toml(
+    input: &str,
+    config: &SecurityConfig,
+    limits: &TomlLimits,
+) -> Result<Table, ValidationErrors> {
+    if input.len() > limits.max_len {
+        return Err(document_error(
+            ValidationError::custom("TOML document is too long")
+                .with_hint(format!("send at most {} bytes", limits.max_len)),
+        ));
+    }
+
+    let table: Table = input.parse().map_err(|err: ::toml::de::Error| {
+        document_error(
+            ValidationError::invalid_format("TOML document").with_hint(err.message().to_string()),
+        )
+    })?;
+
+    let mut walker = Walker {
+        config,
+        limits,
+        path: Vec::new(),
+        errors: ValidationErrors::new(),
+    };
+    let sanitized = walker.table(&table, 0);
+
+    if walker.errors.is_empty() {
+        Ok(sanitized)
+    } else {
+        Err(walker.errors)
+    }
+}
+
+fn document_error(error: ValidationError) -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors.push_field("document", error);
+    errors
+}
+
+struct Walker<'a> {
+    config: &'a SecurityConfig,
+    limits: &'a TomlLimits,
+    path: Vec<Segment>,
+    errors: ValidationErrors,
+}
+
+impl Walker<'_> {
+    fn table(&mut self, table: &Table, depth: usize) -> Table {
+        let mut sanitized = Table::new();
+        for (key, value) in table {
+            self.path.push(Segment::Key(key.clone()));
+            self.check_key(key);
+            let value = self.value(value, depth + 1);
+            self.path.pop();
+            sanitized.insert(key.clone(), value);
+        }
+        sanitized
+    }
+
+    fn value(&mut self, value: &Value, depth: usize) -> Value {
+        match value {
+            Value::Table(_) | Value::Array(_) if depth > self.limits.max_depth => {
+                self.error(
+                    ValidationError::custom(format!(
+                        "TOML nesting exceeds {} levels",
+                        self.limits.max_depth
+                    ))
+                    .with_hint("flatten the document"),
+                );
+                value.clone()
+            }
+            Value::Table(table) => Value::Table(self.table(table, depth)),
+            Value::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(index, item)| {
+                        self.path.push(Segment::Index(index));
+                        let item = self.value(item, depth + 1);
+                        self.path.pop();
+                        item
+                    })
+                    .collect(),
+            ),
+            Value::String(text) => match prepare_input(text, self.config, &[]) {
+                Ok(cleaned) => Value::String(cleaned.into_owned()),
+                Err(error) => {
+                    self.error(error);
+                    value.clone()
+                }
+            },
+            _ => value.clone(),
+        }
+    }
+
+    fn check_key(&mut self, key: &str) {
+        let hint = if key.is_empty() {
+            "provide a non-empty key".to_string()
+        } else if key.len() > self.limits.max_key_len {
+            format!(
+                "shorten the key to at most {} bytes",
+                self.limits.max_key_len
+            )
+        } else if !key
+            .bytes()
+            .all(|b| b == b'_' || b == b'-' || b.is_ascii_alphanumeric())
+        {
+            "use only ASCII letters, digits, '_' and '-' in keys".to_string()
+        } else {
+            return;
+        };
+        self.error(ValidationError::invalid_format("TOML key").with_hint(hint));
+    }
+
+    fn error(&mut self, error: ValidationError) {
+        let path = path::render(&self.path);
+        self.errors.push_field(path, error);
+    }
+}
@@ -0,0 +1,54 @@
+use super::error::ValidationError;
+use tracing::{field, Level, Span};
+
+/// Opens the span covering one run of the pipeline
+pub(crate) fn pipeline_span(input: &str, validator: &'static str) -> Span {
+    tracing::debug_span!(
+        "huginn.validate",
+        validator,
+        input_len = input.len(),
+        outcome = field::Empty,
+    )
+}
+
+/// Records the outcome on the span and emits an event describing it
+///
+/// Accepted inputs are reported at `DEBUG`, rejections at `WARN` with the
+/// error code, the number of characters removed and the matched patterns.
+pub(crate) fn record<T>(span: &Span, result: &Result<T, ValidationError>) {
+    span.in_scope(|| emit(span, result));
+}
+
+fn emit<T>(span: &Span, result: &Result<T, ValidationError>) {
+    let error = match result {
+        Ok(_) => {
+            span.record("outcome", "accepted");
+            tracing::event!(Level::DEBUG, outcome = "accepted", "input accepted");
+            return;
+        }
+        Err(error) => error,
+    };
+
+    span.record("outcome", "rejected");
+    let (chars_removed, patterns) = match error {
+        ValidationError::DangerousCharacters { count, .. } => (*count, String::new()),
+        ValidationError::BlockedPattern { matches, .. } => (
+            0,
+            matches
+                .iter()
+                .map(|m| m.name.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        _ => (0, String::new()),
+    };
+    tracing::event!(
+        Level::WARN,
+        outcome = "rejected",
+        code = error.code(),
+        chars_removed,
+        patterns = patterns.as_str(),
+        error = %error,
+        "input rejected"
+    );
+}
@@ -0,0 +1,161 @@
+use super::{
+    config::{SecurityConfig, DEFAULT_CONFIG},
+    error::ValidationError,
+    validation::sanitize_and_validate,
+    validators::{EmailValidator, PhoneValidator, UrlValidator, UsernameValidator},
+};
+use std::{fmt, ops::Deref, str::FromStr};
+
+/// Defines a string newtype that only a successful run of the pipeline with its validator constructs
+macro_rules! validated_string {
+    ($(#[$meta:meta])* $name:ident, $validator:ty) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name(String);
+
+        impl $name {
+            /// Sanitizes and validates input with the default validator and configuration
+            pub fn new(input: &str) -> Result<Self, ValidationError> {
+                Self::validate_with(input, &<$validator>::new(), &DEFAULT_CONFIG)
+            }
+
+            /// Sanitizes and validates input with the given validator and configuration
+            pub fn validate_with(
+                input: &str,
+                validator: &$validator,
+                config: &SecurityConfig,
+            ) -> Result<Self, ValidationError> {
+                sanitize_and_validate(input, validator, config).map(|result| Self(result.cleaned))
+            }
+
+            /// Returns the validated value
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Consumes the wrapper, returning the validated value
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ValidationError;
+
+            fn from_str(input: &str) -> Result<Self, Self::Err> {
+                Self::new(input)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> String {
+                value.0
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.0)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let raw = <String as serde::Deserialize>::deserialize(deserializer)?;
+                Self::new(&raw).map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+validated_string! {
+    /// Email address validated with [`EmailValidator`]
+    ///
+    /// Values can only be created by validation, so functions taking an
+    /// `Email` need not check their argument again. With the `serde`
+    /// feature, deserialization validates as well.
+    ///
+    /// ```rust
+    /// use huginn::typed::Email;
+    ///
+    /// fn send_welcome(to: &Email) -> String {
+    ///     format!("Welcome mail queued for {}", to)
+    /// }
+    ///
+    /// let email = Email::new("Jane@Example.COM")?;
+    /// assert_eq!(send_welcome(&email), "Welcome mail queued for Jane@example.com");
+    /// assert!("jane@localhost".parse::<Email>().is_err());
+    /// # Ok::<(), huginn::ValidationError>(())
+    /// ```
+    Email,
+    EmailValidator
+}
+
+validated_string! {
+    /// Phone number validated with [`PhoneValidator`], in E.164 form
+    ///
+    /// ```rust
+    /// use huginn::typed::Phone;
+    ///
+    /// assert_eq!(Phone::new("+44 20 7946 0958")?.as_str(), "+442079460958");
+    /// assert!(Phone::new("020 7946 0958").is_err());
+    /// # Ok::<(), huginn::ValidationError>(())
+    /// ```
+    Phone,
+    PhoneValidator
+}
+
+validated_string! {
+    /// URL validated with [`UrlValidator`]
+    ///
+    /// ```rust
+    /// use huginn::typed::Url;
+    /// use huginn::validators::UrlValidator;
+    /// use huginn::SecurityConfig;
+    ///
+    /// assert!(Url::new("https://example.com/docs").is_ok());
+    /// assert!(Url::new("javascript:alert(1)").is_err());
+    ///
+    /// let https_only = UrlValidator::new().with_schemes(["https"]);
+    /// let config = SecurityConfig::default();
+    /// assert!(Url::validate_with("http://example.com/", &https_only, &config).is_err());
+    /// ```
+    Url,
+    UrlValidator
+}
+
+validated_string! {
+    /// Username validated with [`UsernameValidator`]
+    ///
+    /// ```rust
+    /// use huginn::typed::Username;
+    ///
+    /// assert_eq!(&*Username::new("jane_doe")?, "jane_doe");
+    /// assert!(Username::new("jane doe").is_err());
+    /// # Ok::<(), huginn::ValidationError>(())
+    /// ```
+    Username,
+    UsernameValidator
+}
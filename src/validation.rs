@@ -1,23 +1,95 @@
-use super::{config::SecurityConfig, error::ValidationError};
-use std::{borrow::Cow, fmt::Debug};
-use urlencoding::decode;
+#[cfg(feature = "async")]
+use super::pipeline::AsyncSanitizer;
+#[cfg(feature = "tracing")]
+use super::trace;
+use super::{
+    config::SecurityConfig,
+    error::{CharPosition, PatternMatch, ValidationError, ValidationErrors},
+};
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::{fmt::Debug, time::Duration};
+use smallvec::SmallVec;
+#[cfg(feature = "async")]
+use tokio_util::sync::CancellationToken;
 
 /// Result of input processing with sanitized data
+///
+/// The original input is borrowed from the caller; use [`SanitizedInput::into_owned`]
+/// to detach the result from the input's lifetime.
 #[derive(Debug, Clone)]
-pub struct SanitizedInput<T> {
+pub struct SanitizedInput<'a, T> {
     /// Original user input
-    pub original: String,
+    pub original: Cow<'a, str>,
     /// Cleaned and validated data
     pub cleaned: T,
 }
 
+impl<T> SanitizedInput<'_, T> {
+    /// Converts into a result owning its original input
+    pub fn into_owned(self) -> SanitizedInput<'static, T> {
+        SanitizedInput {
+            original: Cow::Owned(self.original.into_owned()),
+            cleaned: self.cleaned,
+        }
+    }
+}
+
+/// Details of how an accepted input was processed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SanitizationReport {
+    /// Stages that changed the input, in order
+    ///
+    /// Stages are `url_decode`, the hook stages `before_sanitize`,
+    /// `after_sanitize` and `before_validate`, and `async_sanitize` for
+    /// asynchronous sanitizers. Forbidden characters never appear here,
+    /// since accepted input contains none.
+    pub modified_by: Vec<&'static str>,
+    /// Warning-level patterns that matched the screened input without rejecting it
+    pub warnings: Vec<PatternMatch>,
+    /// Time spent processing the input, including the validator
+    ///
+    /// Always zero without the `std` feature, which provides the clock.
+    pub duration: Duration,
+}
+
+impl SanitizationReport {
+    /// Returns true if the input was URL-decoded
+    pub fn was_decoded(&self) -> bool {
+        self.modified_by.contains(&"url_decode")
+    }
+
+    /// Returns true if any stage changed the input
+    pub fn was_modified(&self) -> bool {
+        !self.modified_by.is_empty()
+    }
+}
+
+/// Result of input processing with a report of how the input was processed
+#[derive(Debug, Clone)]
+pub struct SanitizedInputDetailed<'a, T> {
+    /// Original input and validated value
+    pub input: SanitizedInput<'a, T>,
+    /// How the input was processed
+    pub report: SanitizationReport,
+}
+
 /// Trait for thread-safe validators with async support
-#[async_trait::async_trait]
+///
+/// `validate_async` is only available with the `async` feature.
+#[cfg_attr(feature = "async", async_trait::async_trait)]
 pub trait Validator<T>: Send + Sync {
     /// Validates and converts cleaned input synchronously
     fn validate(&self, input: &str) -> Result<T, ValidationError>;
 
     /// Validates and converts cleaned input asynchronously
+    #[cfg(feature = "async")]
     async fn validate_async(&self, input: &str) -> Result<T, ValidationError> {
         Ok(self.validate(input)?)
     }
@@ -26,93 +98,703 @@ pub trait Validator<T>: Send + Sync {
     fn target_type(&self) -> &'static str;
 }
 
+/// Middleware hook invoked at each stage of the pipeline
+///
+/// Every method has a no-op default, so implementors only override the stages
+/// they care about. Returning an error vetoes the input and aborts processing.
+pub trait PipelineHook: Send + Sync {
+    /// Called with the decoded input before forbidden characters are checked
+    fn before_sanitize(&self, _input: &mut String) -> Result<(), ValidationError> {
+        Ok(())
+    }
+
+    /// Called with the sanitized input before blocked patterns are checked
+    fn after_sanitize(&self, _cleaned: &mut String) -> Result<(), ValidationError> {
+        Ok(())
+    }
+
+    /// Called with the screened input right before the validator runs
+    fn before_validate(&self, _cleaned: &mut String) -> Result<(), ValidationError> {
+        Ok(())
+    }
+
+    /// Called with the validator outcome before the result is returned
+    fn after_validate(
+        &self,
+        _cleaned: &str,
+        _outcome: Result<(), &ValidationError>,
+    ) -> Result<(), ValidationError> {
+        Ok(())
+    }
+}
+
+/// Type-erased validator run through the synchronous pipeline, returning the text it accepted
+#[cfg(feature = "std")]
+pub(crate) type BoxedCheck =
+    Box<dyn Fn(&str, &SecurityConfig) -> Result<String, ValidationError> + Send + Sync>;
+
+/// Erases the output type of a validator so validators of different types can be stored together
+///
+/// The check returns the decoded and sanitized text the validator accepted,
+/// so callers can hand out exactly what was validated.
+#[cfg(feature = "std")]
+pub(crate) fn boxed_check<T, V>(validator: V) -> BoxedCheck
+where
+    T: Debug + Send + Sync,
+    V: Validator<T> + 'static,
+{
+    Box::new(move |input, config| {
+        let cleaned = prepare_input(input, config, &[])?;
+        validator.validate(&cleaned)?;
+        Ok(cleaned.into_owned())
+    })
+}
+
 /// Main processing pipeline with synchronous validation
-pub fn sanitize_and_validate<T>(
-    input: &str,
+pub fn sanitize_and_validate<'a, T>(
+    input: &'a str,
+    validator: &impl Validator<T>,
+    config: &SecurityConfig,
+) -> Result<SanitizedInput<'a, T>, ValidationError>
+where
+    T: Debug + Send + Sync,
+{
+    run_pipeline(input, validator, config, &[], None)
+}
+
+/// Main processing pipeline with asynchronous validation
+#[cfg(feature = "async")]
+pub async fn sanitize_and_validate_async<'a, T>(
+    input: &'a str,
+    validator: &impl Validator<T>,
+    config: &SecurityConfig,
+) -> Result<SanitizedInput<'a, T>, ValidationError>
+where
+    T: Debug + Send + Sync,
+{
+    run_pipeline_async(input, validator, config, &[], None, AsyncOptions::default()).await
+}
+
+/// Main processing pipeline with asynchronous validation bounded by a timeout
+///
+/// If `validate_async` does not finish in time, it is cancelled and
+/// [`ValidationError::Timeout`] is returned, so a slow external check cannot
+/// hold up the caller. Must be called within a Tokio runtime.
+///
+/// ```rust
+/// use huginn::{sanitize_and_validate_async_with_timeout, SecurityConfig, ValidationError, Validator};
+/// use std::time::Duration;
+///
+/// struct SlowLookup;
+///
+/// #[async_trait::async_trait]
+/// impl Validator<String> for SlowLookup {
+///     fn validate(&self, input: &str) -> Result<String, ValidationError> {
+///         Ok(input.to_string())
+///     }
+///
+///     async fn validate_async(&self, input: &str) -> Result<String, ValidationError> {
+///         tokio::time::sleep(Duration::from_secs(5)).await;
+///         Ok(input.to_string())
+///     }
+///
+///     fn target_type(&self) -> &'static str {
+///         "username"
+///     }
+/// }
+///
+/// # #[tokio::main] async fn main() {
+/// let config = SecurityConfig::default();
+/// let result = sanitize_and_validate_async_with_timeout("alice", &SlowLookup, &config, Duration::from_millis(10)).await;
+/// assert_eq!(result.unwrap_err(), ValidationError::timeout(Duration::from_millis(10)));
+/// # }
+/// ```
+#[cfg(feature = "async")]
+pub async fn sanitize_and_validate_async_with_timeout<'a, T>(
+    input: &'a str,
     validator: &impl Validator<T>,
     config: &SecurityConfig,
-) -> Result<SanitizedInput<T>, ValidationError>
+    timeout: Duration,
+) -> Result<SanitizedInput<'a, T>, ValidationError>
 where
     T: Debug + Send + Sync,
 {
-    let decoded = decode(input).unwrap_or(Cow::Borrowed(input));
-    let (cleaned, bad_chars) = sanitize_input(&decoded, config);
+    let limits = AsyncOptions {
+        timeout: Some(timeout),
+        ..AsyncOptions::default()
+    };
+    run_pipeline_async(input, validator, config, &[], None, limits).await
+}
+
+/// Main processing pipeline with asynchronous validation that stops when the token is cancelled
+///
+/// A cancelled token makes the in-flight `validate_async` call be dropped
+/// and [`ValidationError::Cancelled`] be returned, so shutdown or a client
+/// disconnect does not wait for slow validators.
+///
+/// ```rust
+/// use huginn::{sanitize_and_validate_async_cancellable, CancellationToken, SecurityConfig, ValidationError, Validator};
+///
+/// struct Echo;
+///
+/// impl Validator<String> for Echo {
+///     fn validate(&self, input: &str) -> Result<String, ValidationError> {
+///         Ok(input.to_string())
+///     }
+///
+///     fn target_type(&self) -> &'static str {
+///         "text"
+///     }
+/// }
+///
+/// # #[tokio::main] async fn main() {
+/// let config = SecurityConfig::default();
+/// let token = CancellationToken::new();
+/// assert!(sanitize_and_validate_async_cancellable("hi", &Echo, &config, &token).await.is_ok());
+///
+/// token.cancel();
+/// let result = sanitize_and_validate_async_cancellable("hi", &Echo, &config, &token).await;
+/// assert_eq!(result.unwrap_err(), ValidationError::Cancelled);
+/// # }
+/// ```
+#[cfg(feature = "async")]
+pub async fn sanitize_and_validate_async_cancellable<'a, T>(
+    input: &'a str,
+    validator: &impl Validator<T>,
+    config: &SecurityConfig,
+    cancel: &CancellationToken,
+) -> Result<SanitizedInput<'a, T>, ValidationError>
+where
+    T: Debug + Send + Sync,
+{
+    let limits = AsyncOptions {
+        cancel: Some(cancel),
+        ..AsyncOptions::default()
+    };
+    run_pipeline_async(input, validator, config, &[], None, limits).await
+}
 
-    if !bad_chars.is_empty() {
-        let symbols = bad_chars
-            .iter()
-            .map(|c| format!("'{}'", c))
-            .collect::<Vec<_>>()
-            .join(", ");
-        return Err(ValidationError::DangerousCharacters {
-            symbols,
-            count: bad_chars.len(),
-        });
+/// Main processing pipeline with synchronous validation, reporting how the input was processed
+///
+/// ```rust
+/// # #[cfg(feature = "std")]
+/// # {
+/// use huginn::validators::FilenameValidator;
+/// use huginn::{sanitize_and_validate_detailed, SecurityConfig};
+///
+/// let config = SecurityConfig::default();
+/// let result = sanitize_and_validate_detailed("annual%20report.pdf", &FilenameValidator::new(), &config).unwrap();
+/// assert_eq!(result.input.cleaned, "annual report.pdf");
+/// assert!(result.report.was_decoded());
+/// # }
+/// ```
+pub fn sanitize_and_validate_detailed<'a, T>(
+    input: &'a str,
+    validator: &impl Validator<T>,
+    config: &SecurityConfig,
+) -> Result<SanitizedInputDetailed<'a, T>, ValidationError>
+where
+    T: Debug + Send + Sync,
+{
+    #[cfg(feature = "std")]
+    let started = std::time::Instant::now();
+    let mut report = SanitizationReport::default();
+    let input = run_pipeline(input, validator, config, &[], Some((&mut report, None)))?;
+    #[cfg(feature = "std")]
+    {
+        report.duration = started.elapsed();
     }
+    Ok(SanitizedInputDetailed { input, report })
+}
 
-    if config.has_blocked_pattern(&cleaned) {
-        return Err(ValidationError::BlockedPattern {
-            pattern: "blocked pattern detected".to_string(),
-        });
+/// Processing pipeline that reports every failure instead of stopping at the first
+///
+/// Forbidden characters are stripped before the remaining stages run, so
+/// blocked patterns and the validator see the sanitized input.
+pub fn sanitize_and_validate_collect<'a, T>(
+    input: &'a str,
+    validator: &impl Validator<T>,
+    config: &SecurityConfig,
+) -> Result<SanitizedInput<'a, T>, ValidationErrors>
+where
+    T: Debug + Send + Sync,
+{
+    let (cleaned, positions) = sanitize_cow(decode_input(input), config);
+    let mut errors = ValidationErrors::new();
+
+    if !positions.is_empty() {
+        errors.push(dangerous_characters(positions));
     }
 
-    validator.validate(&cleaned).map(|result| SanitizedInput {
-        original: input.to_string(),
-        cleaned: result,
-    })
+    let matches = config.find_blocked_patterns(&cleaned);
+    if !matches.is_empty() {
+        errors.push(blocked_pattern(matches));
+    }
+
+    match validator.validate(&cleaned) {
+        Ok(result) if errors.is_empty() => Ok(SanitizedInput {
+            original: Cow::Borrowed(input),
+            cleaned: result,
+        }),
+        Ok(_) => Err(errors),
+        Err(error) => {
+            errors.push(error);
+            Err(errors)
+        }
+    }
 }
 
-/// Main processing pipeline with asynchronous validation
-pub async fn sanitize_and_validate_async<T>(
-    input: &str,
+/// Report to fill in while running the pipeline, with the configuration of warning-level patterns
+pub(crate) type Detail<'r> = (&'r mut SanitizationReport, Option<&'r SecurityConfig>);
+
+/// Runs the synchronous pipeline with the given hooks
+pub(crate) fn run_pipeline<'a, T>(
+    input: &'a str,
     validator: &impl Validator<T>,
     config: &SecurityConfig,
-) -> Result<SanitizedInput<T>, ValidationError>
+    hooks: &[Arc<dyn PipelineHook>],
+    detail: Option<Detail<'_>>,
+) -> Result<SanitizedInput<'a, T>, ValidationError>
 where
     T: Debug + Send + Sync,
 {
-    let decoded = decode(input).unwrap_or(Cow::Borrowed(input));
-    let (cleaned, bad_chars) = sanitize_input(&decoded, config);
-
-    if !bad_chars.is_empty() {
-        let symbols = bad_chars
-            .iter()
-            .map(|c| format!("'{}'", c))
-            .collect::<Vec<_>>()
-            .join(", ");
-        return Err(ValidationError::DangerousCharacters {
-            symbols,
-            count: bad_chars.len(),
-        });
-    }
-
-    if config.has_blocked_pattern(&cleaned) {
-        return Err(ValidationError::BlockedPattern {
-            pattern: "blocked pattern detected".to_string(),
-        });
-    }
-
-    validator
-        .validate_async(&cleaned)
-        .await
-        .map(|result| SanitizedInput {
-            original: input.to_string(),
-            cleaned: result,
+    #[cfg(feature = "tracing")]
+    let span = trace::pipeline_span(input, validator.target_type());
+    #[cfg(feature = "tracing")]
+    let _entered = span.enter();
+
+    let result = prepare_detailed(input, config, hooks, detail).and_then(|cleaned| {
+        let result = validator.validate(&cleaned);
+        finish(input, &cleaned, result, hooks)
+    });
+
+    #[cfg(feature = "tracing")]
+    trace::record(&span, &result);
+    result
+}
+
+/// Asynchronous stages and bounds of an asynchronous pipeline run
+#[cfg(feature = "async")]
+#[derive(Clone, Copy, Default)]
+pub(crate) struct AsyncOptions<'c> {
+    /// Time allowed for `validate_async`
+    pub(crate) timeout: Option<Duration>,
+    /// Token aborting asynchronous sanitizers and `validate_async` when cancelled
+    pub(crate) cancel: Option<&'c CancellationToken>,
+    /// Sanitizers run on the screened input before the validator
+    pub(crate) sanitizers: &'c [Arc<dyn AsyncSanitizer>],
+}
+
+/// Runs the asynchronous pipeline with the given hooks
+#[cfg(feature = "async")]
+pub(crate) async fn run_pipeline_async<'a, T>(
+    input: &'a str,
+    validator: &impl Validator<T>,
+    config: &SecurityConfig,
+    hooks: &[Arc<dyn PipelineHook>],
+    detail: Option<Detail<'_>>,
+    limits: AsyncOptions<'_>,
+) -> Result<SanitizedInput<'a, T>, ValidationError>
+where
+    T: Debug + Send + Sync,
+{
+    #[cfg(feature = "tracing")]
+    let span = trace::pipeline_span(input, validator.target_type());
+
+    let run = async {
+        let mut detail = detail;
+        let reborrow = detail
+            .as_mut()
+            .map(|(report, warnings)| (&mut **report, *warnings));
+        let mut cleaned = prepare_detailed(input, config, hooks, reborrow)?;
+        let report = detail.map(|(report, _)| report);
+        let result = validate_bounded(validator, &mut cleaned, report, limits).await?;
+        finish(input, &cleaned, result, hooks)
+    };
+    #[cfg(feature = "tracing")]
+    let run = tracing::Instrument::instrument(run, span.clone());
+    let result = run.await;
+
+    #[cfg(feature = "tracing")]
+    trace::record(&span, &result);
+    result
+}
+
+/// Runs the asynchronous sanitizers, then `validate_async` within the timeout,
+/// stopping early if the token is cancelled
+///
+/// The outer error comes from a sanitizer or cancellation and skips the
+/// `after_validate` hooks; the inner result is the validator outcome.
+#[cfg(feature = "async")]
+async fn validate_bounded<T>(
+    validator: &impl Validator<T>,
+    cleaned: &mut Cow<'_, str>,
+    report: Option<&mut SanitizationReport>,
+    limits: AsyncOptions<'_>,
+) -> Result<Result<T, ValidationError>, ValidationError> {
+    let validation = async {
+        if !limits.sanitizers.is_empty() {
+            let before = report.is_some().then(|| cleaned.to_string());
+            for sanitizer in limits.sanitizers {
+                sanitizer.sanitize(cleaned.to_mut()).await?;
+            }
+            if let (Some(report), Some(before)) = (report, before) {
+                if *cleaned != before {
+                    report.modified_by.push("async_sanitize");
+                }
+            }
+        }
+        Ok(match limits.timeout {
+            Some(after) => tokio::time::timeout(after, validator.validate_async(cleaned))
+                .await
+                .unwrap_or(Err(ValidationError::timeout(after))),
+            None => validator.validate_async(cleaned).await,
         })
+    };
+    match limits.cancel {
+        Some(token) => tokio::select! {
+            biased;
+            _ = token.cancelled() => Err(ValidationError::Cancelled),
+            result = validation => result,
+        },
+        None => validation.await,
+    }
 }
 
-/// Sanitizes input using iterator optimizations
-pub fn sanitize_input(input: &str, config: &SecurityConfig) -> (String, Vec<char>) {
-    let mut cleaned = String::with_capacity(input.len());
-    let mut bad_chars = Vec::with_capacity(8); // Предварительное выделение для типичного случая
-
-    input.chars().for_each(|c| {
-        if config.is_char_forbidden(&c) {
-            bad_chars.push(c);
-        } else {
-            cleaned.push(c);
+/// Decodes, sanitizes and screens input, returning the string handed to the validator
+///
+/// Without hooks, input that needs neither decoding nor stripping is passed
+/// through borrowed.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub(crate) fn prepare_input<'a>(
+    input: &'a str,
+    config: &SecurityConfig,
+    hooks: &[Arc<dyn PipelineHook>],
+) -> Result<Cow<'a, str>, ValidationError> {
+    prepare_detailed(input, config, hooks, None)
+}
+
+/// Sanitizes and screens input that was already URL-decoded, without decoding it again
+#[cfg(feature = "serde")]
+pub(crate) fn prepare_decoded<'a>(
+    input: &'a str,
+    config: &SecurityConfig,
+) -> Result<Cow<'a, str>, ValidationError> {
+    let (cleaned, positions) = sanitize_cow(Cow::Borrowed(input), config);
+    if !positions.is_empty() {
+        return Err(dangerous_characters(positions));
+    }
+    let matches = config.find_blocked_patterns(&cleaned);
+    if !matches.is_empty() {
+        return Err(blocked_pattern(matches));
+    }
+    Ok(cleaned)
+}
+
+/// Prepares input, recording modifying stages and warning-level matches in the report
+fn prepare_detailed<'a>(
+    input: &'a str,
+    config: &SecurityConfig,
+    hooks: &[Arc<dyn PipelineHook>],
+    detail: Option<Detail<'_>>,
+) -> Result<Cow<'a, str>, ValidationError> {
+    let (mut report, warnings) = match detail {
+        Some((report, warnings)) => (Some(report), warnings),
+        None => (None, None),
+    };
+    let mut decoded = decode_input(input);
+    if let Some(report) = report.as_deref_mut() {
+        if decoded != input {
+            report.modified_by.push("url_decode");
+        }
+    }
+
+    run_hooks(
+        hooks,
+        "before_sanitize",
+        &mut decoded,
+        report.as_deref_mut(),
+        |hook, text| hook.before_sanitize(text),
+    )?;
+
+    let (mut cleaned, positions) = sanitize_cow(decoded, config);
+
+    if !positions.is_empty() {
+        return Err(dangerous_characters(positions));
+    }
+
+    run_hooks(
+        hooks,
+        "after_sanitize",
+        &mut cleaned,
+        report.as_deref_mut(),
+        |hook, text| hook.after_sanitize(text),
+    )?;
+
+    let matches = config.find_blocked_patterns(&cleaned);
+    if !matches.is_empty() {
+        return Err(blocked_pattern(matches));
+    }
+
+    run_hooks(
+        hooks,
+        "before_validate",
+        &mut cleaned,
+        report.as_deref_mut(),
+        |hook, text| hook.before_validate(text),
+    )?;
+
+    if let (Some(report), Some(warnings)) = (report, warnings) {
+        report.warnings = warnings.find_blocked_patterns(&cleaned);
+    }
+    Ok(cleaned)
+}
+
+/// Runs one stage of hooks, noting the stage in the report if it changed the text
+fn run_hooks(
+    hooks: &[Arc<dyn PipelineHook>],
+    stage: &'static str,
+    text: &mut Cow<'_, str>,
+    report: Option<&mut SanitizationReport>,
+    call: impl Fn(&dyn PipelineHook, &mut String) -> Result<(), ValidationError>,
+) -> Result<(), ValidationError> {
+    if hooks.is_empty() {
+        return Ok(());
+    }
+    let before = report.is_some().then(|| text.to_string());
+    for hook in hooks {
+        call(hook.as_ref(), text.to_mut())?;
+    }
+    if let (Some(report), Some(before)) = (report, before) {
+        if *text != before {
+            report.modified_by.push(stage);
         }
+    }
+    Ok(())
+}
+
+/// URL-decodes input, skipping the decoder for input without escapes
+///
+/// Input whose escapes do not decode to UTF-8 is kept as is. `+` is not
+/// treated as a space.
+pub(crate) fn decode_input(input: &str) -> Cow<'_, str> {
+    if !input.contains('%') {
+        return Cow::Borrowed(input);
+    }
+    String::from_utf8(percent_decode(input.as_bytes())).map_or(Cow::Borrowed(input), Cow::Owned)
+}
+
+/// Decodes `%XX` escapes, copying malformed escapes through unchanged
+///
+/// Implemented here rather than with a crate so decoding also works without `std`.
+fn percent_decode(input: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::with_capacity(input.len());
+    let Ok(()) = percent_decode_with(input, |byte| {
+        decoded.push(byte);
+        Ok::<(), core::convert::Infallible>(())
     });
+    decoded
+}
+
+/// Decodes `%XX` escapes, handing each decoded byte to `push` and stopping at its first error
+pub(crate) fn percent_decode_with<E>(
+    input: &[u8],
+    mut push: impl FnMut(u8) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut i = 0;
+    while i < input.len() {
+        let escaped = match input.get(i..i + 3) {
+            Some([b'%', high, low]) => hex_value(*high).zip(hex_value(*low)),
+            _ => None,
+        };
+        match escaped {
+            Some((high, low)) => {
+                push((high << 4) | low)?;
+                i += 3;
+            }
+            None => {
+                push(input[i])?;
+                i += 1;
+            }
+        }
+    }
+    Ok(())
+}
 
+fn hex_value(digit: u8) -> Option<u8> {
+    (digit as char).to_digit(16).map(|value| value as u8)
+}
+
+/// Builds the error reported for forbidden characters
+pub(crate) fn dangerous_characters(positions: SmallVec<[CharPosition; 8]>) -> ValidationError {
+    let mut symbols = String::with_capacity(positions.len() * 5);
+    for (i, p) in positions.iter().enumerate() {
+        if i > 0 {
+            symbols.push_str(", ");
+        }
+        symbols.push('\'');
+        symbols.push(p.ch);
+        symbols.push('\'');
+    }
+    let hint = if positions.len() == 1 {
+        format!("remove the {} character", symbols)
+    } else {
+        format!("remove the {} characters", symbols)
+    };
+    ValidationError::DangerousCharacters {
+        symbols,
+        count: positions.len(),
+        positions: positions.into_vec(),
+        hint: Some(hint),
+    }
+}
+
+/// Builds the error reported for blocked patterns
+pub(crate) fn blocked_pattern(matches: Vec<PatternMatch>) -> ValidationError {
+    ValidationError::BlockedPattern {
+        hint: Some("rephrase the input to avoid the flagged content".to_string()),
+        matches,
+    }
+}
+
+/// Lets hooks observe the validator outcome and packages the result
+fn finish<'a, T>(
+    input: &'a str,
+    cleaned: &str,
+    result: Result<T, ValidationError>,
+    hooks: &[Arc<dyn PipelineHook>],
+) -> Result<SanitizedInput<'a, T>, ValidationError> {
+    for hook in hooks {
+        hook.after_validate(cleaned, result.as_ref().map(|_| ()))?;
+    }
+
+    result.map(|result| SanitizedInput {
+        original: Cow::Borrowed(input),
+        cleaned: result,
+    })
+}
+
+/// Sanitizes input using iterator optimizations
+///
+/// Pure-ASCII input, and any input when the forbidden characters are all ASCII
+/// (the default), is scanned byte by byte against a lookup table, copying clean
+/// runs in bulk. Input without forbidden characters is returned borrowed.
+pub fn sanitize_input<'a>(
+    input: &'a str,
+    config: &SecurityConfig,
+) -> (Cow<'a, str>, SmallVec<[char; 8]>) {
+    let mut bad_chars = SmallVec::new();
+    let cleaned = strip_forbidden(input, config, |ch, _, _| bad_chars.push(ch));
     (cleaned, bad_chars)
 }
+
+/// Appends sanitized input to a caller-provided buffer, returning removed characters
+///
+/// Lets hot loops reuse one buffer instead of allocating per input; the buffer
+/// is not cleared first.
+pub fn sanitize_into(
+    input: &str,
+    config: &SecurityConfig,
+    out: &mut String,
+) -> SmallVec<[char; 8]> {
+    let mut bad_chars = SmallVec::new();
+    let mut run_start = 0;
+
+    out.reserve(input.len());
+    scan_forbidden(input, config, |ch, byte_offset, _| {
+        out.push_str(&input[run_start..byte_offset]);
+        bad_chars.push(ch);
+        run_start = byte_offset + ch.len_utf8();
+    });
+    out.push_str(&input[run_start..]);
+
+    bad_chars
+}
+
+/// Sanitizes input, recording the offsets of every removed character
+pub fn sanitize_input_with_positions<'a>(
+    input: &'a str,
+    config: &SecurityConfig,
+) -> (Cow<'a, str>, SmallVec<[CharPosition; 8]>) {
+    let mut positions = SmallVec::new();
+    let cleaned = strip_forbidden(input, config, |ch, byte_offset, char_offset| {
+        positions.push(CharPosition {
+            ch,
+            byte_offset,
+            char_offset,
+        })
+    });
+    (cleaned, positions)
+}
+
+/// Sanitizes possibly owned input, reusing the owned buffer when nothing is removed
+pub(crate) fn sanitize_cow<'a>(
+    input: Cow<'a, str>,
+    config: &SecurityConfig,
+) -> (Cow<'a, str>, SmallVec<[CharPosition; 8]>) {
+    match input {
+        Cow::Borrowed(input) => sanitize_input_with_positions(input, config),
+        Cow::Owned(input) => {
+            let (cleaned, positions) = sanitize_input_with_positions(&input, config);
+            let stripped = match cleaned {
+                Cow::Borrowed(_) => None,
+                Cow::Owned(stripped) => Some(stripped),
+            };
+            (Cow::Owned(stripped.unwrap_or(input)), positions)
+        }
+    }
+}
+
+/// Removes forbidden characters, reporting each with its byte and char offsets
+///
+/// The output buffer is only allocated once the first forbidden character is found.
+fn strip_forbidden<'a>(
+    input: &'a str,
+    config: &SecurityConfig,
+    mut on_removed: impl FnMut(char, usize, usize),
+) -> Cow<'a, str> {
+    let mut cleaned: Option<String> = None;
+    let mut run_start = 0;
+
+    scan_forbidden(input, config, |ch, byte_offset, char_offset| {
+        cleaned
+            .get_or_insert_with(|| String::with_capacity(input.len()))
+            .push_str(&input[run_start..byte_offset]);
+        on_removed(ch, byte_offset, char_offset);
+        run_start = byte_offset + ch.len_utf8();
+    });
+
+    match cleaned {
+        Some(mut cleaned) => {
+            cleaned.push_str(&input[run_start..]);
+            Cow::Owned(cleaned)
+        }
+        None => Cow::Borrowed(input),
+    }
+}
+
+/// Calls `found` with each forbidden character and its byte and char offsets
+pub(crate) fn scan_forbidden(
+    input: &str,
+    config: &SecurityConfig,
+    mut found: impl FnMut(char, usize, usize),
+) {
+    if let Some(table) = config.ascii_forbidden_for(input) {
+        let mut char_offset = 0;
+        for (byte_offset, &b) in input.as_bytes().iter().enumerate() {
+            if b < 128 && table[b as usize] {
+                found(b as char, byte_offset, char_offset);
+            }
+            // Continuation bytes do not start a new character
+            if b & 0xC0 != 0x80 {
+                char_offset += 1;
+            }
+        }
+    } else {
+        for (char_offset, (byte_offset, ch)) in input.char_indices().enumerate() {
+            if config.is_char_forbidden(&ch) {
+                found(ch, byte_offset, char_offset);
+            }
+        }
+    }
+}
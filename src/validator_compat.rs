@@ -0,0 +1,315 @@
+use super::{
+    config::SecurityConfig,
+    error::{ValidationError, ValidationErrors},
+    structs::{validate_struct, FieldReport, Validate},
+    validation::{sanitize_and_validate, Validator},
+};
+use std::{borrow::Cow, fmt, fmt::Debug, fmt::Display, str::FromStr};
+use validator::ValidationErrorsKind;
+
+/// Parser turning sanitized input into the annotated type
+type Parser<T> = Box<dyn Fn(&str) -> Result<T, String> + Send + Sync>;
+
+/// Validator parsing sanitized input into a type annotated for the `validator` crate
+///
+/// The value is parsed, then its `validator::Validate` rules run; failures
+/// of either end in [`ValidationError::InvalidFormat`] with the reason as
+/// hint.
+///
+/// ```rust
+/// use huginn::validator_compat::AnnotatedValidator;
+/// use huginn::{sanitize_and_validate, SecurityConfig};
+/// use validator::Validate;
+///
+/// #[derive(Debug, Validate, serde::Deserialize)]
+/// struct SignUp {
+///     #[validate(email)]
+///     email: String,
+///     #[validate(length(min = 3, max = 20))]
+///     name: String,
+/// }
+///
+/// let validator = AnnotatedValidator::new("sign-up", |input: &str| serde_json::from_str::<SignUp>(input));
+/// let config = SecurityConfig::builder().build();
+///
+/// let ok = sanitize_and_validate(r#"{"email": "jane@example.com", "name": "Jane"}"#, &validator, &config);
+/// assert_eq!(ok.unwrap().cleaned.name, "Jane");
+///
+/// let short = sanitize_and_validate(r#"{"email": "jane@example.com", "name": "J"}"#, &validator, &config);
+/// assert_eq!(short.unwrap_err().code(), "HUG-020");
+/// ```
+pub struct AnnotatedValidator<T> {
+    target_type: &'static str,
+    parse: Parser<T>,
+}
+
+impl<T> Debug for AnnotatedValidator<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnnotatedValidator")
+            .field("target_type", &self.target_type)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: validator::Validate + 'static> AnnotatedValidator<T> {
+    /// Creates validator parsing input with the given function
+    pub fn new<F, E>(target_type: &'static str, parse: F) -> Self
+    where
+        F: Fn(&str) -> Result<T, E> + Send + Sync + 'static,
+        E: Display,
+    {
+        Self {
+            target_type,
+            parse: Box::new(move |input| parse(input).map_err(|err| err.to_string())),
+        }
+    }
+
+    /// Creates validator parsing input with [`FromStr`]
+    pub fn parsed(target_type: &'static str) -> Self
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        Self::new(target_type, str::parse::<T>)
+    }
+}
+
+impl<T: validator::Validate> Validator<T> for AnnotatedValidator<T> {
+    fn validate(&self, input: &str) -> Result<T, ValidationError> {
+        let value = (self.parse)(input).map_err(|reason| {
+            ValidationError::invalid_format(self.target_type).with_hint(reason)
+        })?;
+        match value.validate() {
+            Ok(()) => Ok(value),
+            Err(errors) => {
+                let mut reasons = Vec::new();
+                flatten(&errors, "", &mut |path, error| {
+                    reasons.push(format!("{}: {}", path, reason(error)));
+                });
+                Err(ValidationError::invalid_format(self.target_type).with_hint(reasons.join("; ")))
+            }
+        }
+    }
+
+    fn target_type(&self) -> &'static str {
+        self.target_type
+    }
+}
+
+/// Value annotated for the `validator` crate, usable as a nested huginn structure
+///
+/// Each failure is recorded under its field path, so annotated structs can
+/// be nested in types validated with [`validate_struct`].
+///
+/// ```rust
+/// use huginn::structs::{validate_struct, FieldReport, Validate};
+/// use huginn::validator_compat::Annotated;
+/// use huginn::SecurityConfig;
+///
+/// #[derive(validator::Validate)]
+/// struct Address {
+///     #[validate(length(min = 2))]
+///     city: String,
+/// }
+///
+/// struct Customer {
+///     address: Annotated<Address>,
+/// }
+///
+/// impl Validate for Customer {
+///     fn validate_fields(&self, config: &SecurityConfig, report: &mut FieldReport) {
+///         report.nested("address", &self.address, config);
+///     }
+/// }
+///
+/// let customer = Customer {
+///     address: Annotated(Address { city: "X".into() }),
+/// };
+/// let errors = validate_struct(&customer, &SecurityConfig::default()).unwrap_err();
+/// assert_eq!(errors.for_field("address.city").count(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotated<T>(pub T);
+
+impl<T: validator::Validate> Validate for Annotated<T> {
+    fn validate_fields(&self, _config: &SecurityConfig, report: &mut FieldReport) {
+        if let Err(errors) = self.0.validate() {
+            for (path, error) in to_validation_errors(&errors) {
+                // Every converted error is keyed by its field path
+                report.add_error(path.as_deref().unwrap_or_default(), error);
+            }
+        }
+    }
+}
+
+/// Value validated by huginn, usable wherever the `validator` crate expects `Validate`
+///
+/// Failures are reported under their full field path, such as
+/// `items[1].sku`, as flat field errors carrying the `HUG-` code.
+///
+/// ```rust
+/// use huginn::structs::{FieldReport, Validate};
+/// use huginn::validator_compat::HuginnFields;
+/// use huginn::SecurityConfig;
+/// use huginn::validators::FilenameValidator;
+///
+/// struct Upload {
+///     name: String,
+/// }
+///
+/// impl Validate for Upload {
+///     fn validate_fields(&self, config: &SecurityConfig, report: &mut FieldReport) {
+///         report.field("name", &self.name, &FilenameValidator::new(), config);
+///     }
+/// }
+///
+/// let upload = HuginnFields::new(Upload { name: "../etc/passwd".into() });
+/// let errors = validator::Validate::validate(&upload).unwrap_err();
+/// assert!(errors.field_errors().contains_key("name"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct HuginnFields<T> {
+    value: T,
+    config: SecurityConfig,
+}
+
+impl<T: Validate> HuginnFields<T> {
+    /// Wraps a value validated with the default configuration
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            config: SecurityConfig::default(),
+        }
+    }
+
+    /// Sets the configuration fields are screened with
+    pub fn with_config(mut self, config: SecurityConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Returns the wrapped value
+    pub fn get_ref(&self) -> &T {
+        &self.value
+    }
+
+    /// Consumes the wrapper, returning the value
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Validate> validator::Validate for HuginnFields<T> {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        validate_struct(&self.value, &self.config).map_err(|errors| {
+            let mut converted = validator::ValidationErrors::new();
+            for (field, error) in errors {
+                let field = Cow::Owned(field.unwrap_or_default());
+                let entry = converted
+                    .0
+                    .entry(field)
+                    .or_insert_with(|| ValidationErrorsKind::Field(Vec::new()));
+                if let ValidationErrorsKind::Field(list) = entry {
+                    list.push(error.into());
+                }
+            }
+            converted
+        })
+    }
+}
+
+impl From<ValidationError> for validator::ValidationError {
+    /// Converts the error keeping its `HUG-` code as code and its hint as `hint` parameter
+    fn from(error: ValidationError) -> Self {
+        let mut converted =
+            validator::ValidationError::new(error.code()).with_message(error.to_string().into());
+        if let Some(hint) = error.hint() {
+            converted.add_param("hint".into(), &hint);
+        }
+        converted
+    }
+}
+
+/// Runs a value through a huginn validator, for use in `#[validate(custom(function = ...))]`
+///
+/// ```rust
+/// use huginn::validator_compat::check;
+/// use huginn::validators::FilenameValidator;
+/// use huginn::SecurityConfig;
+/// use validator::Validate;
+///
+/// fn safe_filename(name: &str) -> Result<(), validator::ValidationError> {
+///     check(name, &FilenameValidator::new(), &SecurityConfig::default())
+/// }
+///
+/// #[derive(Validate)]
+/// struct Upload {
+///     #[validate(custom(function = "safe_filename"))]
+///     name: String,
+/// }
+///
+/// assert!(Upload { name: "report.pdf".into() }.validate().is_ok());
+/// assert!(Upload { name: "../../etc/passwd".into() }.validate().is_err());
+/// ```
+pub fn check<T>(
+    value: &str,
+    validator: &impl Validator<T>,
+    config: &SecurityConfig,
+) -> Result<(), validator::ValidationError>
+where
+    T: Debug + Send + Sync,
+{
+    sanitize_and_validate(value, validator, config)
+        .map(|_| ())
+        .map_err(Into::into)
+}
+
+/// Converts errors of the `validator` crate, keying each by its field path
+///
+/// Nested structs and lists are flattened into paths like `items[1].sku`;
+/// each failure becomes a [`ValidationError::Custom`] with the error's
+/// message, or its code if it has none. Rejected values are not echoed.
+pub fn to_validation_errors(errors: &validator::ValidationErrors) -> ValidationErrors {
+    let mut converted = ValidationErrors::new();
+    flatten(errors, "", &mut |path, error| {
+        converted.push_field(path, ValidationError::custom(reason(error)));
+    });
+    converted
+}
+
+/// Message of an error, falling back to its code
+fn reason(error: &validator::ValidationError) -> String {
+    match &error.message {
+        Some(message) => message.to_string(),
+        None => error.code.to_string(),
+    }
+}
+
+fn flatten(
+    errors: &validator::ValidationErrors,
+    prefix: &str,
+    visit: &mut impl FnMut(String, &validator::ValidationError),
+) {
+    let mut fields: Vec<_> = errors.errors().iter().collect();
+    fields.sort_by(|a, b| a.0.cmp(b.0));
+    for (field, kind) in fields {
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", prefix, field)
+        };
+        match kind {
+            ValidationErrorsKind::Field(list) => {
+                for error in list {
+                    visit(path.clone(), error);
+                }
+            }
+            ValidationErrorsKind::Struct(nested) => flatten(nested, &path, visit),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    flatten(nested, &format!("{}[{}]", path, index), visit);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use crate::{error::ValidationError, validation::Validator};
+use std::fmt;
+
+/// Digits shown unmasked at the end of a masked card number
+const VISIBLE_DIGITS: usize = 4;
+
+/// Card network, detected from the leading digits of the card number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CardNetwork {
+    /// Visa, starting with 4
+    Visa,
+    /// Mastercard, starting with 51-55 or 2221-2720
+    Mastercard,
+    /// American Express, starting with 34 or 37
+    Amex,
+    /// Discover, starting with 6011, 644-649, 65 or 622126-622925
+    Discover,
+    /// Diners Club International, starting with 300-305, 36 or 38-39
+    DinersClub,
+    /// JCB, starting with 3528-3589
+    Jcb,
+    /// UnionPay, starting with 62
+    UnionPay,
+    /// Maestro, starting with 50 or 56-69
+    Maestro,
+}
+
+/// Leading digit ranges and the card lengths each network issues, checked in order
+const NETWORKS: &[(u32, u32, CardNetwork, &[usize])] = &[
+    (4, 4, CardNetwork::Visa, &[13, 16, 19]),
+    (51, 55, CardNetwork::Mastercard, &[16]),
+    (2221, 2720, CardNetwork::Mastercard, &[16]),
+    (34, 34, CardNetwork::Amex, &[15]),
+    (37, 37, CardNetwork::Amex, &[15]),
+    (6011, 6011, CardNetwork::Discover, &[16, 17, 18, 19]),
+    (644, 649, CardNetwork::Discover, &[16, 17, 18, 19]),
+    (65, 65, CardNetwork::Discover, &[16, 17, 18, 19]),
+    (622126, 622925, CardNetwork::Discover, &[16, 17, 18, 19]),
+    (300, 305, CardNetwork::DinersClub, &[14, 15, 16, 17, 18, 19]),
+    (36, 36, CardNetwork::DinersClub, &[14, 15, 16, 17, 18, 19]),
+    (38, 39, CardNetwork::DinersClub, &[14, 15, 16, 17, 18, 19]),
+    (3528, 3589, CardNetwork::Jcb, &[16, 17, 18, 19]),
+    (62, 62, CardNetwork::UnionPay, &[16, 17, 18, 19]),
+    (
+        50,
+        50,
+        CardNetwork::Maestro,
+        &[12, 13, 14, 15, 16, 17, 18, 19],
+    ),
+    (
+        56,
+        69,
+        CardNetwork::Maestro,
+        &[12, 13, 14, 15, 16, 17, 18, 19],
+    ),
+];
+
+impl CardNetwork {
+    /// Returns the network's display name, such as `American Express`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Visa => "Visa",
+            Self::Mastercard => "Mastercard",
+            Self::Amex => "American Express",
+            Self::Discover => "Discover",
+            Self::DinersClub => "Diners Club",
+            Self::Jcb => "JCB",
+            Self::UnionPay => "UnionPay",
+            Self::Maestro => "Maestro",
+        }
+    }
+
+    /// Detects the network from the digits of a card number
+    fn detect(digits: &str) -> Option<(Self, &'static [usize])> {
+        NETWORKS.iter().find_map(|&(low, high, network, lengths)| {
+            let width = low.to_string().len();
+            let prefix: u32 = digits.get(..width)?.parse().ok()?;
+            (low..=high).contains(&prefix).then_some((network, lengths))
+        })
+    }
+}
+
+/// Validated card number with its detected network
+///
+/// Debug output shows only the masked number.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CardNumber {
+    digits: String,
+    network: Option<CardNetwork>,
+}
+
+impl CardNumber {
+    /// Returns the card number as digits only
+    pub fn number(&self) -> &str {
+        &self.digits
+    }
+
+    /// Returns the detected network, or `None` for numbers of unlisted networks
+    pub fn network(&self) -> Option<CardNetwork> {
+        self.network
+    }
+
+    /// Returns the last four digits
+    pub fn last4(&self) -> &str {
+        &self.digits[self.digits.len() - VISIBLE_DIGITS..]
+    }
+
+    /// Returns the number with all but the last four digits replaced by `*`
+    pub fn masked(&self) -> String {
+        let hidden = self.digits.len() - VISIBLE_DIGITS;
+        format!("{}{}", "*".repeat(hidden), self.last4())
+    }
+}
+
+impl fmt::Debug for CardNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CardNumber")
+            .field("masked", &self.masked())
+            .field("network", &self.network)
+            .finish()
+    }
+}
+
+/// Validates payment card numbers, returning the number with its network
+///
+/// Spaces and dashes between digits are ignored. The number must have
+/// 12-19 digits, pass the Luhn check and, if its leading digits belong to
+/// a known [`CardNetwork`], have a length that network issues. Numbers of
+/// unlisted networks are accepted with no network unless
+/// [`require_known_network`](Self::require_known_network) is set.
+///
+/// ```rust
+/// use huginn::validators::{CardNetwork, CreditCardValidator};
+/// use huginn::Validator;
+///
+/// let validator = CreditCardValidator::new();
+/// let card = validator.validate("4111 1111 1111 1111").unwrap();
+/// assert_eq!(card.network(), Some(CardNetwork::Visa));
+/// assert_eq!(card.masked(), "************1111");
+/// assert_eq!(card.number(), "4111111111111111");
+///
+/// assert_eq!(
+///     validator.validate("3782-822463-10005").unwrap().network(),
+///     Some(CardNetwork::Amex)
+/// );
+/// assert!(validator.validate("4111 1111 1111 1112").is_err());
+/// assert!(validator.validate("3782 8224 6310 0050").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CreditCardValidator {
+    require_known_network: bool,
+}
+
+impl CreditCardValidator {
+    /// Creates validator accepting numbers of any network
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejects numbers whose network is not detected
+    pub fn require_known_network(mut self) -> Self {
+        self.require_known_network = true;
+        self
+    }
+
+    fn check(&self, input: &str) -> Result<CardNumber, &'static str> {
+        let digits: String = input
+            .trim()
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '-'))
+            .collect();
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err("use only digits, spaces and dashes");
+        }
+        if !(12..=19).contains(&digits.len()) {
+            return Err("enter the full card number");
+        }
+        let network = match CardNetwork::detect(&digits) {
+            Some((_, lengths)) if !lengths.contains(&digits.len()) => {
+                return Err("check the number of digits");
+            }
+            Some((network, _)) => Some(network),
+            None if self.require_known_network => return Err("use a supported card"),
+            None => None,
+        };
+        if !luhn(&digits) {
+            return Err("check the card number for typos");
+        }
+        Ok(CardNumber { digits, network })
+    }
+}
+
+impl Validator<CardNumber> for CreditCardValidator {
+    fn validate(&self, input: &str) -> Result<CardNumber, ValidationError> {
+        self.check(input)
+            .map_err(|hint| ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "card number"
+    }
+}
+
+/// Checks the Luhn checksum of ASCII digits
+fn luhn(digits: &str) -> bool {
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(i, b)| {
+            let digit = u32::from(b - b'0');
+            match i % 2 {
+                0 => digit,
+                _ if digit > 4 => digit * 2 - 9,
+                _ => digit * 2,
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
@@ -0,0 +1,157 @@
+use crate::{error::ValidationError, validation::Validator};
+use std::collections::BTreeSet;
+
+/// Class of characters accepted by [`CharsetValidator`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum CharClass {
+    /// ASCII digits `0`-`9`
+    AsciiDigit,
+    /// ASCII letters `a`-`z` and `A`-`Z`
+    AsciiLetter,
+    /// Letters of any script
+    Alphabetic,
+    /// Digits and other numeric characters of any script
+    Numeric,
+    /// Whitespace, including non-ASCII spaces
+    Whitespace,
+    /// ASCII punctuation such as `!`, `.` and `@`
+    AsciiPunctuation,
+}
+
+impl CharClass {
+    /// Returns true if the character belongs to the class
+    pub fn contains(self, c: char) -> bool {
+        match self {
+            Self::AsciiDigit => c.is_ascii_digit(),
+            Self::AsciiLetter => c.is_ascii_alphabetic(),
+            Self::Alphabetic => c.is_alphabetic(),
+            Self::Numeric => c.is_numeric(),
+            Self::Whitespace => c.is_whitespace(),
+            Self::AsciiPunctuation => c.is_ascii_punctuation(),
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::AsciiDigit => "digits 0-9",
+            Self::AsciiLetter => "letters a-z",
+            Self::Alphabetic => "letters",
+            Self::Numeric => "digits",
+            Self::Whitespace => "spaces",
+            Self::AsciiPunctuation => "punctuation",
+        }
+    }
+}
+
+/// Validates that every character belongs to an allowed set
+///
+/// The allowlist complements the blocked patterns of [`SecurityConfig`](crate::SecurityConfig):
+/// rather than naming what is dangerous, it names everything that is legal.
+/// The set is built from [`CharClass`]es and individual characters.
+///
+/// ```rust
+/// use huginn::validators::{CharClass, CharsetValidator};
+/// use huginn::Validator;
+///
+/// let username = CharsetValidator::ascii_alphanumeric().plus("_-");
+/// assert!(username.validate("jane_doe-42").is_ok());
+/// assert!(username.validate("jane doe").is_err());
+/// assert!(username.validate("jäne").is_err());
+///
+/// let name = CharsetValidator::new()
+///     .with_class(CharClass::Alphabetic)
+///     .with_class(CharClass::Whitespace)
+///     .plus("'-.");
+/// assert!(name.validate("Zoë O'Brien-Łukasz").is_ok());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CharsetValidator {
+    classes: BTreeSet<CharClass>,
+    extra: BTreeSet<char>,
+}
+
+impl CharsetValidator {
+    /// Creates validator allowing no characters, to be extended with classes and characters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates validator allowing letters and digits of any script
+    pub fn alphanumeric() -> Self {
+        Self::new()
+            .with_class(CharClass::Alphabetic)
+            .with_class(CharClass::Numeric)
+    }
+
+    /// Creates validator allowing ASCII letters and digits
+    pub fn ascii_alphanumeric() -> Self {
+        Self::new()
+            .with_class(CharClass::AsciiLetter)
+            .with_class(CharClass::AsciiDigit)
+    }
+
+    /// Creates validator allowing ASCII digits
+    pub fn digits() -> Self {
+        Self::new().with_class(CharClass::AsciiDigit)
+    }
+
+    /// Allows every character of a class
+    pub fn with_class(mut self, class: CharClass) -> Self {
+        self.classes.insert(class);
+        self
+    }
+
+    /// Allows each of the given characters
+    pub fn plus(mut self, chars: &str) -> Self {
+        self.extra.extend(chars.chars());
+        self
+    }
+
+    /// Returns true if the character is allowed
+    pub fn allows(&self, c: char) -> bool {
+        self.extra.contains(&c) || self.classes.iter().any(|class| class.contains(c))
+    }
+
+    fn describe(&self) -> Option<String> {
+        let mut parts: Vec<String> = self
+            .classes
+            .iter()
+            .map(|class| class.description().to_string())
+            .collect();
+        if !self.extra.is_empty() {
+            parts.push(format!("{:?}", self.extra.iter().collect::<String>()));
+        }
+        let (last, rest) = parts.split_last()?;
+        Some(match rest {
+            [] => last.clone(),
+            _ => format!("{} and {}", rest.join(", "), last),
+        })
+    }
+}
+
+impl Validator<String> for CharsetValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        match input.chars().enumerate().find(|(_, c)| !self.allows(*c)) {
+            None => Ok(input.to_string()),
+            Some((position, c)) => {
+                let allowed = match self.describe() {
+                    Some(allowed) => format!("only {} are allowed", allowed),
+                    None => "no characters are allowed".to_string(),
+                };
+                Err(
+                    ValidationError::invalid_format(self.target_type()).with_hint(format!(
+                        "remove {:?} at position {}; {}",
+                        c,
+                        position + 1,
+                        allowed
+                    )),
+                )
+            }
+        }
+    }
+
+    fn target_type(&self) -> &'static str {
+        "text"
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{error::ValidationError, validation::Validator};
+
+/// Number of choices listed in the hint before the list is cut short
+const HINT_CHOICES: usize = 10;
+
+/// Validates values against a fixed set of allowed values, such as the options of a dropdown
+///
+/// Matching is exact by default; [`case_insensitive`](Self::case_insensitive)
+/// and [`trim`](Self::trim) relax it. The matching choice is returned as
+/// declared, so the output is always one of the choices.
+///
+/// ```rust
+/// use huginn::validators::ChoiceValidator;
+/// use huginn::Validator;
+///
+/// let colors = ChoiceValidator::new(["red", "green", "blue"]);
+/// assert_eq!(colors.validate("green").unwrap(), "green");
+/// assert!(colors.validate("Green").is_err());
+/// assert!(colors.validate("purple").is_err());
+///
+/// let relaxed = ChoiceValidator::new(["red", "green", "blue"]).case_insensitive().trim();
+/// assert_eq!(relaxed.validate("  Green ").unwrap(), "green");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChoiceValidator {
+    choices: Vec<String>,
+    case_insensitive: bool,
+    trim: bool,
+}
+
+impl ChoiceValidator {
+    /// Creates validator accepting exactly the given values
+    pub fn new<I, S>(choices: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            choices: choices.into_iter().map(Into::into).collect(),
+            case_insensitive: false,
+            trim: false,
+        }
+    }
+
+    /// Matches choices regardless of letter case
+    pub fn case_insensitive(mut self) -> Self {
+        self.case_insensitive = true;
+        self
+    }
+
+    /// Ignores leading and trailing whitespace of the input
+    pub fn trim(mut self) -> Self {
+        self.trim = true;
+        self
+    }
+
+    /// Returns the allowed values
+    pub fn choices(&self) -> &[String] {
+        &self.choices
+    }
+
+    fn find(&self, input: &str) -> Option<&String> {
+        let input = if self.trim { input.trim() } else { input };
+        if self.case_insensitive {
+            let input = input.to_lowercase();
+            self.choices
+                .iter()
+                .find(|choice| choice.to_lowercase() == input)
+        } else {
+            self.choices.iter().find(|choice| *choice == input)
+        }
+    }
+
+    fn hint(&self) -> String {
+        let mut listed = self
+            .choices
+            .iter()
+            .take(HINT_CHOICES)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+        if self.choices.len() > HINT_CHOICES {
+            listed.push_str(", ...");
+        }
+        format!("choose one of: {}", listed)
+    }
+}
+
+impl Validator<String> for ChoiceValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        self.find(input).cloned().ok_or_else(|| {
+            ValidationError::invalid_format(self.target_type()).with_hint(self.hint())
+        })
+    }
+
+    fn target_type(&self) -> &'static str {
+        "choice"
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{error::ValidationError, validation::Validator};
+
+/// Longest color expression accepted, in bytes
+const MAX_LEN: usize = 128;
+
+/// Validates CSS color functions such as `rgb(255 128 0 / 50%)` and `hsl(120, 50%, 40%)`
+///
+/// `rgb()`, `rgba()`, `hsl()` and `hsla()` are accepted in both the legacy
+/// comma-separated syntax and the space-separated syntax with an optional
+/// `/ alpha`. Components are range checked rather than clamped: red, green
+/// and blue lie within 0-255 or 0%-100%, hue within 0-360 degrees (as a
+/// number or with a `deg`, `grad`, `rad` or `turn` unit), saturation and
+/// lightness within 0%-100%, and alpha within 0-1 or 0%-100%.
+/// [`allow_hex`](Self::allow_hex) also accepts `#rgb`, `#rgba`, `#rrggbb` and
+/// `#rrggbbaa`. The color is returned in lowercase.
+///
+/// ```rust
+/// use huginn::validators::CssColorValidator;
+/// use huginn::Validator;
+///
+/// let validator = CssColorValidator::new();
+/// assert_eq!(validator.validate("RGB(255, 128, 0)").unwrap(), "rgb(255, 128, 0)");
+/// assert!(validator.validate("rgba(0 0 0 / 50%)").is_ok());
+/// assert!(validator.validate("hsl(210deg, 40%, 96%)").is_ok());
+/// assert!(validator.validate("rgb(256, 0, 0)").is_err());
+/// assert!(validator.validate("rgb(100%, 0, 0)").is_err());
+/// assert!(validator.validate("hsl(0, 50%, 50%, 2)").is_err());
+/// assert!(validator.validate("#ff8000").is_err());
+///
+/// assert!(CssColorValidator::new().allow_hex().validate("#FF8000").is_ok());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CssColorValidator {
+    allow_hex: bool,
+}
+
+impl CssColorValidator {
+    /// Creates validator accepting color functions only
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also accepts hexadecimal colors such as `#ff8000`
+    pub fn allow_hex(mut self) -> Self {
+        self.allow_hex = true;
+        self
+    }
+
+    fn check(&self, input: &str) -> Result<String, &'static str> {
+        if input.len() > MAX_LEN {
+            return Err("shorten the color");
+        }
+        let color = input.trim().to_ascii_lowercase();
+        if let Some(digits) = color.strip_prefix('#') {
+            if !self.allow_hex {
+                return Err("use rgb(), rgba(), hsl() or hsla()");
+            }
+            if ![3, 4, 6, 8].contains(&digits.len())
+                || !digits.bytes().all(|b| b.is_ascii_hexdigit())
+            {
+                return Err("use 3, 4, 6 or 8 hex digits after #");
+            }
+            return Ok(color);
+        }
+
+        let (function, rest) = color
+            .split_once('(')
+            .ok_or("write the color as a function such as rgb(255, 0, 0)")?;
+        let args = rest
+            .strip_suffix(')')
+            .ok_or("close the color function with )")?;
+        let kind = match function {
+            "rgb" | "rgba" => Kind::Rgb,
+            "hsl" | "hsla" => Kind::Hsl,
+            _ => return Err("use rgb(), rgba(), hsl() or hsla()"),
+        };
+
+        let (components, alpha) = split_args(args)?;
+        let components = components
+            .iter()
+            .map(|token| Value::parse(token))
+            .collect::<Option<Vec<_>>>()
+            .ok_or("write each component as a number, percentage or angle")?;
+        match kind {
+            Kind::Rgb => check_rgb(&components, args.contains(','))?,
+            Kind::Hsl => check_hsl(&components)?,
+        }
+        if let Some(alpha) = alpha {
+            match Value::parse(alpha) {
+                Some(Value::Number(n)) if (0.0..=1.0).contains(&n) => {}
+                Some(Value::Percent(p)) if (0.0..=100.0).contains(&p) => {}
+                _ => return Err("keep alpha between 0 and 1, or 0% and 100%"),
+            }
+        }
+        Ok(color)
+    }
+}
+
+impl Validator<String> for CssColorValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        self.check(input)
+            .map_err(|hint| ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "color"
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Rgb,
+    Hsl,
+}
+
+/// Component of a color function, with angles converted to degrees
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Value {
+    Number(f64),
+    Percent(f64),
+    Angle(f64),
+}
+
+impl Value {
+    fn parse(token: &str) -> Option<Self> {
+        if let Some(number) = token.strip_suffix('%') {
+            return number_of(number).map(Self::Percent);
+        }
+        const UNITS: [(&str, f64); 4] = [
+            ("deg", 1.0),
+            ("grad", 0.9),
+            ("rad", 180.0 / core::f64::consts::PI),
+            ("turn", 360.0),
+        ];
+        for (unit, degrees) in UNITS {
+            if let Some(number) = token.strip_suffix(unit) {
+                return number_of(number).map(|n| Self::Angle(n * degrees));
+            }
+        }
+        number_of(token).map(Self::Number)
+    }
+}
+
+/// Parses a CSS number: optional sign, digits and fraction, without exponent
+fn number_of(token: &str) -> Option<f64> {
+    let unsigned = token.strip_prefix(['+', '-']).unwrap_or(token);
+    let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    let valid = digits(whole)
+        && digits(fraction)
+        && !(whole.is_empty() && fraction.is_empty())
+        && !(unsigned.ends_with('.'));
+    if !valid {
+        return None;
+    }
+    token.parse().ok()
+}
+
+/// Splits the arguments into three components and an optional alpha
+fn split_args(args: &str) -> Result<(Vec<&str>, Option<&str>), &'static str> {
+    let (components, alpha) = if args.contains(',') {
+        if args.contains('/') {
+            return Err("separate components either with commas or with spaces and /");
+        }
+        let mut parts: Vec<&str> = args.split(',').map(str::trim).collect();
+        let alpha = if parts.len() == 4 { parts.pop() } else { None };
+        (parts, alpha)
+    } else {
+        let (components, alpha) = match args.split_once('/') {
+            Some((components, alpha)) => (components, Some(alpha.trim())),
+            None => (args, None),
+        };
+        (components.split_whitespace().collect(), alpha)
+    };
+    if components.len() != 3 || alpha.is_some_and(|alpha| alpha.is_empty() || alpha.contains('/')) {
+        return Err("give three components and an optional alpha");
+    }
+    Ok((components, alpha))
+}
+
+fn check_rgb(components: &[Value], legacy: bool) -> Result<(), &'static str> {
+    let numbers = components.iter().all(|c| matches!(c, Value::Number(_)));
+    let percents = components.iter().all(|c| matches!(c, Value::Percent(_)));
+    if legacy && !(numbers || percents) {
+        return Err("use either numbers or percentages for all of red, green and blue");
+    }
+    for component in components {
+        match *component {
+            Value::Number(n) if (0.0..=255.0).contains(&n) => {}
+            Value::Percent(p) if (0.0..=100.0).contains(&p) => {}
+            Value::Angle(_) => return Err("remove the angle unit from red, green and blue"),
+            _ => return Err("keep red, green and blue between 0 and 255, or 0% and 100%"),
+        }
+    }
+    Ok(())
+}
+
+fn check_hsl(components: &[Value]) -> Result<(), &'static str> {
+    let hue = match components[0] {
+        Value::Number(degrees) | Value::Angle(degrees) => degrees,
+        Value::Percent(_) => return Err("give the hue in degrees, not as a percentage"),
+    };
+    if !(0.0..=360.0).contains(&hue) {
+        return Err("keep the hue between 0 and 360 degrees");
+    }
+    for component in &components[1..] {
+        match *component {
+            Value::Percent(p) if (0.0..=100.0).contains(&p) => {}
+            Value::Percent(_) => return Err("keep saturation and lightness between 0% and 100%"),
+            _ => return Err("give saturation and lightness as percentages"),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use crate::{error::ValidationError, validation::Validator};
+
+/// Largest cookie size browsers are required to accept, in bytes
+const MAX_COOKIE_SIZE: usize = 4096;
+
+/// Validates cookie names as RFC 6265 tokens
+///
+/// ```rust
+/// use huginn::validators::CookieNameValidator;
+/// use huginn::Validator;
+///
+/// assert!(CookieNameValidator.validate("session_id").is_ok());
+/// assert!(CookieNameValidator.validate("session id").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CookieNameValidator;
+
+impl Validator<String> for CookieNameValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        if input.is_empty() {
+            return Err(ValidationError::invalid_format(self.target_type())
+                .with_hint("provide a non-empty name"));
+        }
+        if input.len() > MAX_COOKIE_SIZE {
+            return Err(too_long(self.target_type(), MAX_COOKIE_SIZE));
+        }
+        if !input.bytes().all(is_token_byte) {
+            return Err(ValidationError::invalid_format(self.target_type())
+                .with_hint("use only letters, digits and !#$%&'*+-.^_`|~"));
+        }
+        Ok(input.to_string())
+    }
+
+    fn target_type(&self) -> &'static str {
+        "cookie name"
+    }
+}
+
+/// Validates cookie values per RFC 6265
+///
+/// Only cookie-octets are accepted, optionally wrapped in double quotes,
+/// which rejects attempts to inject attributes such as `; Domain=evil`.
+/// The returned value has the quotes removed. Since the pipeline URL-decodes
+/// input first, percent-encoded separators are rejected as well.
+///
+/// ```rust
+/// use huginn::validators::CookieValueValidator;
+/// use huginn::Validator;
+///
+/// let validator = CookieValueValidator::new();
+/// assert_eq!(validator.validate("\"abc123\"").unwrap(), "abc123");
+/// assert!(validator.validate("abc; Domain=evil.example").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CookieValueValidator {
+    max_len: usize,
+}
+
+impl Default for CookieValueValidator {
+    fn default() -> Self {
+        Self {
+            max_len: MAX_COOKIE_SIZE,
+        }
+    }
+}
+
+impl CookieValueValidator {
+    /// Creates validator allowing values up to 4096 bytes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum value length in bytes
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+}
+
+impl Validator<String> for CookieValueValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        if input.len() > self.max_len {
+            return Err(too_long(self.target_type(), self.max_len));
+        }
+
+        let value = match input.strip_prefix('"') {
+            Some(rest) => rest.strip_suffix('"').ok_or_else(|| {
+                ValidationError::invalid_format(self.target_type())
+                    .with_hint("close the opening double quote")
+            })?,
+            None => input,
+        };
+
+        if let Some(b) = value.bytes().find(|&b| !is_cookie_octet(b)) {
+            let hint = match b {
+                b';' => "remove ';', which would start a cookie attribute",
+                b',' => "remove ',', which separates cookies in some parsers",
+                b' ' | b'\t' => "remove whitespace or encode the value",
+                b'\r' | b'\n' => "remove line breaks from the value",
+                _ => "encode the value, e.g. with base64url",
+            };
+            return Err(ValidationError::invalid_format(self.target_type()).with_hint(hint));
+        }
+
+        Ok(value.to_string())
+    }
+
+    fn target_type(&self) -> &'static str {
+        "cookie value"
+    }
+}
+
+/// Checks for an RFC 7230 `tchar`
+fn is_token_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+/// Checks for an RFC 6265 `cookie-octet`
+fn is_cookie_octet(b: u8) -> bool {
+    matches!(b, 0x21 | 0x23..=0x2B | 0x2D..=0x3A | 0x3C..=0x5B | 0x5D..=0x7E)
+}
+
+fn too_long(target_type: &'static str, max_len: usize) -> ValidationError {
+    ValidationError::invalid_format(target_type)
+        .with_hint(format!("shorten to at most {} bytes", max_len))
+}
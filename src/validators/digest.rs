@@ -0,0 +1,182 @@
+use crate::{error::ValidationError, validation::Validator};
+
+/// Hash algorithm whose digests [`DigestValidator`] accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DigestAlgorithm {
+    /// MD5, 16 bytes
+    Md5,
+    /// SHA-1, 20 bytes
+    Sha1,
+    /// SHA-256, 32 bytes
+    Sha256,
+    /// SHA-512, 64 bytes
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    const ALL: [Self; 4] = [Self::Md5, Self::Sha1, Self::Sha256, Self::Sha512];
+
+    /// Returns the digest length in bytes
+    pub fn output_len(self) -> usize {
+        match self {
+            Self::Md5 => 16,
+            Self::Sha1 => 20,
+            Self::Sha256 => 32,
+            Self::Sha512 => 64,
+        }
+    }
+
+    /// Returns the conventional name, such as `SHA-256`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Md5 => "MD5",
+            Self::Sha1 => "SHA-1",
+            Self::Sha256 => "SHA-256",
+            Self::Sha512 => "SHA-512",
+        }
+    }
+}
+
+/// Validates hash digests given as checksums, such as a SHA-256 of an upload
+///
+/// Digests are hexadecimal by default and returned in lowercase. Without
+/// [`for_algorithm`](Self::for_algorithm) any of the supported algorithms is
+/// accepted, told apart by length. [`allow_base64`](Self::allow_base64) also
+/// accepts standard or URL-safe base64, padded or not, returned as given.
+///
+/// ```rust
+/// use huginn::validators::{DigestAlgorithm, DigestValidator};
+/// use huginn::Validator;
+///
+/// let sha256 = DigestValidator::new().for_algorithm(DigestAlgorithm::Sha256);
+/// let hex = "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855";
+/// assert_eq!(sha256.validate(hex).unwrap(), hex.to_lowercase());
+/// assert!(sha256.validate("d41d8cd98f00b204e9800998ecf8427e").is_err());
+///
+/// let any = DigestValidator::new().allow_base64();
+/// assert_eq!(any.algorithm("1B2M2Y8AsgTpgAmY7PhCfg=="), Some(DigestAlgorithm::Md5));
+/// assert!(any.validate("not a digest").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DigestValidator {
+    algorithm: Option<DigestAlgorithm>,
+    base64: bool,
+}
+
+impl DigestValidator {
+    /// Creates validator accepting hexadecimal digests of any supported algorithm
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts only digests of the given algorithm
+    pub fn for_algorithm(mut self, algorithm: DigestAlgorithm) -> Self {
+        self.algorithm = Some(algorithm);
+        self
+    }
+
+    /// Also accepts base64-encoded digests
+    pub fn allow_base64(mut self) -> Self {
+        self.base64 = true;
+        self
+    }
+
+    /// Returns the algorithm of a valid digest, or `None` if the digest is invalid
+    pub fn algorithm(&self, input: &str) -> Option<DigestAlgorithm> {
+        self.check(input).ok().map(|(algorithm, _)| algorithm)
+    }
+
+    fn accepts(&self, len: usize) -> Option<DigestAlgorithm> {
+        match self.algorithm {
+            Some(algorithm) => Some(algorithm).filter(|a| a.output_len() == len),
+            None => DigestAlgorithm::ALL
+                .into_iter()
+                .find(|a| a.output_len() == len),
+        }
+    }
+
+    fn check(&self, input: &str) -> Result<(DigestAlgorithm, String), String> {
+        if input.is_empty() {
+            return Err("enter a checksum".to_string());
+        }
+        if input.bytes().all(|b| b.is_ascii_hexdigit()) && input.len().is_multiple_of(2) {
+            if let Some(algorithm) = self.accepts(input.len() / 2) {
+                return Ok((algorithm, input.to_ascii_lowercase()));
+            }
+        }
+        if self.base64 {
+            if let Some(algorithm) = base64_len(input).and_then(|len| self.accepts(len)) {
+                return Ok((algorithm, input.to_string()));
+            }
+        }
+        Err(self.hint())
+    }
+
+    fn hint(&self) -> String {
+        let encoding = if self.base64 { "hex or base64" } else { "hex" };
+        match self.algorithm {
+            Some(algorithm) => format!(
+                "enter a {} digest of {} {} characters",
+                algorithm.name(),
+                algorithm.output_len() * 2,
+                encoding
+            ),
+            None => format!(
+                "enter an MD5, SHA-1, SHA-256 or SHA-512 digest in {}",
+                encoding
+            ),
+        }
+    }
+}
+
+impl Validator<String> for DigestValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        self.check(input)
+            .map(|(_, digest)| digest)
+            .map_err(|hint| ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "digest"
+    }
+}
+
+/// Returns the decoded length of canonical base64, standard or URL-safe, padded or not
+fn base64_len(input: &str) -> Option<usize> {
+    let data = input.trim_end_matches('=');
+    let padding = input.len() - data.len();
+    if padding > 0 && (!input.len().is_multiple_of(4) || padding > 2) {
+        return None;
+    }
+    let standard = data
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/');
+    let url_safe = data
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+    if !(standard || url_safe) {
+        return None;
+    }
+    let sextet = |b: u8| match b {
+        b'A'..=b'Z' => b - b'A',
+        b'a'..=b'z' => b - b'a' + 26,
+        b'0'..=b'9' => b - b'0' + 52,
+        b'+' | b'-' => 62,
+        _ => 63,
+    };
+    // The bits after the last whole byte must be zero for the encoding to be canonical
+    let last = data.bytes().last().map(sextet);
+    let tail = match data.len() % 4 {
+        0 => 0,
+        2 => 1,
+        3 => 2,
+        _ => return None,
+    };
+    match (tail, last) {
+        (1, Some(bits)) if bits & 0x0f != 0 => return None,
+        (2, Some(bits)) if bits & 0x03 != 0 => return None,
+        _ => {}
+    }
+    Some(data.len() / 4 * 3 + tail)
+}
@@ -0,0 +1,125 @@
+use crate::{error::ValidationError, validation::Validator};
+use std::time::Duration;
+
+/// Longest input considered, in bytes
+const MAX_LEN: usize = 64;
+
+/// Units in the order they must appear, with their length in milliseconds
+const UNITS: [(&str, u64); 6] = [
+    ("w", 7 * 24 * 3_600_000),
+    ("d", 24 * 3_600_000),
+    ("h", 3_600_000),
+    ("m", 60_000),
+    ("s", 1_000),
+    ("ms", 1),
+];
+
+/// Validates human-written durations such as `5m30s`, `2h` or `1d 12h`
+///
+/// A duration is a sequence of whole numbers, each followed by one of the
+/// units `w`, `d`, `h`, `m`, `s` and `ms`. Units appear from largest to
+/// smallest, each at most once, optionally separated by spaces. A day is
+/// always 24 hours. [`with_max`](Self::with_max) caps the duration, which
+/// is otherwise only limited by `u64` milliseconds.
+///
+/// ```rust
+/// use huginn::validators::DurationValidator;
+/// use huginn::Validator;
+/// use std::time::Duration;
+///
+/// let validator = DurationValidator::new().with_max(Duration::from_secs(7 * 86_400));
+/// assert_eq!(validator.validate("5m30s").unwrap(), Duration::from_secs(330));
+/// assert_eq!(validator.validate("1d 12h").unwrap(), Duration::from_secs(129_600));
+/// assert_eq!(validator.validate("250ms").unwrap(), Duration::from_millis(250));
+/// assert!(validator.validate("30s5m").is_err());
+/// assert!(validator.validate("90").is_err());
+/// assert!(validator.validate("8d").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DurationValidator {
+    max: Option<Duration>,
+}
+
+impl DurationValidator {
+    /// Creates validator without a maximum
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the longest accepted duration
+    pub fn with_max(mut self, max: Duration) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    fn check(&self, input: &str) -> Result<Duration, String> {
+        if input.len() > MAX_LEN {
+            return Err("shorten the duration".to_string());
+        }
+        let mut rest = input.trim();
+        if rest.is_empty() {
+            return Err("enter a duration such as 5m30s".to_string());
+        }
+        let mut millis: u64 = 0;
+        let mut next_unit = 0;
+        while !rest.is_empty() {
+            let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+            let letters = rest[digits..]
+                .bytes()
+                .take_while(u8::is_ascii_lowercase)
+                .count();
+            let (number, unit) = (&rest[..digits], &rest[digits..digits + letters]);
+            if number.is_empty() || unit.is_empty() {
+                return Err(
+                    "write each part as a number and a unit: w, d, h, m, s or ms".to_string(),
+                );
+            }
+            let position = UNITS[next_unit..]
+                .iter()
+                .position(|(name, _)| *name == unit)
+                .ok_or("give units from largest to smallest, each once: w, d, h, m, s, ms")?;
+            let (_, unit_millis) = UNITS[next_unit + position];
+            next_unit += position + 1;
+            millis = number
+                .parse::<u64>()
+                .ok()
+                .and_then(|n| n.checked_mul(unit_millis))
+                .and_then(|part| millis.checked_add(part))
+                .ok_or("shorten the duration")?;
+            rest = rest[digits + letters..].trim_start();
+        }
+
+        let duration = Duration::from_millis(millis);
+        match self.max {
+            Some(max) if duration > max => Err(format!("use at most {}", describe(max))),
+            _ => Ok(duration),
+        }
+    }
+}
+
+impl Validator<Duration> for DurationValidator {
+    fn validate(&self, input: &str) -> Result<Duration, ValidationError> {
+        self.check(input)
+            .map_err(|hint| ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "duration"
+    }
+}
+
+/// Writes a duration in the accepted syntax, such as `1d12h`
+fn describe(duration: Duration) -> String {
+    let mut millis = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+    if millis == 0 {
+        return "0s".to_string();
+    }
+    let mut text = String::new();
+    for (name, unit_millis) in UNITS {
+        if millis >= unit_millis {
+            text.push_str(&format!("{}{}", millis / unit_millis, name));
+            millis %= unit_millis;
+        }
+    }
+    text
+}
@@ -0,0 +1,85 @@
+use super::HostnameValidator;
+use crate::{error::ValidationError, validation::Validator};
+
+/// Characters allowed in the local part besides letters, digits and dots (RFC 5322 `atext`)
+const LOCAL_SPECIALS: &str = "!#$%&'*+/=?^_`{|}~-";
+
+/// Validates email addresses in the common `local@domain` form
+///
+/// The local part must be a dot-separated run of letters, digits and the
+/// characters `!#$%&'*+/=?^_`{|}~-`, at most 64 bytes long; quoted local
+/// parts and IP address literals are rejected, since hardly any real
+/// address uses them. The domain is checked with [`HostnameValidator`] and
+/// returned in lowercase ASCII form, and the address may be at most 254
+/// bytes. The checks are syntactic only; see the `dns` feature for
+/// validators that also look the domain up.
+///
+/// ```rust
+/// use huginn::validators::EmailValidator;
+/// use huginn::Validator;
+///
+/// let validator = EmailValidator::new();
+/// assert_eq!(validator.validate("Jane.Doe+news@Example.COM").unwrap(), "Jane.Doe+news@example.com");
+/// assert!(validator.validate("jane..doe@example.com").is_err());
+/// assert!(validator.validate("jane@localhost").is_err());
+/// assert!(validator.validate("\"jane doe\"@example.com").is_err());
+/// assert!(validator.validate("jane@[192.0.2.1]").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmailValidator {
+    domain: HostnameValidator,
+}
+
+impl EmailValidator {
+    /// Creates validator requiring a fully qualified domain
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Splits a valid address into its local part and normalized domain
+    pub(crate) fn parse(&self, input: &str) -> Result<(String, String), ValidationError> {
+        let invalid =
+            |hint: &str| ValidationError::invalid_format(self.target_type()).with_hint(hint);
+
+        if input.len() > 254 {
+            return Err(invalid("shorten the address to at most 254 bytes"));
+        }
+        let (local, domain) = input
+            .rsplit_once('@')
+            .ok_or_else(|| invalid("use the form 'name@example.com'"))?;
+
+        if local.is_empty() || local.len() > 64 {
+            return Err(invalid(
+                "keep the part before '@' between 1 and 64 characters",
+            ));
+        }
+        let atom = |part: &str| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || LOCAL_SPECIALS.contains(c))
+        };
+        if !local.split('.').all(atom) {
+            return Err(invalid(
+                "use letters, digits and single dots before '@', without spaces or quotes",
+            ));
+        }
+
+        let domain = self.domain.validate(domain).map_err(|err| {
+            let hint = err.hint().unwrap_or("fix the domain").to_string();
+            invalid(&format!("fix the domain: {}", hint))
+        })?;
+        Ok((local.to_string(), domain))
+    }
+}
+
+impl Validator<String> for EmailValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        let (local, domain) = self.parse(input)?;
+        Ok(format!("{}@{}", local, domain))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "email address"
+    }
+}
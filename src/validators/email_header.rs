@@ -0,0 +1,78 @@
+use crate::pattern::Regex;
+use crate::{error::ValidationError, validation::Validator};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    /// Header names whose injection redirects or rewrites a message
+    static ref HEADER_NAME: Regex = Regex::new(
+        r"(?i)\b(bcc|cc|reply-to|content-type|content-transfer-encoding|mime-version)\s*:"
+    )
+    .unwrap();
+}
+
+/// Validates values placed into email headers such as the subject or a display name
+///
+/// Rejects CR, LF and other control characters, which would end the header
+/// and let the value add recipients or replace the body, and header-like
+/// sequences such as `Bcc:` or `Content-Type:` that some mailers unfold
+/// into headers. Values are limited to 998 bytes, the line length limit of
+/// RFC 5322.
+///
+/// ```rust
+/// use huginn::validators::EmailHeaderValidator;
+/// use huginn::Validator;
+///
+/// let validator = EmailHeaderValidator::new();
+/// assert!(validator.validate("Your order #1234 has shipped").is_ok());
+/// assert!(validator.validate("Hello\r\nBcc: victim@example.com").is_err());
+/// assert!(validator.validate("Hi Content-Type: text/html").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmailHeaderValidator {
+    max_len: usize,
+}
+
+impl Default for EmailHeaderValidator {
+    fn default() -> Self {
+        Self { max_len: 998 }
+    }
+}
+
+impl EmailHeaderValidator {
+    /// Creates validator allowing values up to 998 bytes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum value length in bytes
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+}
+
+impl Validator<String> for EmailHeaderValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        let invalid =
+            |hint: &str| ValidationError::invalid_format(self.target_type()).with_hint(hint);
+
+        if input.len() > self.max_len {
+            return Err(invalid(&format!(
+                "shorten the value to at most {} bytes",
+                self.max_len
+            )));
+        }
+        if input.chars().any(|c| c.is_control() && c != '\t') {
+            return Err(invalid("remove line breaks and control characters"));
+        }
+        if HEADER_NAME.is_match(input) {
+            return Err(invalid("remove header names such as 'Bcc:' from the value"));
+        }
+
+        Ok(input.to_string())
+    }
+
+    fn target_type(&self) -> &'static str {
+        "email header"
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{error::ValidationError, validation::Validator};
+
+/// Device names Windows reserves regardless of extension
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Validates client-supplied file names such as upload names
+///
+/// Rejects path separators, `..`, control characters, names reserved on
+/// Windows and names longer than the limit, so the name can be used as a
+/// single path component.
+///
+/// ```rust
+/// use huginn::validators::FilenameValidator;
+/// use huginn::Validator;
+///
+/// let validator = FilenameValidator::new();
+/// assert!(validator.validate("report-2024.pdf").is_ok());
+/// assert!(validator.validate("../../etc/passwd").is_err());
+/// assert!(validator.validate("CON.txt").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FilenameValidator {
+    max_len: usize,
+    allow_hidden: bool,
+}
+
+impl Default for FilenameValidator {
+    fn default() -> Self {
+        Self {
+            max_len: 255,
+            allow_hidden: false,
+        }
+    }
+}
+
+impl FilenameValidator {
+    /// Creates validator allowing names up to 255 bytes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum name length in bytes
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Allows names starting with a dot
+    pub fn allow_hidden(mut self, allow: bool) -> Self {
+        self.allow_hidden = allow;
+        self
+    }
+}
+
+impl Validator<String> for FilenameValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        let invalid =
+            |hint: &str| ValidationError::invalid_format(self.target_type()).with_hint(hint);
+
+        if input.is_empty() || input == "." || input == ".." {
+            return Err(invalid("provide a file name"));
+        }
+        if input.len() > self.max_len {
+            return Err(invalid(&format!(
+                "shorten the name to at most {} bytes",
+                self.max_len
+            )));
+        }
+        if input.contains(['/', '\\']) || input.contains("..") {
+            return Err(invalid("remove path separators and '..' from the name"));
+        }
+        if input.chars().any(|c| c.is_control() || c == ':') {
+            return Err(invalid("remove control characters and ':' from the name"));
+        }
+        if input.starts_with('.') && !self.allow_hidden {
+            return Err(invalid("remove the leading '.'"));
+        }
+        if input.ends_with([' ', '.']) {
+            return Err(invalid("remove trailing spaces and dots"));
+        }
+
+        let stem = input.split('.').next().unwrap_or(input).trim_end();
+        if RESERVED_NAMES
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(stem))
+        {
+            return Err(invalid("choose a name that is not a reserved device name"));
+        }
+
+        Ok(input.to_string())
+    }
+
+    fn target_type(&self) -> &'static str {
+        "file name"
+    }
+}
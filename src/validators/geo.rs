@@ -0,0 +1,210 @@
+use crate::{error::ValidationError, validation::Validator};
+
+/// Longest input considered, in bytes
+const MAX_LEN: usize = 128;
+
+/// Validates latitude/longitude pairs, returning `(latitude, longitude)` in decimal degrees
+///
+/// Each coordinate is either decimal, signed or followed by a hemisphere
+/// letter (`52.52`, `-13.4`, `13.4 W`), or in degrees, minutes and seconds
+/// (`52°31'12"N`, `52° 31.2′ N`, with `″` or `''` for seconds). The two
+/// coordinates are separated by a comma or whitespace, latitude first
+/// unless hemisphere letters say otherwise. Latitude must lie within
+/// −90..=90 and longitude within −180..=180.
+///
+/// The default configuration forbids `'`, `"` and `;`, so pipelines
+/// receiving DMS input with ASCII marks need a configuration allowing them;
+/// the prime marks `′` and `″` pass either way.
+///
+/// ```rust
+/// use huginn::validators::GeoCoordValidator;
+/// use huginn::Validator;
+///
+/// let validator = GeoCoordValidator::new();
+/// assert_eq!(validator.validate("52.52, 13.405").unwrap(), (52.52, 13.405));
+/// assert_eq!(validator.validate("33.9 S 18.4 E").unwrap(), (-33.9, 18.4));
+///
+/// let (lat, lon) = validator.validate(r#"40°26'46"N 79°58'56"W"#).unwrap();
+/// assert!((lat - 40.446_111).abs() < 1e-6 && (lon + 79.982_222).abs() < 1e-6);
+///
+/// assert!(validator.validate("91, 0").is_err());
+/// assert!(validator.validate("45°61'N 10°E").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GeoCoordValidator;
+
+impl GeoCoordValidator {
+    /// Creates validator
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Validator<(f64, f64)> for GeoCoordValidator {
+    fn validate(&self, input: &str) -> Result<(f64, f64), ValidationError> {
+        parse(input)
+            .map_err(|hint| ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "coordinates"
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Axis {
+    Latitude,
+    Longitude,
+}
+
+/// Coordinate in decimal degrees, with the axis its hemisphere letter names
+#[derive(Debug, Clone, Copy)]
+struct Coordinate {
+    degrees: f64,
+    axis: Option<Axis>,
+}
+
+fn parse(input: &str) -> Result<(f64, f64), &'static str> {
+    if input.len() > MAX_LEN {
+        return Err("shorten the coordinates");
+    }
+    let mut cursor = Cursor(input.trim());
+    let first = coordinate(&mut cursor)?;
+    cursor.skip_whitespace();
+    cursor.eat(&[',', ';']);
+    cursor.skip_whitespace();
+    if cursor.0.is_empty() {
+        return Err("give both latitude and longitude");
+    }
+    let second = coordinate(&mut cursor)?;
+    cursor.skip_whitespace();
+    if !cursor.0.is_empty() {
+        return Err("remove the text after the longitude");
+    }
+
+    let (latitude, longitude) = match (first.axis, second.axis) {
+        (Some(a), Some(b)) if a == b => {
+            return Err("give one latitude (N/S) and one longitude (E/W)")
+        }
+        (Some(Axis::Longitude), _) | (_, Some(Axis::Latitude)) => (second, first),
+        _ => (first, second),
+    };
+    if !(-90.0..=90.0).contains(&latitude.degrees) {
+        return Err("keep the latitude between -90 and 90 degrees");
+    }
+    if !(-180.0..=180.0).contains(&longitude.degrees) {
+        return Err("keep the longitude between -180 and 180 degrees");
+    }
+    Ok((latitude.degrees, longitude.degrees))
+}
+
+/// Parses one decimal or degrees-minutes-seconds coordinate
+fn coordinate(cursor: &mut Cursor<'_>) -> Result<Coordinate, &'static str> {
+    cursor.skip_whitespace();
+    let leading = cursor.eat(&['N', 'S', 'E', 'W', 'n', 's', 'e', 'w']);
+    cursor.skip_whitespace();
+    let sign = cursor.eat(&['-', '+', '\u{2212}']);
+
+    let whole = cursor
+        .number()
+        .ok_or("write each coordinate as a number, optionally with °, ' and \"")?;
+    let mut degrees = whole;
+    cursor.skip_whitespace();
+    if cursor.eat(&['°', 'º']).is_some() {
+        if let Some(minutes) = cursor.marked(&['\'', '′', '’']) {
+            if whole.fract() != 0.0 {
+                return Err("use whole degrees when giving minutes");
+            }
+            if minutes >= 60.0 {
+                return Err("keep minutes and seconds below 60");
+            }
+            degrees += minutes / 60.0;
+            if let Some(seconds) = cursor.seconds() {
+                if minutes.fract() != 0.0 {
+                    return Err("use whole minutes when giving seconds");
+                }
+                if seconds >= 60.0 {
+                    return Err("keep minutes and seconds below 60");
+                }
+                degrees += seconds / 3600.0;
+            }
+        }
+    }
+
+    cursor.skip_whitespace();
+    let hemisphere = match leading {
+        Some(_) => leading,
+        None => cursor.eat(&['N', 'S', 'E', 'W', 'n', 's', 'e', 'w']),
+    };
+    if hemisphere.is_some() && sign.is_some() {
+        return Err("use either a sign or a hemisphere letter");
+    }
+    let negative =
+        matches!(sign, Some('-' | '\u{2212}')) || matches!(hemisphere, Some('S' | 's' | 'W' | 'w'));
+    let axis = hemisphere.map(|letter| match letter {
+        'N' | 'n' | 'S' | 's' => Axis::Latitude,
+        _ => Axis::Longitude,
+    });
+    Ok(Coordinate {
+        degrees: if negative { -degrees } else { degrees },
+        axis,
+    })
+}
+
+/// Unparsed remainder of the input
+#[derive(Debug, Clone, Copy)]
+struct Cursor<'a>(&'a str);
+
+impl Cursor<'_> {
+    fn skip_whitespace(&mut self) {
+        self.0 = self.0.trim_start();
+    }
+
+    /// Consumes the next character if it is one of the options
+    fn eat(&mut self, options: &[char]) -> Option<char> {
+        let next = self.0.chars().next().filter(|c| options.contains(c))?;
+        self.0 = &self.0[next.len_utf8()..];
+        Some(next)
+    }
+
+    /// Consumes digits with an optional fraction
+    fn number(&mut self) -> Option<f64> {
+        let digits = |s: &str| s.bytes().take_while(u8::is_ascii_digit).count();
+        let whole = digits(self.0);
+        if whole == 0 {
+            return None;
+        }
+        let mut len = whole;
+        if self.0[len..].starts_with('.') {
+            let fraction = digits(&self.0[len + 1..]);
+            if fraction > 0 {
+                len += 1 + fraction;
+            }
+        }
+        let value = self.0[..len].parse().ok()?;
+        self.0 = &self.0[len..];
+        Some(value)
+    }
+
+    /// Consumes a number followed by one of the marks, leaving the input untouched otherwise
+    fn marked(&mut self, marks: &[char]) -> Option<f64> {
+        let mut attempt = *self;
+        attempt.skip_whitespace();
+        let value = attempt.number()?;
+        attempt.skip_whitespace();
+        attempt.eat(marks)?;
+        *self = attempt;
+        Some(value)
+    }
+
+    /// Consumes seconds marked with `"`, `″` or two apostrophes
+    fn seconds(&mut self) -> Option<f64> {
+        self.marked(&['"', '″', '”']).or_else(|| {
+            let mut attempt = *self;
+            let value = attempt.marked(&['\''])?;
+            attempt.eat(&['\''])?;
+            *self = attempt;
+            Some(value)
+        })
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{error::ValidationError, validation::Validator};
+
+/// Validates values placed into HTTP headers
+///
+/// Rejects CR, LF and other control characters that would allow header
+/// injection, values longer than the limit and, unless enabled, non-ASCII
+/// text. Optional whitespace around the value is trimmed. Pair it with
+/// [`SecurityConfig::for_headers`](crate::SecurityConfig::for_headers).
+///
+/// ```rust
+/// use huginn::validators::HeaderValueValidator;
+/// use huginn::{sanitize_and_validate, SecurityConfig};
+///
+/// let config = SecurityConfig::for_headers();
+/// let validator = HeaderValueValidator::new();
+///
+/// let location = sanitize_and_validate(" /home ", &validator, &config).unwrap();
+/// assert_eq!(location.cleaned, "/home");
+///
+/// let injected = "/home%0d%0aSet-Cookie: session=stolen";
+/// assert!(sanitize_and_validate(injected, &validator, &config).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderValueValidator {
+    max_len: usize,
+    allow_non_ascii: bool,
+}
+
+impl Default for HeaderValueValidator {
+    fn default() -> Self {
+        Self {
+            max_len: 8192,
+            allow_non_ascii: false,
+        }
+    }
+}
+
+impl HeaderValueValidator {
+    /// Creates validator allowing ASCII values up to 8 KiB
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum value length in bytes
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Allows non-ASCII characters, which many HTTP stacks reject
+    pub fn allow_non_ascii(mut self, allow: bool) -> Self {
+        self.allow_non_ascii = allow;
+        self
+    }
+}
+
+impl Validator<String> for HeaderValueValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        if input.len() > self.max_len {
+            return Err(
+                ValidationError::invalid_format(self.target_type()).with_hint(format!(
+                    "shorten the value to at most {} bytes",
+                    self.max_len
+                )),
+            );
+        }
+
+        for c in input.chars() {
+            let hint = match c {
+                '\r' | '\n' => "remove line breaks from the value",
+                '\t' => continue,
+                c if c.is_control() => "remove control characters from the value",
+                c if !c.is_ascii() && !self.allow_non_ascii => "use only ASCII characters",
+                _ => continue,
+            };
+            return Err(ValidationError::invalid_format(self.target_type()).with_hint(hint));
+        }
+
+        Ok(input.trim_matches([' ', '\t']).to_string())
+    }
+
+    fn target_type(&self) -> &'static str {
+        "header value"
+    }
+}
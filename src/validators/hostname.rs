@@ -0,0 +1,91 @@
+use crate::{error::ValidationError, validation::Validator};
+use url::Host;
+
+/// Validates DNS host names such as `mail.example.com`
+///
+/// Names are converted to their lowercase ASCII form, so internationalized
+/// names are accepted and returned in punycode. Every label must be 1 to 63
+/// letters, digits or hyphens that neither start nor end with a hyphen, and
+/// the whole name at most 253 bytes; a trailing dot is removed. IP
+/// addresses are rejected, as are names with a single label unless
+/// [`allow_single_label`](Self::allow_single_label) is set.
+///
+/// ```rust
+/// use huginn::validators::HostnameValidator;
+/// use huginn::Validator;
+///
+/// let validator = HostnameValidator::new();
+/// assert_eq!(validator.validate("Mail.Example.COM.").unwrap(), "mail.example.com");
+/// assert_eq!(validator.validate("bücher.example").unwrap(), "xn--bcher-kva.example");
+/// assert!(validator.validate("-bad-.example.com").is_err());
+/// assert!(validator.validate("under_score.example.com").is_err());
+/// assert!(validator.validate("192.168.0.1").is_err());
+/// assert!(validator.validate("intranet").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HostnameValidator {
+    single_label: bool,
+}
+
+impl HostnameValidator {
+    /// Creates validator requiring at least two labels
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts names with a single label, such as `intranet`
+    pub fn allow_single_label(mut self) -> Self {
+        self.single_label = true;
+        self
+    }
+
+    fn check(&self, input: &str) -> Result<String, String> {
+        let name = input.strip_suffix('.').unwrap_or(input);
+        if name.is_empty() {
+            return Err("enter a host name".into());
+        }
+        if name.contains(['%', '[', ']', ':', '/']) {
+            return Err("remove characters other than letters, digits, hyphens and dots".into());
+        }
+        let name = match Host::parse(name) {
+            Ok(Host::Domain(domain)) => domain,
+            Ok(_) => return Err("use a host name instead of an IP address".into()),
+            Err(err) => return Err(format!("fix the host name: {}", err)),
+        };
+
+        if name.len() > 253 {
+            return Err("shorten the host name to at most 253 bytes".into());
+        }
+        for label in name.split('.') {
+            if label.is_empty() || label.len() > 63 {
+                return Err("keep every label between 1 and 63 characters".into());
+            }
+            if !label
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+            {
+                return Err(
+                    "remove characters other than letters, digits, hyphens and dots".into(),
+                );
+            }
+            if label.starts_with('-') || label.ends_with('-') {
+                return Err("do not start or end a label with a hyphen".into());
+            }
+        }
+        if !self.single_label && !name.contains('.') {
+            return Err("use a fully qualified name such as 'example.com'".into());
+        }
+        Ok(name)
+    }
+}
+
+impl Validator<String> for HostnameValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        self.check(input)
+            .map_err(|hint| ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "host name"
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{error::ValidationError, validation::Validator};
+
+/// Validates ISBN-10 and ISBN-13 book numbers, verifying the check digit
+///
+/// Hyphens or single spaces may separate the digits, as in
+/// `978-0-306-40615-7`. By default separators are removed, so the same book
+/// always yields the same value, and a trailing `x` check digit is
+/// uppercased; [`keep_hyphens`](Self::keep_hyphens) returns the input as
+/// written instead. ISBN-13 must start with the `978` or `979` prefix.
+///
+/// ```rust
+/// use huginn::validators::IsbnValidator;
+/// use huginn::Validator;
+///
+/// let validator = IsbnValidator::new();
+/// assert_eq!(validator.validate("978-0-306-40615-7").unwrap(), "9780306406157");
+/// assert_eq!(validator.validate("0 8044 2957 x").unwrap(), "080442957X");
+/// assert!(validator.validate("978-0-306-40615-8").is_err());
+/// assert!(validator.validate("123-4-567-89012-8").is_err());
+///
+/// let upgrade = IsbnValidator::new().convert_to_isbn13();
+/// assert_eq!(upgrade.validate("0-306-40615-2").unwrap(), "9780306406157");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IsbnValidator {
+    keep_hyphens: bool,
+    isbn13_only: bool,
+    to_isbn13: bool,
+}
+
+impl IsbnValidator {
+    /// Creates validator accepting both formats and removing separators
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns valid numbers as written, with their hyphens or spaces
+    ///
+    /// Ignored for ISBN-10 input when [`convert_to_isbn13`](Self::convert_to_isbn13) is set.
+    pub fn keep_hyphens(mut self) -> Self {
+        self.keep_hyphens = true;
+        self
+    }
+
+    /// Rejects ISBN-10 unless [`convert_to_isbn13`](Self::convert_to_isbn13) is set
+    pub fn require_isbn13(mut self) -> Self {
+        self.isbn13_only = true;
+        self
+    }
+
+    /// Converts valid ISBN-10 to the equivalent `978` ISBN-13
+    pub fn convert_to_isbn13(mut self) -> Self {
+        self.to_isbn13 = true;
+        self
+    }
+
+    fn check(&self, input: &str) -> Result<String, &'static str> {
+        let digits = compact(input)?;
+        match digits.len() {
+            10 if self.isbn13_only && !self.to_isbn13 => {
+                Err("use the 13-digit ISBN starting with 978 or 979")
+            }
+            10 => {
+                if isbn10_check_digit(&digits[..9]) != digits[9] {
+                    return Err("check the digits, the ISBN-10 check digit does not match");
+                }
+                if self.to_isbn13 {
+                    let mut isbn13 = b"978".to_vec();
+                    isbn13.extend_from_slice(&digits[..9]);
+                    isbn13.push(isbn13_check_digit(&isbn13));
+                    Ok(String::from_utf8_lossy(&isbn13).into_owned())
+                } else {
+                    Ok(self.output(input, &digits))
+                }
+            }
+            13 => {
+                if !digits.starts_with(b"978") && !digits.starts_with(b"979") {
+                    return Err("start the ISBN-13 with 978 or 979");
+                }
+                if digits.contains(&b'X') {
+                    return Err("use digits only in an ISBN-13");
+                }
+                if isbn13_check_digit(&digits[..12]) != digits[12] {
+                    return Err("check the digits, the ISBN-13 check digit does not match");
+                }
+                Ok(self.output(input, &digits))
+            }
+            _ => Err("enter 10 or 13 digits"),
+        }
+    }
+
+    fn output(&self, input: &str, digits: &[u8]) -> String {
+        if self.keep_hyphens {
+            input.to_string()
+        } else {
+            String::from_utf8_lossy(digits).into_owned()
+        }
+    }
+}
+
+impl Validator<String> for IsbnValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        self.check(input)
+            .map_err(|hint| ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "ISBN"
+    }
+}
+
+/// Removes single hyphens or spaces between digits, uppercasing a final `x`
+fn compact(input: &str) -> Result<Vec<u8>, &'static str> {
+    let bytes = input.as_bytes();
+    let mut digits = Vec::with_capacity(13);
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'0'..=b'9' => digits.push(b),
+            b'x' | b'X' if i + 1 == bytes.len() => digits.push(b'X'),
+            b'-' | b' ' => {
+                let between = i > 0
+                    && bytes[i - 1].is_ascii_alphanumeric()
+                    && bytes.get(i + 1).is_some_and(u8::is_ascii_alphanumeric);
+                if !between {
+                    return Err("use single hyphens or spaces only between digits");
+                }
+            }
+            _ => {
+                return Err("use only digits, hyphens and spaces, with X as the last ISBN-10 digit")
+            }
+        }
+        if digits.len() > 13 {
+            return Err("enter 10 or 13 digits");
+        }
+    }
+    Ok(digits)
+}
+
+/// Computes the ISBN-10 check digit of the first nine digits
+fn isbn10_check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits
+        .iter()
+        .zip((2..=10).rev())
+        .map(|(d, weight)| u32::from(d - b'0') * weight)
+        .sum();
+    match (11 - sum % 11) % 11 {
+        10 => b'X',
+        check => b'0' + check as u8,
+    }
+}
+
+/// Computes the ISBN-13 check digit of the first twelve digits
+fn isbn13_check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits
+        .iter()
+        .zip([1, 3].into_iter().cycle())
+        .map(|(d, weight)| u32::from(d - b'0') * weight)
+        .sum();
+    b'0' + ((10 - sum % 10) % 10) as u8
+}
@@ -0,0 +1,96 @@
+use crate::{error::ValidationError, validation::Validator};
+use std::borrow::Cow;
+
+/// Validates values placed into LDAP search filters, escaping them per RFC 4515
+///
+/// The validated value has `*`, `(`, `)`, `\` and NUL replaced by their
+/// `\XX` hex escapes, so it can be inserted into a filter such as
+/// `(uid={})` without changing its structure. Pair it with a configuration
+/// that enables [`PatternCategory::LdapInjection`](crate::PatternCategory::LdapInjection)
+/// to reject obvious filter rewrites outright.
+///
+/// ```rust
+/// use huginn::validators::LdapFilterValidator;
+/// use huginn::Validator;
+///
+/// let validator = LdapFilterValidator::new();
+/// assert_eq!(validator.validate("jdoe").unwrap(), "jdoe");
+/// assert_eq!(validator.validate("*)(uid=*").unwrap(), r"\2a\29\28uid=\2a");
+///
+/// let prefix = LdapFilterValidator::new().allow_wildcards(true);
+/// assert_eq!(prefix.validate("jdo*").unwrap(), "jdo*");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LdapFilterValidator {
+    max_len: usize,
+    allow_wildcards: bool,
+}
+
+impl Default for LdapFilterValidator {
+    fn default() -> Self {
+        Self {
+            max_len: 256,
+            allow_wildcards: false,
+        }
+    }
+}
+
+impl LdapFilterValidator {
+    /// Creates validator allowing values up to 256 bytes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum value length in bytes, before escaping
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Leaves `*` unescaped so the value can be used as a substring pattern
+    pub fn allow_wildcards(mut self, allow: bool) -> Self {
+        self.allow_wildcards = allow;
+        self
+    }
+
+    /// Escapes a value for use in an LDAP filter as RFC 4515 requires
+    pub fn escape(input: &str) -> Cow<'_, str> {
+        escape_filter(input, true)
+    }
+}
+
+fn escape_filter(input: &str, escape_wildcards: bool) -> Cow<'_, str> {
+    let needs_escape =
+        |c: char| matches!(c, '(' | ')' | '\\' | '\0') || (escape_wildcards && c == '*');
+    if !input.contains(needs_escape) {
+        return Cow::Borrowed(input);
+    }
+    let mut escaped = String::with_capacity(input.len() + 8);
+    for c in input.chars() {
+        if needs_escape(c) {
+            escaped.push_str(&format!("\\{:02x}", c as u32));
+        } else {
+            escaped.push(c);
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+impl Validator<String> for LdapFilterValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        if input.len() > self.max_len {
+            return Err(
+                ValidationError::invalid_format(self.target_type()).with_hint(format!(
+                    "shorten the value to at most {} bytes",
+                    self.max_len
+                )),
+            );
+        }
+
+        Ok(escape_filter(input, !self.allow_wildcards).into_owned())
+    }
+
+    fn target_type(&self) -> &'static str {
+        "LDAP filter value"
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{error::ValidationError, validation::Validator};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Unit in which [`LengthValidator`] measures input
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LengthUnit {
+    /// UTF-8 bytes, matching storage limits such as a `VARCHAR` byte size
+    Bytes,
+    /// Unicode scalar values
+    #[default]
+    Chars,
+    /// Extended grapheme clusters, matching what users perceive as characters
+    Graphemes,
+}
+
+impl LengthUnit {
+    /// Measures the input in this unit
+    pub fn measure(self, input: &str) -> usize {
+        match self {
+            Self::Bytes => input.len(),
+            Self::Chars => input.chars().count(),
+            Self::Graphemes => input.graphemes(true).count(),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Bytes => "bytes",
+            Self::Chars | Self::Graphemes => "characters",
+        }
+    }
+}
+
+/// Validates that input length lies within a range
+///
+/// Length is measured in characters by default; [`measured_in`](Self::measured_in)
+/// switches to bytes or grapheme clusters, so an emoji with skin tone or a
+/// letter with combining accents counts as one.
+///
+/// ```rust
+/// use huginn::validators::{LengthUnit, LengthValidator};
+/// use huginn::Validator;
+///
+/// let validator = LengthValidator::new().with_min(2).with_max(5);
+/// assert!(validator.validate("héllo").is_ok());
+/// assert!(validator.validate("h").is_err());
+/// assert!(validator.validate("hello!").is_err());
+///
+/// let family = "👨‍👩‍👧";
+/// assert!(LengthValidator::new().with_max(1).validate(family).is_err());
+/// let graphemes = LengthValidator::new().with_max(1).measured_in(LengthUnit::Graphemes);
+/// assert!(graphemes.validate(family).is_ok());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LengthValidator {
+    min: usize,
+    max: Option<usize>,
+    unit: LengthUnit,
+}
+
+impl LengthValidator {
+    /// Creates validator without limits, counting characters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the minimum length
+    pub fn with_min(mut self, min: usize) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Sets the maximum length
+    pub fn with_max(mut self, max: usize) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Sets the unit lengths are measured in
+    pub fn measured_in(mut self, unit: LengthUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    fn check(&self, input: &str) -> Result<(), String> {
+        let length = self.unit.measure(input);
+        if length < self.min {
+            return Err(format!("use at least {} {}", self.min, self.unit.name()));
+        }
+        match self.max {
+            Some(max) if length > max => Err(format!("use at most {} {}", max, self.unit.name())),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Validator<String> for LengthValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        self.check(input)
+            .map(|()| input.to_string())
+            .map_err(|hint| ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "text"
+    }
+}
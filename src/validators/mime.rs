@@ -0,0 +1,145 @@
+use crate::{error::ValidationError, validation::Validator};
+
+/// Longest media type accepted, in bytes, parameters included
+const MAX_LEN: usize = 512;
+
+/// Longest type or subtype name allowed by RFC 6838
+const MAX_NAME_LEN: usize = 127;
+
+/// Validates media types such as `image/png` or `text/plain; charset=utf-8`
+///
+/// Type and subtype follow the RFC 6838 naming rules and are returned in
+/// lowercase. Parameters are accepted unless
+/// [`reject_parameters`](Self::reject_parameters) is set, and are returned
+/// as `; name=value` with lowercase names; quoted values may not contain
+/// escapes. Once any type is allowed with [`allow`](Self::allow), all
+/// others are rejected.
+///
+/// The default configuration forbids `;` and `"`, so pipelines receiving
+/// parameters need a configuration allowing them.
+///
+/// ```rust
+/// use huginn::validators::MimeTypeValidator;
+/// use huginn::Validator;
+///
+/// let validator = MimeTypeValidator::new();
+/// assert_eq!(validator.validate("Image/PNG").unwrap(), "image/png");
+/// assert_eq!(
+///     validator.validate("text/plain;Charset=UTF-8").unwrap(),
+///     "text/plain; charset=UTF-8"
+/// );
+/// assert!(validator.validate("image").is_err());
+/// assert!(validator.validate("image/png jpeg").is_err());
+///
+/// let images = MimeTypeValidator::new().allow("image/*").allow("application/pdf");
+/// assert!(images.validate("image/webp").is_ok());
+/// assert!(images.validate("application/pdf").is_ok());
+/// assert!(images.validate("text/html").is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MimeTypeValidator {
+    allowed: Vec<String>,
+    reject_parameters: bool,
+}
+
+impl MimeTypeValidator {
+    /// Creates validator accepting any well-formed media type
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows a media type; `image/*` allows every subtype and `*/*` every type
+    pub fn allow(mut self, media_type: &str) -> Self {
+        self.allowed.push(media_type.to_ascii_lowercase());
+        self
+    }
+
+    /// Rejects media types carrying parameters such as `charset`
+    pub fn reject_parameters(mut self) -> Self {
+        self.reject_parameters = true;
+        self
+    }
+
+    /// Returns true if the lowercase `type/subtype` is allowed
+    fn allows(&self, essence: &str) -> bool {
+        self.allowed.is_empty()
+            || self.allowed.iter().any(|allowed| match allowed.as_str() {
+                "*/*" => true,
+                _ => match allowed.strip_suffix("/*") {
+                    Some(top) => essence.split('/').next() == Some(top),
+                    None => allowed == essence,
+                },
+            })
+    }
+
+    fn check(&self, input: &str) -> Result<String, String> {
+        if input.len() > MAX_LEN {
+            return Err("shorten the media type".to_string());
+        }
+        let mut parts = input.split(';');
+        let essence = parts.next().unwrap_or_default().trim();
+        let valid = essence
+            .split_once('/')
+            .is_some_and(|(top, sub)| is_name(top) && is_name(sub));
+        if !valid {
+            return Err("write the media type as type/subtype, e.g. image/png".to_string());
+        }
+
+        let mut normalized = essence.to_ascii_lowercase();
+        if !self.allows(&normalized) {
+            return Err(format!("use one of: {}", self.allowed.join(", ")));
+        }
+        for parameter in parts {
+            if self.reject_parameters {
+                return Err("remove the parameters after ';'".to_string());
+            }
+            let (name, value) = parameter
+                .trim()
+                .split_once('=')
+                .filter(|(name, value)| is_token(name) && is_value(value))
+                .ok_or("write parameters as name=value")?;
+            normalized.push_str("; ");
+            normalized.push_str(&name.to_ascii_lowercase());
+            normalized.push('=');
+            normalized.push_str(value);
+        }
+        Ok(normalized)
+    }
+}
+
+impl Validator<String> for MimeTypeValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        self.check(input)
+            .map_err(|hint| ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "media type"
+    }
+}
+
+/// Checks an RFC 6838 type or subtype name
+fn is_name(name: &str) -> bool {
+    let mut bytes = name.bytes();
+    name.len() <= MAX_NAME_LEN
+        && bytes.next().is_some_and(|b| b.is_ascii_alphanumeric())
+        && bytes.all(|b| b.is_ascii_alphanumeric() || b"!#$&-^_.+".contains(&b))
+}
+
+/// Checks an RFC 2045 token, used for parameter names and unquoted values
+fn is_token(token: &str) -> bool {
+    !token.is_empty()
+        && token
+            .bytes()
+            .all(|b| b.is_ascii_graphic() && !b"()<>@,;:\\\"/[]?=".contains(&b))
+}
+
+/// Checks a parameter value, either a token or a quoted string without escapes
+fn is_value(value: &str) -> bool {
+    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(quoted) => quoted
+            .bytes()
+            .all(|b| (b.is_ascii_graphic() || b == b' ') && b != b'"' && b != b'\\'),
+        None => is_token(value),
+    }
+}
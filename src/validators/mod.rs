@@ -0,0 +1,68 @@
+mod card;
+mod charset;
+mod choice;
+mod color;
+mod cookie;
+mod digest;
+mod duration;
+mod email;
+mod email_header;
+mod filename;
+mod geo;
+mod header;
+mod hostname;
+mod isbn;
+mod ldap;
+mod length;
+mod mime;
+mod money;
+mod phone;
+mod port;
+mod postal;
+mod redirect;
+mod shell;
+mod slug;
+mod url;
+mod username;
+#[cfg(feature = "semver")]
+mod version;
+#[cfg(feature = "wordlist")]
+mod wordlist;
+#[cfg(feature = "xml")]
+mod xml;
+mod xpath;
+
+pub use card::{CardNetwork, CardNumber, CreditCardValidator};
+pub use charset::{CharClass, CharsetValidator};
+pub use choice::ChoiceValidator;
+pub use color::CssColorValidator;
+pub use cookie::{CookieNameValidator, CookieValueValidator};
+pub use digest::{DigestAlgorithm, DigestValidator};
+pub use duration::DurationValidator;
+pub use email::EmailValidator;
+pub use email_header::EmailHeaderValidator;
+pub use filename::FilenameValidator;
+pub use geo::GeoCoordValidator;
+pub use header::HeaderValueValidator;
+pub use hostname::HostnameValidator;
+pub use isbn::IsbnValidator;
+pub use ldap::LdapFilterValidator;
+pub use length::{LengthUnit, LengthValidator};
+pub use mime::MimeTypeValidator;
+pub use money::{Amount, MoneyValidator};
+pub use phone::PhoneValidator;
+pub use port::PortValidator;
+pub use postal::PostalCodeValidator;
+pub use redirect::RedirectTargetValidator;
+pub use shell::ShellArgValidator;
+pub(crate) use shell::SHELL_METACHARS;
+pub use slug::{slugify, SlugValidator};
+pub use url::UrlValidator;
+pub use username::UsernameValidator;
+#[cfg(feature = "semver")]
+pub use version::VersionReqValidator;
+#[cfg(feature = "wordlist")]
+pub use wordlist::{WordlistError, WordlistValidator};
+#[cfg(feature = "xml")]
+pub use xml::XmlValidator;
+pub use xpath::XPathLiteralValidator;
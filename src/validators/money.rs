@@ -0,0 +1,208 @@
+use crate::{error::ValidationError, validation::Validator};
+use std::fmt;
+
+/// Longest input considered, in bytes
+const MAX_LEN: usize = 64;
+
+/// Most fraction digits an [`Amount`] can carry while keeping `i64` range useful
+const MAX_PRECISION: u32 = 9;
+
+/// Fixed-point amount: `units` in multiples of 10^-`scale`
+///
+/// ```rust
+/// use huginn::validators::Amount;
+///
+/// let amount = Amount::new(-1250, 2);
+/// assert_eq!(amount.to_string(), "-12.50");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Amount {
+    units: i64,
+    scale: u32,
+}
+
+impl Amount {
+    /// Creates amount of `units` × 10^-`scale`, such as cents for a scale of 2
+    pub fn new(units: i64, scale: u32) -> Self {
+        Self { units, scale }
+    }
+
+    /// Returns the amount in its smallest units, such as cents
+    pub fn units(self) -> i64 {
+        self.units
+    }
+
+    /// Returns the number of fraction digits
+    pub fn scale(self) -> u32 {
+        self.scale
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.units < 0 { "-" } else { "" };
+        let magnitude = self.units.unsigned_abs();
+        let Some(divisor) = 10u64.checked_pow(self.scale) else {
+            return write!(f, "{}{}e-{}", sign, magnitude, self.scale);
+        };
+        write!(f, "{}{}", sign, magnitude / divisor)?;
+        if self.scale > 0 {
+            let width = self.scale as usize;
+            write!(f, ".{:0width$}", magnitude % divisor, width = width)?;
+        }
+        Ok(())
+    }
+}
+
+/// Validates monetary amounts, returning an exact [`Amount`] instead of a float
+///
+/// Amounts are plain decimal numbers such as `1234.50`, with at most the
+/// configured number of fraction digits (2 by default) and no exponent,
+/// `inf`, `NaN`, sign or leading zeros; nothing is rounded. The decimal
+/// separator is `.` unless changed, and a group separator such as `,` may
+/// be enabled, in which case groups must hold three digits. The returned
+/// amount always carries the configured precision as its scale.
+///
+/// ```rust
+/// use huginn::validators::{Amount, MoneyValidator};
+/// use huginn::Validator;
+///
+/// let validator = MoneyValidator::new();
+/// assert_eq!(validator.validate("12.5").unwrap(), Amount::new(1250, 2));
+/// assert!(validator.validate("0.125").is_err());
+/// assert!(validator.validate("1e3").is_err());
+/// assert!(validator.validate("-5").is_err());
+///
+/// let european = MoneyValidator::new()
+///     .with_decimal_separator(',')
+///     .with_group_separator('.')
+///     .allow_negative();
+/// assert_eq!(european.validate("-1.234,56").unwrap().units(), -123_456);
+/// assert!(european.validate("12.34,56").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoneyValidator {
+    decimal_separator: char,
+    group_separator: Option<char>,
+    precision: u32,
+    allow_negative: bool,
+}
+
+impl Default for MoneyValidator {
+    fn default() -> Self {
+        Self {
+            decimal_separator: '.',
+            group_separator: None,
+            precision: 2,
+            allow_negative: false,
+        }
+    }
+}
+
+impl MoneyValidator {
+    /// Creates validator for non-negative amounts with up to two decimals and `.` as separator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the character separating whole units from the fraction
+    pub fn with_decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Accepts a character grouping the whole units in thousands, such as `,` or a space
+    pub fn with_group_separator(mut self, separator: char) -> Self {
+        self.group_separator = Some(separator);
+        self
+    }
+
+    /// Sets the number of fraction digits, at most 9
+    pub fn with_precision(mut self, precision: u32) -> Self {
+        self.precision = precision.min(MAX_PRECISION);
+        self
+    }
+
+    /// Accepts amounts with a leading `-`
+    pub fn allow_negative(mut self) -> Self {
+        self.allow_negative = true;
+        self
+    }
+
+    fn check(&self, input: &str) -> Result<Amount, String> {
+        if input.len() > MAX_LEN {
+            return Err("shorten the amount".to_string());
+        }
+        let (negative, unsigned) = match input.strip_prefix('-') {
+            Some(rest) if self.allow_negative => (true, rest),
+            Some(_) => return Err("enter an amount of zero or more".to_string()),
+            None => (false, input),
+        };
+        let (whole, fraction) = match unsigned.split_once(self.decimal_separator) {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (unsigned, None),
+        };
+        let whole = self.whole_digits(whole).ok_or_else(|| self.hint())?;
+        let fraction = fraction.unwrap_or_default();
+        if fraction.len() > self.precision as usize {
+            return Err(match self.precision {
+                0 => "enter a whole amount".to_string(),
+                precision => format!("use at most {} decimals", precision),
+            });
+        }
+        if !fraction.bytes().all(|b| b.is_ascii_digit())
+            || (fraction.is_empty() && unsigned.contains(self.decimal_separator))
+        {
+            return Err(self.hint());
+        }
+
+        let scale = 10i64.pow(self.precision);
+        let fraction_units = format!("{:0<width$}", fraction, width = self.precision as usize);
+        let units = whole
+            .parse::<i64>()
+            .ok()
+            .and_then(|whole| whole.checked_mul(scale))
+            .and_then(|units| units.checked_add(fraction_units.parse().unwrap_or(0)))
+            .ok_or("enter a smaller amount")?;
+        Ok(Amount::new(
+            if negative { -units } else { units },
+            self.precision,
+        ))
+    }
+
+    /// Returns the digits of the whole part with group separators removed
+    fn whole_digits(&self, whole: &str) -> Option<String> {
+        let groups: Vec<&str> = match self.group_separator {
+            Some(separator) => whole.split(separator).collect(),
+            None => vec![whole],
+        };
+        let (first, rest) = groups.split_first()?;
+        let valid = !first.is_empty()
+            && first.bytes().all(|b| b.is_ascii_digit())
+            && !(first.len() > 1 && first.starts_with('0'))
+            && (rest.is_empty() || (first.len() <= 3 && *first != "0"))
+            && rest
+                .iter()
+                .all(|group| group.len() == 3 && group.bytes().all(|b| b.is_ascii_digit()));
+        valid.then(|| groups.concat())
+    }
+
+    fn hint(&self) -> String {
+        let example = match self.group_separator {
+            Some(group) => format!("1{}234{}50", group, self.decimal_separator),
+            None => format!("1234{}50", self.decimal_separator),
+        };
+        format!("write the amount as digits, e.g. {}", example)
+    }
+}
+
+impl Validator<Amount> for MoneyValidator {
+    fn validate(&self, input: &str) -> Result<Amount, ValidationError> {
+        self.check(input)
+            .map_err(|hint| ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "amount"
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{error::ValidationError, validation::Validator};
+
+/// Longest input considered, in bytes
+const MAX_LEN: usize = 32;
+
+/// Validates international phone numbers, returning them in E.164 form such as `+14155550123`
+///
+/// Numbers start with `+` or the international prefix `00`, followed by a
+/// country code that does not start with 0 and 7-15 digits in total.
+/// Spaces, dashes, dots and parentheses may group the digits.
+/// Only the structure is checked; whether the number is assigned is not.
+///
+/// ```rust
+/// use huginn::validators::PhoneValidator;
+/// use huginn::Validator;
+///
+/// let validator = PhoneValidator::new();
+/// assert_eq!(validator.validate("+1 (415) 555-0123").unwrap(), "+14155550123");
+/// assert_eq!(validator.validate("0049 30 901820").unwrap(), "+4930901820");
+/// assert!(validator.validate("415 555 0123").is_err());
+/// assert!(validator.validate("+0 123 456 789").is_err());
+/// assert!(validator.validate("+1 415 555 0123 45678").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhoneValidator;
+
+impl PhoneValidator {
+    /// Creates validator
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn check(&self, input: &str) -> Result<String, &'static str> {
+        if input.len() > MAX_LEN {
+            return Err("shorten the phone number");
+        }
+        let input = input.trim();
+        let rest = input
+            .strip_prefix('+')
+            .or_else(|| input.strip_prefix("00"))
+            .ok_or("start with + and the country code")?;
+
+        let mut digits = String::from("+");
+        let mut open = false;
+        for c in rest.chars() {
+            match c {
+                '0'..='9' => digits.push(c),
+                ' ' | '-' | '.' => {}
+                '(' if !open => open = true,
+                ')' if open => open = false,
+                _ => return Err("use only digits, spaces, dashes, dots and parentheses"),
+            }
+        }
+        if open {
+            return Err("close the parenthesis");
+        }
+        if digits.starts_with("+0") {
+            return Err("start the country code with a digit other than 0");
+        }
+        if !(8..=16).contains(&digits.len()) {
+            return Err("use 7 to 15 digits including the country code");
+        }
+        Ok(digits)
+    }
+}
+
+impl Validator<String> for PhoneValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        self.check(input)
+            .map_err(|hint| ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "phone number"
+    }
+}
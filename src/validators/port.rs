@@ -0,0 +1,78 @@
+use crate::{error::ValidationError, validation::Validator};
+
+/// First port outside the well-known range 0-1023
+const FIRST_REGISTERED_PORT: u16 = 1024;
+
+/// Validates TCP/UDP port numbers, returning them as `u16`
+///
+/// Ports 1-65535 are accepted by default, written as plain decimal digits
+/// without sign or leading zeros. [`exclude_well_known`](Self::exclude_well_known)
+/// rejects the ports below 1024 that need privileges to bind, and
+/// [`with_min`](Self::with_min) raises the lower bound further.
+///
+/// ```rust
+/// use huginn::validators::PortValidator;
+/// use huginn::Validator;
+///
+/// let validator = PortValidator::new();
+/// assert_eq!(validator.validate("443").unwrap(), 443);
+/// assert!(validator.validate("0").is_err());
+/// assert!(validator.validate("65536").is_err());
+/// assert!(validator.validate("080").is_err());
+///
+/// let unprivileged = PortValidator::new().exclude_well_known();
+/// assert!(unprivileged.validate("80").is_err());
+/// assert_eq!(unprivileged.validate("8080").unwrap(), 8080);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortValidator {
+    min: u16,
+}
+
+impl Default for PortValidator {
+    fn default() -> Self {
+        Self { min: 1 }
+    }
+}
+
+impl PortValidator {
+    /// Creates validator accepting ports 1-65535
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejects the well-known ports 1-1023
+    pub fn exclude_well_known(self) -> Self {
+        self.with_min(FIRST_REGISTERED_PORT)
+    }
+
+    /// Sets the lowest accepted port; a minimum below the current one is ignored
+    pub fn with_min(mut self, min: u16) -> Self {
+        self.min = self.min.max(min);
+        self
+    }
+
+    fn check(&self, input: &str) -> Result<u16, String> {
+        if input.is_empty() || !input.bytes().all(|b| b.is_ascii_digit()) {
+            return Err("enter the port as a number".to_string());
+        }
+        if input.len() > 1 && input.starts_with('0') {
+            return Err("remove the leading zeros".to_string());
+        }
+        match input.parse::<u16>() {
+            Ok(port) if port >= self.min => Ok(port),
+            _ => Err(format!("use a port between {} and 65535", self.min)),
+        }
+    }
+}
+
+impl Validator<u16> for PortValidator {
+    fn validate(&self, input: &str) -> Result<u16, ValidationError> {
+        self.check(input)
+            .map_err(|hint| ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "port"
+    }
+}
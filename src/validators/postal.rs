@@ -0,0 +1,210 @@
+use crate::{error::ValidationError, validation::Validator};
+
+/// Postal code formats by ISO 3166-1 alpha-2 country code
+///
+/// In a format `9` stands for a digit, `A` for a letter and `?` for either;
+/// spaces and hyphens are separators placed in the canonical form.
+const FORMATS: &[(&str, &[&str])] = &[
+    ("AR", &["9999", "A9999AAA"]),
+    ("AT", &["9999"]),
+    ("AU", &["9999"]),
+    ("BE", &["9999"]),
+    ("BR", &["99999-999"]),
+    ("CA", &["A9A 9A9"]),
+    ("CH", &["9999"]),
+    ("CN", &["999999"]),
+    ("CZ", &["999 99"]),
+    ("DE", &["99999"]),
+    ("DK", &["9999"]),
+    ("ES", &["99999"]),
+    ("FI", &["99999"]),
+    ("FR", &["99999"]),
+    (
+        "GB",
+        &[
+            "A9 9AA", "A99 9AA", "A9A 9AA", "AA9 9AA", "AA99 9AA", "AA9A 9AA",
+        ],
+    ),
+    ("GR", &["999 99"]),
+    ("HU", &["9999"]),
+    ("IE", &["A99 ????", "A9A ????"]),
+    ("IL", &["9999999"]),
+    ("IN", &["999999"]),
+    ("IT", &["99999"]),
+    ("JP", &["999-9999"]),
+    ("KR", &["99999"]),
+    ("MX", &["99999"]),
+    ("NL", &["9999 AA"]),
+    ("NO", &["9999"]),
+    ("NZ", &["9999"]),
+    ("PL", &["99-999"]),
+    ("PT", &["9999-999"]),
+    ("RU", &["999999"]),
+    ("SE", &["999 99"]),
+    ("SG", &["999999"]),
+    ("SK", &["999 99"]),
+    ("TR", &["99999"]),
+    ("UA", &["99999"]),
+    ("US", &["99999", "99999-9999"]),
+    ("ZA", &["9999"]),
+];
+
+/// Longest input considered, in bytes
+const MAX_LEN: usize = 16;
+
+/// Validates postal codes against the format of a country
+///
+/// Codes are compared without their spaces and hyphens and returned in the
+/// country's canonical form with letters uppercased, so `sw1a1aa` becomes
+/// `SW1A 1AA` for `GB`. Countries without a built-in format, and
+/// [`PostalCodeValidator::new`], fall back to a generic check: 2 to 10
+/// letters and digits, optionally separated by single spaces or hyphens.
+///
+/// ```rust
+/// use huginn::validators::PostalCodeValidator;
+/// use huginn::Validator;
+///
+/// let germany = PostalCodeValidator::for_country("DE");
+/// assert_eq!(germany.validate("10115").unwrap(), "10115");
+/// assert!(germany.validate("1011").is_err());
+///
+/// let uk = PostalCodeValidator::for_country("gb");
+/// assert_eq!(uk.validate("sw1a1aa").unwrap(), "SW1A 1AA");
+/// assert!(uk.validate("SW1A 1A").is_err());
+///
+/// let us = PostalCodeValidator::for_country("US");
+/// assert_eq!(us.validate("12345 6789").unwrap(), "12345-6789");
+///
+/// let elsewhere = PostalCodeValidator::for_country("XX");
+/// assert_eq!(elsewhere.country(), None);
+/// assert_eq!(elsewhere.validate("ab-123").unwrap(), "AB-123");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PostalCodeValidator {
+    country: Option<&'static str>,
+    formats: &'static [&'static str],
+}
+
+impl PostalCodeValidator {
+    /// Creates validator applying the generic check
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates validator for an ISO 3166-1 alpha-2 country code, case-insensitive
+    ///
+    /// Unknown countries use the generic check.
+    pub fn for_country(country: &str) -> Self {
+        FORMATS
+            .iter()
+            .find(|(code, _)| code.eq_ignore_ascii_case(country))
+            .map_or_else(Self::default, |(code, formats)| Self {
+                country: Some(code),
+                formats,
+            })
+    }
+
+    /// Returns the country whose format is applied, `None` for the generic check
+    pub fn country(&self) -> Option<&'static str> {
+        self.country
+    }
+
+    /// Returns the codes of the countries with a built-in format
+    pub fn countries() -> impl Iterator<Item = &'static str> {
+        FORMATS.iter().map(|(code, _)| *code)
+    }
+
+    fn check(&self, input: &str) -> Result<String, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err("enter a postal code".into());
+        }
+        if input.len() > MAX_LEN
+            || !input
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b' ' || b == b'-')
+        {
+            return Err("use only letters, digits, spaces and hyphens".into());
+        }
+
+        if self.formats.is_empty() {
+            return generic(input);
+        }
+        let compact: Vec<u8> = input
+            .bytes()
+            .filter(u8::is_ascii_alphanumeric)
+            .map(|b| b.to_ascii_uppercase())
+            .collect();
+        self.formats
+            .iter()
+            .find_map(|format| apply(format, &compact))
+            .ok_or_else(|| describe(self.formats))
+    }
+}
+
+impl Validator<String> for PostalCodeValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        self.check(input)
+            .map_err(|hint| ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "postal code"
+    }
+}
+
+/// Lays the compact code out in the format, `None` if it does not fit
+fn apply(format: &str, compact: &[u8]) -> Option<String> {
+    let mut code = compact.iter();
+    let mut out = String::with_capacity(format.len());
+    for slot in format.bytes() {
+        match slot {
+            b' ' | b'-' => out.push(slot as char),
+            _ => {
+                let &c = code.next()?;
+                let fits = match slot {
+                    b'9' => c.is_ascii_digit(),
+                    b'A' => c.is_ascii_alphabetic(),
+                    _ => true,
+                };
+                if !fits {
+                    return None;
+                }
+                out.push(c as char);
+            }
+        }
+    }
+    code.next().is_none().then_some(out)
+}
+
+/// Accepts 2 to 10 letters and digits with single separators between them
+fn generic(input: &str) -> Result<String, String> {
+    let alphanumerics = input.bytes().filter(u8::is_ascii_alphanumeric).count();
+    if !(2..=10).contains(&alphanumerics) {
+        return Err("enter 2 to 10 letters and digits".into());
+    }
+    let separated = input.split([' ', '-']).all(|part| !part.is_empty());
+    if !separated {
+        return Err("use single spaces or hyphens only between letters and digits".into());
+    }
+    Ok(input.to_ascii_uppercase())
+}
+
+/// Describes formats for hints, such as `use the format 99-999, where 9 is a digit`
+fn describe(formats: &[&str]) -> String {
+    let slots = formats.concat();
+    let legend: Vec<&str> = [
+        ('9', "9 is a digit"),
+        ('A', "A a letter"),
+        ('?', "? a letter or digit"),
+    ]
+    .iter()
+    .filter(|(slot, _)| slots.contains(*slot))
+    .map(|(_, meaning)| *meaning)
+    .collect();
+    format!(
+        "use the format {}, where {}",
+        formats.join(" or "),
+        legend.join(", ")
+    )
+}
@@ -0,0 +1,216 @@
+use crate::{error::ValidationError, validation::Validator};
+
+/// ASCII spellings of common accented Latin letters
+const FOLDS: &[(char, &str)] = &[
+    ('à', "a"),
+    ('á', "a"),
+    ('â', "a"),
+    ('ã', "a"),
+    ('ä', "a"),
+    ('å', "a"),
+    ('ā', "a"),
+    ('ą', "a"),
+    ('æ', "ae"),
+    ('ç', "c"),
+    ('ć', "c"),
+    ('č', "c"),
+    ('ď', "d"),
+    ('đ', "d"),
+    ('ð', "d"),
+    ('è', "e"),
+    ('é', "e"),
+    ('ê', "e"),
+    ('ë', "e"),
+    ('ē', "e"),
+    ('ę', "e"),
+    ('ě', "e"),
+    ('ğ', "g"),
+    ('ì', "i"),
+    ('í', "i"),
+    ('î', "i"),
+    ('ï', "i"),
+    ('ī', "i"),
+    ('ı', "i"),
+    ('ł', "l"),
+    ('ñ', "n"),
+    ('ń', "n"),
+    ('ň', "n"),
+    ('ò', "o"),
+    ('ó', "o"),
+    ('ô', "o"),
+    ('õ', "o"),
+    ('ö', "o"),
+    ('ø', "o"),
+    ('ō', "o"),
+    ('ő', "o"),
+    ('œ', "oe"),
+    ('ř', "r"),
+    ('ś', "s"),
+    ('š', "s"),
+    ('ş', "s"),
+    ('ß', "ss"),
+    ('ť', "t"),
+    ('þ', "th"),
+    ('ù', "u"),
+    ('ú', "u"),
+    ('û', "u"),
+    ('ü', "u"),
+    ('ū', "u"),
+    ('ů', "u"),
+    ('ű', "u"),
+    ('ý', "y"),
+    ('ÿ', "y"),
+    ('ź', "z"),
+    ('ż', "z"),
+    ('ž', "z"),
+];
+
+/// Default maximum slug length in bytes
+const DEFAULT_MAX_LEN: usize = 200;
+
+/// Turns arbitrary text into a URL slug
+///
+/// Letters are lowercased and common accented Latin letters spelled in
+/// ASCII; every other run of characters becomes a single dash, and dashes
+/// are trimmed from both ends. The result may be empty.
+///
+/// ```rust
+/// use huginn::validators::slugify;
+///
+/// assert_eq!(slugify("Hello, World!"), "hello-world");
+/// assert_eq!(slugify("  Crème Brûlée -- 2024 "), "creme-brulee-2024");
+/// assert_eq!(slugify("Straße"), "strasse");
+/// ```
+pub fn slugify(input: &str) -> String {
+    let mut slug = String::with_capacity(input.len());
+    let mut pending_dash = false;
+    for c in input.chars().flat_map(char::to_lowercase) {
+        let folded = FOLDS
+            .iter()
+            .find(|(accented, _)| *accented == c)
+            .map(|(_, ascii)| *ascii);
+        if !(c.is_ascii_lowercase() || c.is_ascii_digit() || folded.is_some()) {
+            pending_dash = true;
+            continue;
+        }
+        if pending_dash && !slug.is_empty() {
+            slug.push('-');
+        }
+        pending_dash = false;
+        match folded {
+            Some(ascii) => slug.push_str(ascii),
+            None => slug.push(c),
+        }
+    }
+    slug
+}
+
+/// Validates URL slugs such as `my-first-post`
+///
+/// Slugs consist of lowercase ASCII letters, digits and single dashes
+/// between them, and are at most 200 bytes long by default. With
+/// [`normalize`](Self::normalize), input is passed through [`slugify`]
+/// first, so titles become slugs and only input without any letter or digit
+/// is rejected.
+///
+/// ```rust
+/// use huginn::validators::SlugValidator;
+/// use huginn::Validator;
+///
+/// let validator = SlugValidator::new();
+/// assert!(validator.validate("my-first-post").is_ok());
+/// assert!(validator.validate("My-First-Post").is_err());
+/// assert!(validator.validate("-draft").is_err());
+/// assert!(validator.validate("a--b").is_err());
+///
+/// let normalizing = SlugValidator::new().normalize();
+/// assert_eq!(normalizing.validate("My First Post!").unwrap(), "my-first-post");
+/// assert!(normalizing.validate("?!").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlugValidator {
+    max_len: usize,
+    normalize: bool,
+}
+
+impl Default for SlugValidator {
+    fn default() -> Self {
+        Self {
+            max_len: DEFAULT_MAX_LEN,
+            normalize: false,
+        }
+    }
+}
+
+impl SlugValidator {
+    /// Creates validator accepting only slugs, up to 200 bytes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum slug length in bytes
+    ///
+    /// When normalizing, longer slugs are cut at the last dash that fits
+    /// instead of being rejected.
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Turns input into a slug with [`slugify`] before checking it
+    pub fn normalize(mut self) -> Self {
+        self.normalize = true;
+        self
+    }
+
+    fn check(&self, input: &str) -> Result<String, &'static str> {
+        if self.normalize {
+            let slug = truncate(slugify(input), self.max_len);
+            if slug.is_empty() {
+                return Err("include at least one letter or digit");
+            }
+            return Ok(slug);
+        }
+
+        if input.is_empty() {
+            return Err("enter a slug");
+        }
+        if input.len() > self.max_len {
+            return Err("shorten the slug");
+        }
+        if !input
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+        {
+            return Err("use only lowercase letters a-z, digits and dashes");
+        }
+        if input.starts_with('-') || input.ends_with('-') || input.contains("--") {
+            return Err("use single dashes only between letters and digits");
+        }
+        Ok(input.to_string())
+    }
+}
+
+impl Validator<String> for SlugValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        self.check(input)
+            .map_err(|hint| ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "slug"
+    }
+}
+
+/// Cuts a slug to at most `max_len` bytes, at the last dash if a word would be split
+fn truncate(mut slug: String, max_len: usize) -> String {
+    if slug.len() <= max_len {
+        return slug;
+    }
+    let cut = match slug.as_bytes()[max_len] {
+        b'-' => max_len,
+        _ => slug[..max_len].rfind('-').unwrap_or(max_len),
+    };
+    slug.truncate(cut);
+    slug
+}
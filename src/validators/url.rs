@@ -0,0 +1,217 @@
+use crate::{error::ValidationError, validation::Validator};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use url::{Host, Url};
+
+/// Host names that resolve to the local machine or cloud metadata services
+const INTERNAL_SUFFIXES: [&str; 4] = ["localhost", "local", "internal", "localdomain"];
+
+/// Validates absolute URLs, optionally guarding against server-side request forgery
+///
+/// URLs are parsed the way browsers do, so hosts written as decimal, octal
+/// or hex numbers are normalized before they are checked, and the validated
+/// value is the normalized URL. Only `http` and `https` are accepted unless
+/// other schemes are configured.
+///
+/// With [`ssrf_protection`](Self::ssrf_protection) enabled, URLs whose host
+/// is a loopback, private, link-local (including the `169.254.169.254`
+/// metadata endpoint) or otherwise reserved address are rejected, as are
+/// `localhost` and names under `.local` and `.internal`, and every scheme
+/// other than `http` and `https`. The checks do not resolve names, so a
+/// public name pointing at a private address still passes; resolve and
+/// check again at connection time when that matters.
+///
+/// ```rust
+/// use huginn::validators::UrlValidator;
+/// use huginn::Validator;
+///
+/// let validator = UrlValidator::new().ssrf_protection(true);
+/// assert!(validator.validate("https://example.com/avatar.png").is_ok());
+/// assert!(validator.validate("http://169.254.169.254/latest/meta-data/").is_err());
+/// assert!(validator.validate("http://2130706433/admin").is_err());
+/// assert!(validator.validate("http://[::ffff:10.0.0.1]/").is_err());
+/// assert!(validator.validate("file:///etc/passwd").is_err());
+///
+/// let internal = UrlValidator::new().ssrf_protection(true).allow_host("billing.internal");
+/// assert!(internal.validate("http://billing.internal/invoices").is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlValidator {
+    max_len: usize,
+    schemes: Vec<String>,
+    ssrf_protection: bool,
+    allowed_hosts: Vec<String>,
+    denied_hosts: Vec<String>,
+}
+
+impl Default for UrlValidator {
+    fn default() -> Self {
+        Self {
+            max_len: 2048,
+            schemes: vec!["http".to_string(), "https".to_string()],
+            ssrf_protection: false,
+            allowed_hosts: Vec::new(),
+            denied_hosts: Vec::new(),
+        }
+    }
+}
+
+impl UrlValidator {
+    /// Creates validator accepting http and https URLs up to 2048 bytes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum URL length in bytes
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Replaces the accepted schemes
+    ///
+    /// With SSRF protection enabled, schemes other than `http` and `https`
+    /// are rejected regardless.
+    pub fn with_schemes<I, S>(mut self, schemes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.schemes = schemes
+            .into_iter()
+            .map(|scheme| scheme.into().to_ascii_lowercase())
+            .collect();
+        self
+    }
+
+    /// Rejects URLs targeting local, private and reserved hosts
+    pub fn ssrf_protection(mut self, enabled: bool) -> Self {
+        self.ssrf_protection = enabled;
+        self
+    }
+
+    /// Exempts a host from the SSRF checks, such as a known internal service
+    pub fn allow_host(mut self, host: &str) -> Self {
+        self.allowed_hosts.push(host.to_ascii_lowercase());
+        self
+    }
+
+    /// Rejects a host and its subdomains
+    pub fn deny_host(mut self, host: &str) -> Self {
+        self.denied_hosts.push(host.to_ascii_lowercase());
+        self
+    }
+
+    fn check(&self, url: &Url) -> Result<(), String> {
+        let scheme = url.scheme();
+        if !self.schemes.iter().any(|allowed| allowed == scheme)
+            || (self.ssrf_protection && scheme != "http" && scheme != "https")
+        {
+            return Err(format!(
+                "use one of the schemes: {}",
+                self.schemes.join(", ")
+            ));
+        }
+
+        let host = match url.host() {
+            Some(host) => host,
+            None if self.ssrf_protection => return Err("include a host name".into()),
+            None => return Ok(()),
+        };
+        let name = match &host {
+            Host::Domain(domain) => domain.trim_end_matches('.').to_string(),
+            Host::Ipv4(ip) => ip.to_string(),
+            Host::Ipv6(ip) => ip.to_string(),
+        };
+
+        if self
+            .denied_hosts
+            .iter()
+            .any(|denied| is_same_or_subdomain(&name, denied))
+        {
+            return Err("use a host that is not blocked".into());
+        }
+        if !self.ssrf_protection || self.allowed_hosts.contains(&name) {
+            return Ok(());
+        }
+
+        let internal = match host {
+            Host::Domain(_) => INTERNAL_SUFFIXES
+                .iter()
+                .any(|suffix| is_same_or_subdomain(&name, suffix)),
+            Host::Ipv4(ip) => !is_public_v4(ip),
+            Host::Ipv6(ip) => !is_public_v6(ip),
+        };
+        if internal {
+            return Err(
+                "use a public host; local, private and reserved addresses are not allowed".into(),
+            );
+        }
+        Ok(())
+    }
+}
+
+fn is_same_or_subdomain(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || a == 0
+        || a >= 240
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 192 && b == 0 && c == 0)
+        || (a == 198 && (18..20).contains(&b)))
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    if let Some(v4) = ip.to_ipv4_mapped() {
+        return is_public_v4(v4);
+    }
+    let segments = ip.segments();
+    match segments {
+        // IPv4-compatible and NAT64 addresses embed an IPv4 address
+        [0, 0, 0, 0, 0, 0, hi, lo] | [0x64, 0xff9b, 0, 0, 0, 0, hi, lo] => {
+            !ip.is_loopback()
+                && !ip.is_unspecified()
+                && is_public_v4(Ipv4Addr::from(((hi as u32) << 16) | lo as u32))
+        }
+        _ => {
+            !(ip.is_multicast()
+                || (segments[0] & 0xfe00) == 0xfc00
+                || (segments[0] & 0xffc0) == 0xfe80
+                || (segments[0] == 0x2001 && segments[1] == 0x0db8))
+        }
+    }
+}
+
+impl Validator<String> for UrlValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        let invalid =
+            |hint: &str| ValidationError::invalid_format(self.target_type()).with_hint(hint);
+
+        if input.len() > self.max_len {
+            return Err(invalid(&format!(
+                "shorten the URL to at most {} bytes",
+                self.max_len
+            )));
+        }
+        let url = Url::parse(input).map_err(|err| invalid(&format!("fix the URL: {}", err)))?;
+        self.check(&url).map_err(|hint| invalid(&hint))?;
+
+        Ok(url.into())
+    }
+
+    fn target_type(&self) -> &'static str {
+        "URL"
+    }
+}
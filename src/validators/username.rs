@@ -0,0 +1,92 @@
+use crate::{error::ValidationError, validation::Validator};
+
+/// Validates usernames such as `jane_doe` or `j.doe-42`
+///
+/// Usernames consist of ASCII letters, digits and the separators `_`, `-`
+/// and `.`; they start and end with a letter or digit, never contain two
+/// separators in a row, and are 3-32 characters long by default. Case is
+/// kept as given.
+///
+/// ```rust
+/// use huginn::validators::UsernameValidator;
+/// use huginn::Validator;
+///
+/// let validator = UsernameValidator::new();
+/// assert!(validator.validate("jane_doe").is_ok());
+/// assert!(validator.validate("j.doe-42").is_ok());
+/// assert!(validator.validate("_jane").is_err());
+/// assert!(validator.validate("jane..doe").is_err());
+/// assert!(validator.validate("jd").is_err());
+/// assert!(UsernameValidator::new().with_min_len(2).validate("jd").is_ok());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UsernameValidator {
+    min_len: usize,
+    max_len: usize,
+}
+
+impl Default for UsernameValidator {
+    fn default() -> Self {
+        Self {
+            min_len: 3,
+            max_len: 32,
+        }
+    }
+}
+
+impl UsernameValidator {
+    /// Creates validator accepting usernames of 3-32 characters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the minimum length
+    pub fn with_min_len(mut self, min_len: usize) -> Self {
+        self.min_len = min_len.max(1);
+        self
+    }
+
+    /// Sets the maximum length
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    fn check(&self, input: &str) -> Result<(), String> {
+        if !input
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-' | b'.'))
+        {
+            return Err("use only letters a-z, digits, '_', '-' and '.'".to_string());
+        }
+        if input.len() < self.min_len || input.len() > self.max_len {
+            return Err(format!(
+                "use {} to {} characters",
+                self.min_len, self.max_len
+            ));
+        }
+        let bytes = input.as_bytes();
+        let separator = |b: &u8| !b.is_ascii_alphanumeric();
+        if bytes.first().is_some_and(separator)
+            || bytes.last().is_some_and(separator)
+            || bytes.windows(2).any(|pair| pair.iter().all(separator))
+        {
+            return Err(
+                "start and end with a letter or digit, without separators in a row".to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Validator<String> for UsernameValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        self.check(input)
+            .map(|()| input.to_string())
+            .map_err(|hint| ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "username"
+    }
+}
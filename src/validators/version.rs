@@ -0,0 +1,58 @@
+use crate::{error::ValidationError, validation::Validator};
+use semver::VersionReq;
+
+/// Longest requirement accepted, in bytes
+const MAX_LEN: usize = 256;
+
+/// Validates version requirements such as `^1.4` or `>=1.2, <2.0`, returning a [`VersionReq`]
+///
+/// Requirements use Cargo's syntax: comma-separated comparators with the
+/// operators `=`, `>`, `>=`, `<`, `<=`, `~` and `^`, wildcards such as
+/// `1.*`, and bare versions meaning `^`.
+///
+/// The default configuration forbids `<` and `>`, so pipelines receiving
+/// range comparators need a configuration allowing them.
+///
+/// ```rust
+/// use huginn::validators::VersionReqValidator;
+/// use huginn::Validator;
+/// use semver::Version;
+///
+/// let validator = VersionReqValidator::new();
+/// let requirement = validator.validate(">=1.2, <2.0").unwrap();
+/// assert!(requirement.matches(&Version::new(1, 9, 3)));
+/// assert!(!requirement.matches(&Version::new(2, 0, 0)));
+///
+/// assert!(validator.validate("^1.4").is_ok());
+/// assert!(validator.validate("1.x.3").is_err());
+/// assert!(validator.validate(">= latest").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VersionReqValidator;
+
+impl VersionReqValidator {
+    /// Creates validator
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Validator<VersionReq> for VersionReqValidator {
+    fn validate(&self, input: &str) -> Result<VersionReq, ValidationError> {
+        let hint = if input.len() > MAX_LEN {
+            "shorten the version requirement"
+        } else if input.trim().is_empty() {
+            "enter a version requirement such as ^1.4"
+        } else {
+            match VersionReq::parse(input) {
+                Ok(requirement) => return Ok(requirement),
+                Err(_) => "write a version requirement such as ^1.4 or >=1.2, <2.0",
+            }
+        };
+        Err(ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "version requirement"
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{error::ValidationError, validation::Validator};
+use aho_corasick::{AhoCorasick, BuildError};
+use std::{fmt, fs, io, path::Path};
+use thiserror::Error;
+
+/// Characters read as the letters they resemble when normalizing leetspeak
+const LEET: &[(char, char)] = &[
+    ('0', 'o'),
+    ('1', 'i'),
+    ('3', 'e'),
+    ('4', 'a'),
+    ('5', 's'),
+    ('7', 't'),
+    ('8', 'b'),
+    ('9', 'g'),
+    ('@', 'a'),
+    ('$', 's'),
+    ('|', 'l'),
+];
+
+/// Errors raised while loading a word list
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum WordlistError {
+    /// The list could not be read
+    #[error("Cannot read word list: {0}")]
+    Io(#[from] io::Error),
+
+    /// The list is too large to search
+    #[error("Cannot build word list: {0}")]
+    Build(#[from] BuildError),
+}
+
+/// Validates that input contains none of the words on a list, such as profanity in display names
+///
+/// Words are matched case-insensitively anywhere in the input, or only as
+/// whole words with [`whole_words`](Self::whole_words), where any
+/// character other than a letter or digit separates words.
+/// [`normalize_leetspeak`](Self::normalize_leetspeak) reads digits and
+/// symbols in the input as the letters they resemble (`h3ll0` as `hello`),
+/// so list entries should be written in plain letters. All words are
+/// searched in a single pass, however long the list.
+///
+/// Lists hold one word per line; blank lines and lines starting with `#`
+/// are skipped. Embed one with [`from_list`](Self::from_list) and
+/// `include_str!`, or load it at startup with [`open`](Self::open).
+///
+/// ```rust
+/// use huginn::validators::WordlistValidator;
+/// use huginn::Validator;
+///
+/// let validator = WordlistValidator::from_list("# reserved\nadmin\nroot\n")?
+///     .whole_words()
+///     .normalize_leetspeak();
+/// assert!(validator.validate("jane_doe").is_ok());
+/// assert!(validator.validate("the admin").is_err());
+/// assert!(validator.validate("R00T").is_err());
+/// assert!(validator.validate("administrator").is_ok());
+/// assert_eq!(validator.find("4dmin!"), Some("admin"));
+/// # Ok::<(), huginn::validators::WordlistError>(())
+/// ```
+#[derive(Clone)]
+pub struct WordlistValidator {
+    words: Vec<String>,
+    automaton: AhoCorasick,
+    whole_words: bool,
+    leetspeak: bool,
+}
+
+impl fmt::Debug for WordlistValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WordlistValidator")
+            .field("words", &self.words.len())
+            .field("whole_words", &self.whole_words)
+            .field("leetspeak", &self.leetspeak)
+            .finish()
+    }
+}
+
+impl WordlistValidator {
+    /// Creates validator rejecting input containing any of the words
+    pub fn new<I, S>(words: I) -> Result<Self, WordlistError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let words: Vec<String> = words
+            .into_iter()
+            .map(|word| word.as_ref().trim().to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+        let automaton = AhoCorasick::new(&words)?;
+        Ok(Self {
+            words,
+            automaton,
+            whole_words: false,
+            leetspeak: false,
+        })
+    }
+
+    /// Creates validator from a list with one word per line
+    pub fn from_list(list: &str) -> Result<Self, WordlistError> {
+        Self::new(
+            list.lines()
+                .map(str::trim)
+                .filter(|line| !line.starts_with('#')),
+        )
+    }
+
+    /// Creates validator from a file with one word per line
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, WordlistError> {
+        Self::from_list(&fs::read_to_string(path)?)
+    }
+
+    /// Matches words only when they stand alone, not inside longer words
+    pub fn whole_words(mut self) -> Self {
+        self.whole_words = true;
+        self
+    }
+
+    /// Reads digits and symbols such as `0`, `3` and `@` as the letters they resemble
+    pub fn normalize_leetspeak(mut self) -> Self {
+        self.leetspeak = true;
+        self
+    }
+
+    /// Returns the listed word found in the input, if any
+    pub fn find(&self, input: &str) -> Option<&str> {
+        let text = self.normalize(input);
+        let is_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+        self.automaton
+            .find_overlapping_iter(&text)
+            .find(|found| {
+                !self.whole_words
+                    || !(is_word(text[..found.start()].chars().next_back())
+                        || is_word(text[found.end()..].chars().next()))
+            })
+            .map(|found| self.words[found.pattern().as_usize()].as_str())
+    }
+
+    fn normalize(&self, input: &str) -> String {
+        input
+            .chars()
+            .flat_map(char::to_lowercase)
+            .map(|c| match self.leetspeak {
+                true => LEET
+                    .iter()
+                    .find(|(symbol, _)| *symbol == c)
+                    .map_or(c, |(_, letter)| *letter),
+                false => c,
+            })
+            .collect()
+    }
+}
+
+impl Validator<String> for WordlistValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        match self.find(input) {
+            None => Ok(input.to_string()),
+            Some(_) => Err(ValidationError::invalid_format(self.target_type())
+                .with_hint("remove words that are not allowed")),
+        }
+    }
+
+    fn target_type(&self) -> &'static str {
+        "text"
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{error::ValidationError, validation::Validator};
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+
+/// Validates XML documents and fragments submitted as user input
+///
+/// Checks well-formedness and rejects DOCTYPE declarations, which is where
+/// external and recursively expanding entities (XXE, billion laughs) are
+/// defined, as well as processing instructions such as stylesheet links.
+/// Documents must have a single root element and stay within the size and
+/// nesting limits.
+///
+/// The default configuration forbids `<` and `>`, so validate XML with a
+/// configuration that allows markup.
+///
+/// ```rust
+/// use huginn::validators::XmlValidator;
+/// use huginn::{sanitize_and_validate, SecurityConfig};
+///
+/// let config = SecurityConfig::builder().build();
+/// let validator = XmlValidator::new();
+///
+/// let order = "<order id=\"7\"><item>tea</item></order>";
+/// assert!(sanitize_and_validate(order, &validator, &config).is_ok());
+///
+/// let xxe = r#"<!DOCTYPE r [<!ENTITY x SYSTEM "file:///etc/passwd">]><r>&x;</r>"#;
+/// assert!(sanitize_and_validate(xxe, &validator, &config).is_err());
+/// assert!(sanitize_and_validate("<a><b></a>", &validator, &config).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XmlValidator {
+    max_len: usize,
+    max_depth: usize,
+}
+
+impl Default for XmlValidator {
+    fn default() -> Self {
+        Self {
+            max_len: 1 << 20,
+            max_depth: 32,
+        }
+    }
+}
+
+impl XmlValidator {
+    /// Creates validator allowing documents up to 1 MiB and 32 levels deep
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum document length in bytes
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Sets the maximum element nesting depth
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    fn check(&self, input: &str) -> Result<(), String> {
+        let mut reader = Reader::from_str(input);
+        let mut depth = 0usize;
+        let mut roots = 0usize;
+
+        loop {
+            let event = reader.read_event().map_err(|err| {
+                format!(
+                    "fix the markup near byte {}: {}",
+                    reader.error_position(),
+                    err
+                )
+            })?;
+            match event {
+                Event::DocType(_) => {
+                    return Err("remove the DOCTYPE declaration; entities are not allowed".into())
+                }
+                Event::PI(_) => return Err("remove processing instructions".into()),
+                Event::Start(_) | Event::Empty(_) if depth == 0 && roots > 0 => {
+                    return Err("wrap the content in a single root element".into());
+                }
+                Event::Start(start) => {
+                    check_attributes(&start)?;
+                    depth += 1;
+                    roots += usize::from(depth == 1);
+                    if depth > self.max_depth {
+                        return Err(format!(
+                            "nest elements at most {} levels deep",
+                            self.max_depth
+                        ));
+                    }
+                }
+                Event::Empty(start) => {
+                    check_attributes(&start)?;
+                    roots += usize::from(depth == 0);
+                }
+                Event::End(_) => depth = depth.saturating_sub(1),
+                Event::Text(text) => {
+                    let text = text
+                        .unescape()
+                        .map_err(|err| format!("fix the entity reference: {}", err))?;
+                    if depth == 0 && !text.trim().is_empty() {
+                        return Err("place text inside the root element".into());
+                    }
+                }
+                Event::Eof => break,
+                Event::CData(_) | Event::Comment(_) | Event::Decl(_) => {}
+            }
+        }
+
+        match (depth, roots) {
+            (0, 0) => Err("provide a root element".into()),
+            (0, _) => Ok(()),
+            _ => Err("close every opened element".into()),
+        }
+    }
+}
+
+fn check_attributes(start: &BytesStart<'_>) -> Result<(), String> {
+    for attribute in start.attributes() {
+        attribute
+            .map_err(|err| format!("fix the attribute: {}", err))?
+            .unescape_value()
+            .map_err(|err| format!("fix the entity reference: {}", err))?;
+    }
+    Ok(())
+}
+
+impl Validator<String> for XmlValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        if input.len() > self.max_len {
+            return Err(
+                ValidationError::invalid_format(self.target_type()).with_hint(format!(
+                    "shorten the document to at most {} bytes",
+                    self.max_len
+                )),
+            );
+        }
+
+        self.check(input)
+            .map(|_| input.to_string())
+            .map_err(|hint| ValidationError::invalid_format(self.target_type()).with_hint(hint))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "XML"
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{error::ValidationError, validation::Validator};
+
+/// Validates values placed into XPath queries, returning them as a quoted literal
+///
+/// XPath 1.0 string literals have no escape sequences, so the value is
+/// wrapped in whichever quote it does not contain, or split into a
+/// `concat()` call when it contains both. Insert the result directly, as in
+/// `//user[name={}]`, without adding quotes. Control characters that XML
+/// cannot represent are rejected.
+///
+/// ```rust
+/// use huginn::validators::XPathLiteralValidator;
+/// use huginn::Validator;
+///
+/// let validator = XPathLiteralValidator::new();
+/// assert_eq!(validator.validate("alice").unwrap(), "'alice'");
+/// assert_eq!(validator.validate("' or '1'='1").unwrap(), "\"' or '1'='1\"");
+/// assert_eq!(validator.validate(r#"x' or "1"="1"#).unwrap(), r#"concat('x', "'", ' or "1"="1')"#);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XPathLiteralValidator {
+    max_len: usize,
+}
+
+impl Default for XPathLiteralValidator {
+    fn default() -> Self {
+        Self { max_len: 1024 }
+    }
+}
+
+impl XPathLiteralValidator {
+    /// Creates validator allowing values up to 1024 bytes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum value length in bytes, before quoting
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Quotes a value as an XPath 1.0 string literal expression
+    pub fn quote(input: &str) -> String {
+        if !input.contains('\'') {
+            return format!("'{}'", input);
+        }
+        if !input.contains('"') {
+            return format!("\"{}\"", input);
+        }
+
+        let mut parts = Vec::new();
+        let mut rest = input;
+        while !rest.is_empty() {
+            let end = rest.find('\'').unwrap_or(rest.len());
+            if end == 0 {
+                parts.push("\"'\"".to_string());
+                rest = &rest[1..];
+            } else {
+                parts.push(format!("'{}'", &rest[..end]));
+                rest = &rest[end..];
+            }
+        }
+        format!("concat({})", parts.join(", "))
+    }
+}
+
+impl Validator<String> for XPathLiteralValidator {
+    fn validate(&self, input: &str) -> Result<String, ValidationError> {
+        let invalid =
+            |hint: &str| ValidationError::invalid_format(self.target_type()).with_hint(hint);
+
+        if input.len() > self.max_len {
+            return Err(invalid(&format!(
+                "shorten the value to at most {} bytes",
+                self.max_len
+            )));
+        }
+        if input
+            .chars()
+            .any(|c| c.is_control() && !matches!(c, '\t' | '\n' | '\r'))
+        {
+            return Err(invalid("remove control characters"));
+        }
+
+        Ok(Self::quote(input))
+    }
+
+    fn target_type(&self) -> &'static str {
+        "XPath literal"
+    }
+}
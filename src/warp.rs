@@ -0,0 +1,139 @@
+use super::{
+    config::SecurityConfig,
+    error::ValidationError,
+    query::raw_value,
+    validation::{sanitize_and_validate_async, Validator},
+};
+use ::warp::{
+    http::{header, StatusCode},
+    reject::{self, Reject, Rejection},
+    reply::{self, Reply, Response},
+    Filter,
+};
+use std::{fmt::Debug, sync::Arc};
+
+/// Filter extracting the next path segment through the pipeline
+///
+/// Rejections carry a [`SanitizedRejection`]; install [`handle_rejection`]
+/// with `recover` to answer them with `422 Unprocessable Entity` and the
+/// serialized [`ValidationError`].
+///
+/// ```rust
+/// use huginn::warp::{handle_rejection, sanitized_param};
+/// use huginn::{SecurityConfig, ValidationError, Validator};
+/// use warp::Filter;
+///
+/// struct Username;
+///
+/// impl Validator<String> for Username {
+///     fn validate(&self, input: &str) -> Result<String, ValidationError> {
+///         if input.chars().all(|c| c.is_ascii_alphanumeric()) {
+///             Ok(input.to_string())
+///         } else {
+///             Err(ValidationError::invalid_format(self.target_type()))
+///         }
+///     }
+///
+///     fn target_type(&self) -> &'static str {
+///         "username"
+///     }
+/// }
+///
+/// let config = SecurityConfig::default();
+/// let routes = warp::path("users")
+///     .and(sanitized_param(Username, &config))
+///     .map(|name: String| format!("Hello, {}", name))
+///     .recover(handle_rejection);
+/// ```
+pub fn sanitized_param<T, V>(
+    validator: V,
+    config: &SecurityConfig,
+) -> impl Filter<Extract = (T,), Error = Rejection> + Clone
+where
+    T: Debug + Send + Sync + 'static,
+    V: Validator<T> + 'static,
+{
+    let validator = Arc::new(validator);
+    let config = config.clone();
+    ::warp::path::param::<String>().and_then(move |raw: String| {
+        let validator = Arc::clone(&validator);
+        let config = config.clone();
+        async move { validate(&raw, &*validator, &config).await }
+    })
+}
+
+/// Filter extracting a named query string parameter through the pipeline
+///
+/// The value is taken from the raw query string, so the pipeline decodes it
+/// exactly once.
+pub fn sanitized_query<T, V>(
+    name: &'static str,
+    validator: V,
+    config: &SecurityConfig,
+) -> impl Filter<Extract = (T,), Error = Rejection> + Clone
+where
+    T: Debug + Send + Sync + 'static,
+    V: Validator<T> + 'static,
+{
+    let validator = Arc::new(validator);
+    let config = config.clone();
+    ::warp::query::raw()
+        .or(::warp::any().map(String::new))
+        .unify()
+        .and_then(move |query: String| {
+            let validator = Arc::clone(&validator);
+            let config = config.clone();
+            let raw = raw_value(&query, name);
+            async move {
+                let raw =
+                    raw.ok_or_else(|| reject::custom(SanitizedRejection::MissingField(name)))?;
+                validate(&raw, &*validator, &config).await
+            }
+        })
+}
+
+async fn validate<T>(
+    raw: &str,
+    validator: &impl Validator<T>,
+    config: &SecurityConfig,
+) -> Result<T, Rejection>
+where
+    T: Debug + Send + Sync,
+{
+    sanitize_and_validate_async(raw, validator, config)
+        .await
+        .map(|result| result.cleaned)
+        .map_err(|err| reject::custom(SanitizedRejection::Invalid(err)))
+}
+
+/// Rejection produced by the huginn filters
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SanitizedRejection {
+    /// The value failed sanitization or validation
+    Invalid(ValidationError),
+    /// The request did not contain the field
+    MissingField(&'static str),
+}
+
+impl Reject for SanitizedRejection {}
+
+/// Turns huginn rejections into responses, passing other rejections through
+pub async fn handle_rejection(err: Rejection) -> Result<Response, Rejection> {
+    match err.find::<SanitizedRejection>() {
+        Some(SanitizedRejection::Invalid(error)) => {
+            let response = match serde_json::to_string(error) {
+                Ok(body) => reply::with_header(body, header::CONTENT_TYPE, "application/json")
+                    .into_response(),
+                Err(_) => error.to_string().into_response(),
+            };
+            Ok(reply::with_status(response, StatusCode::UNPROCESSABLE_ENTITY).into_response())
+        }
+        Some(SanitizedRejection::MissingField(name)) => Ok(reply::with_status(
+            format!("Missing field `{}`", name),
+            StatusCode::BAD_REQUEST,
+        )
+        .into_response()),
+        None => Err(err),
+    }
+}
@@ -0,0 +1,163 @@
+use super::{
+    config::SecurityConfig,
+    error::ValidationError,
+    validation::{prepare_input, sanitize_and_validate, Validator},
+    validators::{
+        CookieNameValidator, CookieValueValidator, EmailHeaderValidator, EmailValidator,
+        FilenameValidator, HeaderValueValidator, HostnameValidator, ShellArgValidator,
+        UrlValidator,
+    },
+};
+use wasm_bindgen::prelude::*;
+
+/// Names accepted by the `preset` argument
+const PRESETS: [&str; 4] = ["default", "headers", "sql", "shell"];
+
+/// Names accepted by the `validator` argument of [`validate`]
+const VALIDATORS: [&str; 9] = [
+    "cookie_name",
+    "cookie_value",
+    "email",
+    "email_header",
+    "filename",
+    "header",
+    "hostname",
+    "shell_arg",
+    "url",
+];
+
+/// Outcome of a check, shaped for form feedback in the browser
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    cleaned: Option<String>,
+    error: Option<ValidationError>,
+}
+
+#[wasm_bindgen]
+impl CheckResult {
+    /// True if the input passed every check
+    #[wasm_bindgen(getter)]
+    pub fn valid(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Sanitized value, if the input passed
+    #[wasm_bindgen(getter)]
+    pub fn cleaned(&self) -> Option<String> {
+        self.cleaned.clone()
+    }
+
+    /// Stable error code such as `HUG-020`, if the input was rejected
+    #[wasm_bindgen(getter)]
+    pub fn code(&self) -> Option<String> {
+        self.error.as_ref().map(|error| error.code().to_string())
+    }
+
+    /// Error message, if the input was rejected
+    #[wasm_bindgen(getter)]
+    pub fn message(&self) -> Option<String> {
+        self.error.as_ref().map(ToString::to_string)
+    }
+
+    /// Suggestion for fixing the input, if the error has one
+    #[wasm_bindgen(getter)]
+    pub fn hint(&self) -> Option<String> {
+        self.error
+            .as_ref()
+            .and_then(|error| error.hint())
+            .map(str::to_string)
+    }
+}
+
+impl From<Result<String, ValidationError>> for CheckResult {
+    fn from(result: Result<String, ValidationError>) -> Self {
+        match result {
+            Ok(cleaned) => Self {
+                cleaned: Some(cleaned),
+                error: None,
+            },
+            Err(error) => Self {
+                cleaned: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+/// Decodes, strips and screens input with a preset configuration
+///
+/// Presets are `default`, `headers`, `sql` and `shell`, matching
+/// [`SecurityConfig::default`], [`SecurityConfig::for_headers`],
+/// [`SecurityConfig::for_sql_params`] and [`SecurityConfig::for_shell_args`].
+/// Throws for an unknown preset.
+#[wasm_bindgen]
+pub fn sanitize(input: &str, preset: &str) -> Result<CheckResult, JsError> {
+    let config = preset_config(preset)?;
+    Ok(prepare_input(input, &config, &[])
+        .map(|cleaned| cleaned.into_owned())
+        .into())
+}
+
+/// Sanitizes input with a preset configuration and validates it with a built-in validator
+///
+/// Validators are named after the types in [`validators`](crate::validators),
+/// such as `email`, `filename` or `url`, each with its default settings.
+/// Throws for an unknown preset or validator.
+#[wasm_bindgen]
+pub fn validate(input: &str, validator: &str, preset: &str) -> Result<CheckResult, JsError> {
+    let config = preset_config(preset)?;
+    let result = match validator {
+        "cookie_name" => run(input, &CookieNameValidator, &config),
+        "cookie_value" => run(input, &CookieValueValidator::new(), &config),
+        "email" => run(input, &EmailValidator::new(), &config),
+        "email_header" => run(input, &EmailHeaderValidator::new(), &config),
+        "filename" => run(input, &FilenameValidator::new(), &config),
+        "header" => run(input, &HeaderValueValidator::new(), &config),
+        "hostname" => run(input, &HostnameValidator::new(), &config),
+        "shell_arg" => run(input, &ShellArgValidator::new(), &config),
+        "url" => run(input, &UrlValidator::new(), &config),
+        other => {
+            return Err(JsError::new(&format!(
+                "unknown validator '{}'; use one of: {}",
+                other,
+                VALIDATORS.join(", ")
+            )))
+        }
+    };
+    Ok(result.into())
+}
+
+/// Returns the names of the preset configurations
+#[wasm_bindgen]
+pub fn presets() -> Vec<String> {
+    PRESETS.iter().map(|name| name.to_string()).collect()
+}
+
+/// Returns the names of the built-in validators
+#[wasm_bindgen]
+pub fn validators() -> Vec<String> {
+    VALIDATORS.iter().map(|name| name.to_string()).collect()
+}
+
+fn run(
+    input: &str,
+    validator: &impl Validator<String>,
+    config: &SecurityConfig,
+) -> Result<String, ValidationError> {
+    sanitize_and_validate(input, validator, config).map(|sanitized| sanitized.cleaned)
+}
+
+fn preset_config(preset: &str) -> Result<SecurityConfig, JsError> {
+    match preset {
+        "default" => Ok(SecurityConfig::default()),
+        "headers" => Ok(SecurityConfig::for_headers()),
+        "sql" => Ok(SecurityConfig::for_sql_params()),
+        "shell" => Ok(SecurityConfig::for_shell_args()),
+        other => Err(JsError::new(&format!(
+            "unknown preset '{}'; use one of: {}",
+            other,
+            PRESETS.join(", ")
+        ))),
+    }
+}
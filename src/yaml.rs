@@ -0,0 +1,223 @@
+use super::{
+    config::SecurityConfig,
+    error::{ValidationError, ValidationErrors},
+    validation::prepare_input,
+};
+use std::collections::HashMap;
+use yaml_rust2::parser::{Event, Parser};
+
+/// Limits applied to YAML documents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YamlLimits {
+    /// Maximum document length in bytes
+    pub max_len: usize,
+    /// Maximum nesting depth of mappings and sequences
+    pub max_depth: usize,
+    /// Maximum number of aliases
+    pub max_aliases: usize,
+    /// Maximum number of nodes once every alias is expanded
+    pub max_expanded_nodes: u64,
+}
+
+impl Default for YamlLimits {
+    fn default() -> Self {
+        Self {
+            max_len: 64 << 10,
+            max_depth: 32,
+            max_aliases: 100,
+            max_expanded_nodes: 10_000,
+        }
+    }
+}
+
+enum Frame {
+    Mapping {
+        key: Option<String>,
+        expect_key: bool,
+    },
+    Sequence {
+        index: usize,
+    },
+}
+
+struct Open {
+    frame: Frame,
+    anchor: usize,
+    nodes: u64,
+}
+
+/// Screens a YAML document without constructing it
+///
+/// Aliases are costed by the size of the node they refer to, so documents
+/// that expand exponentially ("billion laughs") are rejected before any
+/// loader sees them. Every string scalar, key or value, runs through the
+/// pipeline stages that precede the validator, with errors keyed by path.
+///
+/// ```rust
+/// use huginn::yaml::{screen_yaml, YamlLimits};
+/// use huginn::SecurityConfig;
+///
+/// let config = SecurityConfig::default();
+/// let limits = YamlLimits::default();
+///
+/// assert!(screen_yaml("name: demo\nreplicas: 3\n", &config, &limits).is_ok());
+///
+/// let laughs = "a: &a [x, x, x, x, x, x, x, x, x, x]\n\
+///               b: &b [*a, *a, *a, *a, *a, *a, *a, *a, *a, *a]\n\
+///               c: &c [*b, *b, *b, *b, *b, *b, *b, *b, *b, *b]\n\
+///               d: &d [*c, *c, *c, *c, *c, *c, *c, *c, *c, *c]\n";
+/// assert!(screen_yaml(laughs, &config, &limits).is_err());
+///
+/// let errors = screen_yaml("hooks:\n  - cmd: rm -rf /\n", &config, &limits).unwrap_err();
+/// assert_eq!(errors.for_field("hooks[0].cmd").count(), 1);
+/// ```
+pub fn screen_yaml(
+    input: &str,
+    config: &SecurityConfig,
+    limits: &YamlLimits,
+) -> Result<(), ValidationErrors> {
+    if input.len() > limits.max_len {
+        return Err(document_error(
+            ValidationError::custom("YAML document is too long")
+                .with_hint(format!("send at most {} bytes", limits.max_len)),
+        ));
+    }
+
+    let mut parser = Parser::new_from_str(input);
+    let mut stack: Vec<Open> = Vec::new();
+    let mut anchors: HashMap<usize, u64> = HashMap::new();
+    let mut aliases = 0usize;
+    let mut total = 0u64;
+    let mut errors = ValidationErrors::new();
+
+    loop {
+        let (event, marker) = parser.next_token().map_err(|err| {
+            document_error(
+                ValidationError::invalid_format("YAML document").with_hint(err.to_string()),
+            )
+        })?;
+
+        let completed = match event {
+            Event::StreamEnd => break,
+            Event::Scalar(value, _, anchor, _) => {
+                if let Err(error) = prepare_input(&value, config, &[]) {
+                    errors.push_field(scalar_path(&stack, &value), error);
+                }
+                Some((1, anchor, Some(value)))
+            }
+            Event::Alias(anchor) => {
+                aliases += 1;
+                if aliases > limits.max_aliases {
+                    return Err(document_error(
+                        ValidationError::custom("YAML document uses too many aliases")
+                            .with_hint(format!("use at most {} aliases", limits.max_aliases)),
+                    ));
+                }
+                Some((anchors.get(&anchor).copied().unwrap_or(1), 0, None))
+            }
+            Event::MappingStart(anchor, _) | Event::SequenceStart(anchor, _) => {
+                if stack.len() >= limits.max_depth {
+                    return Err(document_error(
+                        ValidationError::custom("YAML document is nested too deeply")
+                            .with_hint(format!("nest at most {} levels deep", limits.max_depth)),
+                    ));
+                }
+                let frame = match event {
+                    Event::MappingStart(..) => Frame::Mapping {
+                        key: None,
+                        expect_key: true,
+                    },
+                    _ => Frame::Sequence { index: 0 },
+                };
+                stack.push(Open {
+                    frame,
+                    anchor,
+                    nodes: 1,
+                });
+                None
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                stack.pop().map(|open| (open.nodes, open.anchor, None))
+            }
+            _ => None,
+        };
+
+        let Some((nodes, anchor, scalar)) = completed else {
+            continue;
+        };
+        if anchor != 0 {
+            anchors.insert(anchor, nodes);
+        }
+
+        match stack.last_mut() {
+            Some(parent) => {
+                parent.nodes = parent.nodes.saturating_add(nodes);
+                advance(&mut parent.frame, scalar);
+            }
+            None => total = total.saturating_add(nodes),
+        }
+
+        let pending: u64 = stack.iter().map(|open| open.nodes).sum();
+        if total.saturating_add(pending) > limits.max_expanded_nodes {
+            return Err(document_error(
+                ValidationError::custom("YAML aliases expand to too many nodes").with_hint(
+                    format!(
+                        "keep the expanded document under {} nodes (line {})",
+                        limits.max_expanded_nodes,
+                        marker.line()
+                    ),
+                ),
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn document_error(error: ValidationError) -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors.push_field("document", error);
+    errors
+}
+
+/// Moves a container past a completed child node
+fn advance(frame: &mut Frame, scalar: Option<String>) {
+    match frame {
+        Frame::Mapping { key, expect_key } => {
+            if *expect_key {
+                *key = Some(scalar.unwrap_or_else(|| "?".to_string()));
+            }
+            *expect_key = !*expect_key;
+        }
+        Frame::Sequence { index } => *index += 1,
+    }
+}
+
+/// Renders the path of a scalar about to be added to the innermost container
+fn scalar_path(stack: &[Open], scalar: &str) -> String {
+    let mut path = String::new();
+    for (depth, open) in stack.iter().enumerate() {
+        let innermost = depth + 1 == stack.len();
+        match &open.frame {
+            Frame::Mapping { key, expect_key } => {
+                let segment = match (innermost, *expect_key) {
+                    (true, true) => Some(scalar),
+                    (_, false) => key.as_deref(),
+                    (false, true) => None,
+                };
+                if let Some(segment) = segment {
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(segment);
+                }
+            }
+            Frame::Sequence { index } => path.push_str(&format!("[{}]", index)),
+        }
+    }
+    path
+}